@@ -9,6 +9,7 @@ import {
   type TripCandidate 
 } from '@/lib/services/prefilter';
 import { computeTripMatch } from '@/lib/arcium/compute-match';
+import { matchProfileAccounts } from '@/lib/solana/match-actions';
 import type { TripData } from '@/lib/arcium/encryption';
 import type { Match, Trip } from '@/types';
 import IDL from '@/lib/anchor/triper.json';
@@ -212,12 +213,15 @@ export function useMatches() {
       
       const matchPubkey = new PublicKey(matchId);
       const matchAccount = await program.account.matchRecord.fetch(matchPubkey);
+      const profiles = await matchProfileAccounts(program, matchPubkey);
       
       await (program.methods as any)
         .acceptMatch()
         .accounts({
           matchAccount: matchPubkey,
           trip: matchAccount.tripA, // or tripB depending on user
+          counterpartyTrip: matchAccount.tripB,
+          ...profiles,
           user: wallet.publicKey,
         })
         .rpc();
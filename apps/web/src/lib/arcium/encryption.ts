@@ -7,7 +7,7 @@
 
 import { x25519, RescueCipher, getMXEPublicKey } from '@arcium-hq/client';
 import { AnchorProvider, web3 } from '@coral-xyz/anchor';
-import { waypointsToH3Cells, h3ToU64, computeDestinationHash } from '@/lib/geo/h3';
+import { waypointsToH3Cells, h3ToU64, computeDestinationHash, validateRouteContinuity } from '@/lib/geo/h3';
 import type { H3Index, Waypoint, InterestTag } from '@/types';
 
 // Constants from Rust circuit
@@ -24,12 +24,20 @@ const MAX_INTERESTS = 32; // DEPRECATED: Now in UserProfile
  * pub struct WaypointData {
  *     waypoints: [u64; 20],      // H3 cells at resolution 7
  *     waypoint_count: u8,
+ *     min_interests: u8,         // Interest threshold for interest scoring
+ *     age: u8,                   // Traveler age (0 = undisclosed)
+ *     daily_budget: u32,         // USD cents per day (0 = undisclosed)
+ *     seeking: u32,              // Sought interests, bit i = InterestTag i
  * }
  * 
- * Total size: 20*8 + 1 = 161 bytes
+ * Total size: 20*8 + 1 + 1 + 1 + 4 + 4 = 171 bytes
  */
 export interface TripData {
   waypoints: Waypoint[];    // Will be converted to H3 cells and padded to 20
+  minInterests?: number;    // Both parties need this many interests for them to count (default 0)
+  age?: number;             // Traveler age in years; only scored when both trips set one (default 0)
+  dailyBudget?: number;     // Daily budget in USD cents; only scored when both trips set one (default 0)
+  seeking?: InterestTag[];  // Interests wanted in a companion, scored in both directions (default none)
 }
 
 /**
@@ -80,6 +88,7 @@ export async function initializeEncryption(
  * pub struct WaypointData {
  *     waypoints: [u64; 20],
  *     waypoint_count: u8,
+ *     min_interests: u8,
  * }
  * 
  * NOTE: Dates are PUBLIC (stored in Trip.start_date, Trip.end_date)
@@ -92,6 +101,9 @@ export function serializeTripData(data: TripData): bigint[] {
   const h3Cells = waypointsToH3Cells(data.waypoints);
   const actualWaypointCount = h3Cells.length;
   
+  // Reject teleporting routes - the program can't check ciphertext
+  validateRouteContinuity(h3Cells);
+  
   // Convert H3 cells to u64 and pad to MAX_WAYPOINTS
   for (let i = 0; i < MAX_WAYPOINTS; i++) {
     if (i < h3Cells.length) {
@@ -104,6 +116,23 @@ export function serializeTripData(data: TripData): bigint[] {
   // 2. Waypoint count (u8)
   serialized.push(BigInt(actualWaypointCount));
   
+  // 3. Minimum interest count for interest scoring (u8)
+  serialized.push(BigInt(data.minInterests ?? 0));
+  
+  // 4. Traveler age (u8, 0 = undisclosed)
+  serialized.push(BigInt(data.age ?? 0));
+  
+  // 5. Daily budget (u32 USD cents, 0 = undisclosed)
+  serialized.push(BigInt(data.dailyBudget ?? 0));
+  
+  // 6. Sought interests, packed as a u32 bitmask (0 = no preference)
+  let seekingMask = BigInt(0);
+  for (const interest of data.seeking ?? []) {
+    if (interest >= 0 && interest < 32) {
+      seekingMask |= BigInt(1) << BigInt(interest);
+    }
+  }
+  serialized.push(seekingMask);
   
   return serialized;
 }
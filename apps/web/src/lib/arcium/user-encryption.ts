@@ -192,6 +192,43 @@ export async function decryptUserData(
   };
 }
 
+/**
+ * Match scores as stored in MatchRecord.encrypted_scores_a / encrypted_scores_b
+ * (encrypt_scores mode). Mirrors MatchScores in encrypted-ixs/src/trip_matching.rs
+ */
+export interface MatchScores {
+  routeScore: number;
+  dateScore: number;
+  interestScore: number;
+  ageScore: number;
+  budgetScore: number;
+  totalScore: number;
+  coverageScore: number;
+}
+
+/**
+ * Decrypt a participant's copy of encrypted match scores
+ * Only works with the cipher of the participant's own profile key:
+ * trip_a's owner reads encryptedScoresA, trip_b's owner reads encryptedScoresB
+ */
+export function decryptMatchScores(
+  encryptedScores: { nonce: { toString(): string }; ciphertexts: number[][] },
+  cipher: RescueCipher
+): MatchScores {
+  // Nonce is stored on-chain as a little-endian u128
+  const nonce = new Uint8Array(16);
+  let value = BigInt(encryptedScores.nonce.toString());
+  for (let i = 0; i < 16; i++) {
+    nonce[i] = Number(value & BigInt(0xFF));
+    value >>= BigInt(8);
+  }
+  
+  const [routeScore, dateScore, interestScore, ageScore, budgetScore, totalScore, coverageScore] =
+    cipher.decrypt(encryptedScores.ciphertexts, nonce).map(Number);
+  
+  return { routeScore, dateScore, interestScore, ageScore, budgetScore, totalScore, coverageScore };
+}
+
 /**
  * Helper: Convert InterestTag array to human-readable string
  */
@@ -11,7 +11,7 @@
  * - Hierarchical structure (easy coarse-graining)
  */
 
-import { latLngToCell, cellToLatLng, gridDisk, cellToParent } from 'h3-js';
+import { latLngToCell, cellToLatLng, gridDisk, cellToParent, gridDistance } from 'h3-js';
 import type { H3Index, Waypoint } from '@/types';
 
 // Resolution levels matching the MPC circuit
@@ -21,6 +21,10 @@ export const DESTINATION_RESOLUTION = 6; // ~36 km² (~7 km edge length)
 // Maximum waypoints supported by MPC circuit
 export const MAX_WAYPOINTS = 20;
 
+// Largest plausible jump between consecutive waypoints, in level-7 cells
+// (~2 km between neighboring cell centers, so ~1000 km)
+export const DEFAULT_MAX_GAP_CELLS = 500;
+
 /**
  * Convert lat/lng to H3 cell at waypoint resolution (level 7)
  * Used for route waypoints stored in encrypted TripData
@@ -71,6 +75,81 @@ export function waypointsToH3Cells(waypoints: Waypoint[]): H3Index[] {
   return Array.from(cells);
 }
 
+/**
+ * Reject routes whose consecutive cells are implausibly far apart
+ * 
+ * The program only ever sees ciphertext, so it cannot check this itself:
+ * clients MUST validate before encrypting. A "teleporting" route would
+ * otherwise inflate cell coverage across unrelated regions.
+ * 
+ * Cells too far apart for H3 to measure (different icosahedron faces)
+ * are treated as a gap that exceeds any limit.
+ * 
+ * @param cells - Route cells in travel order
+ * @param maxGapCells - Largest allowed grid distance between consecutive cells
+ * @throws Error naming the first offending segment
+ */
+export function validateRouteContinuity(
+  cells: H3Index[],
+  maxGapCells: number = DEFAULT_MAX_GAP_CELLS
+): void {
+  for (let i = 1; i < cells.length; i++) {
+    let gap: number;
+    try {
+      gap = gridDistance(cells[i - 1], cells[i]);
+    } catch {
+      gap = Infinity;
+    }
+    
+    if (gap > maxGapCells) {
+      throw new Error(
+        `Route jumps ${gap} cells between waypoints ${i - 1} and ${i} (max ${maxGapCells})`
+      );
+    }
+  }
+}
+
+// Route density scale - mirrors ROUTE_DENSITY_SCALE in the program
+export const ROUTE_DENSITY_SCALE = 10;
+
+/**
+ * Public inputs for the on-chain route_density hint (set_route_density)
+ * 
+ * areaBucket = floor(log2(bounding-box area in level-7 cells + 1)).
+ * The box is never smaller than the cells themselves, so straight
+ * north-south or east-west routes still get a plausible area.
+ * 
+ * @param cells - Route cells at WAYPOINT_RESOLUTION
+ */
+export function computeRouteDensityInputs(cells: H3Index[]): {
+  waypointCount: number;
+  areaBucket: number;
+} {
+  const points = cells.map(h3CellToLatLng);
+  const lats = points.map(p => p.lat);
+  const lngs = points.map(p => p.lng);
+  
+  const KM_PER_DEGREE = 111.32;
+  const midLat = (Math.max(...lats) + Math.min(...lats)) / 2;
+  const heightKm = (Math.max(...lats) - Math.min(...lats)) * KM_PER_DEGREE;
+  const widthKm = (Math.max(...lngs) - Math.min(...lngs)) * KM_PER_DEGREE * Math.cos(midLat * Math.PI / 180);
+  
+  const boxCells = (heightKm * widthKm) / getCellAreaKm2(WAYPOINT_RESOLUTION);
+  const areaCells = Math.max(boxCells, cells.length);
+  
+  return {
+    waypointCount: cells.length,
+    areaBucket: Math.floor(Math.log2(areaCells + 1)),
+  };
+}
+
+/**
+ * Route density as the program computes it from set_route_density inputs
+ */
+export function computeRouteDensity(waypointCount: number, areaBucket: number): number {
+  return Math.min(255, Math.floor((waypointCount * ROUTE_DENSITY_SCALE) / (areaBucket + 1)));
+}
+
 /**
  * Compute destination grid hash (level 6) for pre-filtering
  * Used as public index for finding potential matches
@@ -108,30 +187,91 @@ export function getNeighboringCells(h3Index: H3Index, radius: number = 1): H3Ind
   }
 }
 
+// Level-7 cells under the same level-6 parent differ only in the level-7
+// digit (bits 24-26) - mirrors H3_PARENT_SHIFT in the circuit
+const H3_PARENT_SHIFT = BigInt(27);
+
+// Credit (percent of an exact match) for cells sharing only a level-6
+// parent - mirrors PARENT_MATCH_CREDIT_PCT in the circuit
+export const PARENT_MATCH_CREDIT_PCT = 50;
+
 /**
- * Calculate Jaccard similarity between two sets of H3 cells
- * Returns percentage 0-100
- * This matches the MPC circuit's route similarity algorithm
+ * Route similarity (0-100) with parent-cell fallback
+ * Mirrors compute_route_similarity in the MPC circuit, including integer
+ * flooring: exact level-7 matches count fully, cells that only share a
+ * level-6 parent count PARENT_MATCH_CREDIT_PCT, over the parent-level union
  */
 export function calculateRouteSimilarity(cellsA: H3Index[], cellsB: H3Index[]): number {
   if (cellsA.length === 0 || cellsB.length === 0) return 0;
   
-  const setA = new Set(cellsA);
-  const setB = new Set(cellsB);
-  
-  // Calculate intersection
-  let intersection = 0;
-  setA.forEach(cell => {
-    if (setB.has(cell)) intersection++;
-  });
+  const keysA = cellsA.map(h3ToU64);
+  const keysB = cellsB.map(h3ToU64);
+  const exact = intersectionCount(keysA, keysB);
+  const parent = intersectionCount(
+    keysA.map(k => k >> H3_PARENT_SHIFT),
+    keysB.map(k => k >> H3_PARENT_SHIFT)
+  );
   
-  // Calculate union
-  const union = setA.size + setB.size - intersection;
+  const union = keysA.length + keysB.length - parent;
+  const credited = exact * 100 + (parent - exact) * PARENT_MATCH_CREDIT_PCT;
+  return union === 0 ? 0 : Math.min(100, Math.floor(credited / union));
+}
+
+// Suggested share of the coarse (parent) Jaccard in the multi-res blend
+export const DEFAULT_COARSE_WEIGHT_PCT = 30;
+
+/**
+ * Multi-resolution route similarity (0-100)
+ * Mirrors compute_route_similarity_multires in the MPC circuit, including
+ * integer flooring: the level-7 Jaccard blended with the Jaccard over
+ * level-6 parents, so near-misses at the fine resolution earn partial credit.
+ * 
+ * @param cellsA - Route cells at WAYPOINT_RESOLUTION (deduplicated)
+ * @param cellsB - Route cells at WAYPOINT_RESOLUTION (deduplicated)
+ * @param coarseWeightPct - Share of the parent-level score (0 = exact cells only)
+ */
+export function calculateRouteSimilarityMultires(
+  cellsA: H3Index[],
+  cellsB: H3Index[],
+  coarseWeightPct: number = DEFAULT_COARSE_WEIGHT_PCT
+): number {
+  if (cellsA.length === 0 || cellsB.length === 0) return 0;
   
-  if (union === 0) return 0;
+  const keysA = cellsA.map(h3ToU64);
+  const keysB = cellsB.map(h3ToU64);
+  const fine = jaccardPct(keysA, keysB);
+  const coarse = jaccardPct(
+    keysA.map(k => k >> H3_PARENT_SHIFT),
+    keysB.map(k => k >> H3_PARENT_SHIFT)
+  );
   
-  // Jaccard index as percentage
-  return Math.round((intersection / union) * 100);
+  const coarseW = Math.min(100, Math.max(0, coarseWeightPct));
+  return Math.min(100, Math.floor((fine * (100 - coarseW) + coarse * coarseW) / 100));
+}
+
+/**
+ * One-to-one matched keys between two lists (multiset intersection)
+ */
+function intersectionCount(keysA: bigint[], keysB: bigint[]): number {
+  const visited = new Array(keysA.length).fill(false);
+  let intersection = 0;
+  for (const key of keysB) {
+    const j = keysA.findIndex((candidate, index) => !visited[index] && candidate === key);
+    if (j >= 0) {
+      visited[j] = true;
+      intersection++;
+    }
+  }
+  return intersection;
+}
+
+/**
+ * Floored Jaccard percentage with one-to-one matching (multiset intersection)
+ */
+function jaccardPct(keysA: bigint[], keysB: bigint[]): number {
+  const intersection = intersectionCount(keysA, keysB);
+  const union = keysA.length + keysB.length - intersection;
+  return union === 0 ? 0 : Math.min(100, Math.floor((intersection * 100) / union));
 }
 
 /**
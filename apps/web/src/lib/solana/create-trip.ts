@@ -17,8 +17,89 @@ import {
   computePublicDestinationHash,
   type TripData,
 } from '../arcium/encryption';
+import { computeRouteDensityInputs, waypointsToH3Cells } from '../geo/h3';
+import { getUserProfilePDA } from './user-actions';
 import type { Waypoint, InterestTag } from '@/types';
 
+// Trips per destination index shard - mirrors DESTINATION_SHARD_CAPACITY
+export const DESTINATION_SHARD_CAPACITY = 32;
+
+/**
+ * Derive a destination index shard PDA
+ * Seeds: [b"dest", destination_grid_hash, shard_id (u16 LE)]
+ */
+export function deriveDestinationShardPDA(
+  programId: web3.PublicKey,
+  destinationGridHash: Uint8Array | number[],
+  shardId: number
+): web3.PublicKey {
+  const shardIdBytes = Buffer.alloc(2);
+  shardIdBytes.writeUInt16LE(shardId);
+  const [shardPDA] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('dest'), Buffer.from(destinationGridHash), shardIdBytes],
+    programId
+  );
+  return shardPDA;
+}
+
+/**
+ * Derive a user's payload registry PDA
+ * Seeds: [b"payload_registry", owner]
+ */
+export function derivePayloadRegistryPDA(
+  programId: web3.PublicKey,
+  owner: web3.PublicKey
+): web3.PublicKey {
+  const [registryPDA] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('payload_registry'), owner.toBuffer()],
+    programId
+  );
+  return registryPDA;
+}
+
+/**
+ * Derive a trip PDA from its owner and index
+ * Seeds: [b"trip", owner, trip_index (u32 LE)] - an owner's trips are
+ * indices 0..next_trip_index of their UserProfile
+ */
+export function deriveTripPDA(
+  programId: web3.PublicKey,
+  owner: web3.PublicKey,
+  tripIndex: number
+): web3.PublicKey {
+  const indexBytes = Buffer.alloc(4);
+  indexBytes.writeUInt32LE(tripIndex);
+  const [tripPDA] = web3.PublicKey.findProgramAddressSync(
+    [Buffer.from('trip'), owner.toBuffer(), indexBytes],
+    programId
+  );
+  return tripPDA;
+}
+
+/**
+ * Find the destination's open index shard - the one create_trip must write
+ * Shards fill in order, so the first shard with room is the least full and
+ * the shard before it (if any) is full
+ */
+export async function findOpenDestinationShard(
+  program: Program<Triper>,
+  destinationGridHash: Uint8Array | number[]
+): Promise<{
+  shardId: number;
+  destinationShard: web3.PublicKey;
+  previousShard: web3.PublicKey | null;
+}> {
+  let previousShard: web3.PublicKey | null = null;
+  for (let shardId = 0; ; shardId++) {
+    const destinationShard = deriveDestinationShardPDA(program.programId, destinationGridHash, shardId);
+    const shard = await program.account.destinationIndexShard.fetchNullable(destinationShard);
+    if (!shard || shard.trips.length < DESTINATION_SHARD_CAPACITY) {
+      return { shardId, destinationShard, previousShard };
+    }
+    previousShard = destinationShard;
+  }
+}
+
 /**
  * Create a new trip on-chain
  * 
@@ -33,6 +114,7 @@ import type { Waypoint, InterestTag } from '@/types';
  * What's in encrypted_waypoints (PRIVATE):
  * - waypoints: Array of H3 cells (up to 20)
  * - waypoint_count: u8
+ * - min_interests: u8
  * 
  * What's in UserProfile.encrypted_data (PRIVATE - separate account):
  * - interests: Boolean array[32]
@@ -43,7 +125,18 @@ import type { Waypoint, InterestTag } from '@/types';
  * @param destination - Final destination (used for public hash)
  * @param startDate - Trip start date (stored PUBLIC for filtering)
  * @param endDate - Trip end date (stored PUBLIC for filtering)
- * @returns Transaction signature and trip PDA
+ * @param minInterests - Interests both parties must declare before interest
+ *                       scoring counts toward the total (encrypted, default 0)
+ * @param age - Traveler age in years, scored for age compatibility only when
+ *              the other trip sets one too (encrypted, default 0 = undisclosed)
+ * @param dailyBudget - Daily budget in USD cents, scored for budget
+ *                      compatibility when the match enables it and the other
+ *                      trip sets one too (encrypted, default 0 = undisclosed)
+ * @param seeking - Interests wanted in a companion; the interest score then
+ *                  also weighs whether both sides hold what the other seeks
+ *                  (encrypted, default none)
+ * @returns Transaction signature, trip PDA and the encryption nonce
+ *          (needed to queue computations over this ciphertext)
  */
 export async function createTrip(
   program: Program<Triper>,
@@ -51,11 +144,16 @@ export async function createTrip(
   waypoints: Waypoint[],
   destination: Waypoint,
   startDate: Date,
-  endDate: Date
+  endDate: Date,
+  minInterests: number = 0,
+  age: number = 0,
+  dailyBudget: number = 0,
+  seeking: InterestTag[] = []
 ): Promise<{
   signature: string;
   tripPDA: web3.PublicKey;
   destinationGridHash: string;
+  nonce: Uint8Array;
 }> {
   const owner = program.provider.publicKey;
   
@@ -72,6 +170,10 @@ export async function createTrip(
   // 3. Prepare TripData
   const tripData: TripData = {
     waypoints,
+    minInterests,
+    age,
+    dailyBudget,
+    seeking,
   };
   
   // 4. Encrypt trip data
@@ -92,19 +194,13 @@ export async function createTrip(
   const hashData = encoder.encode(destinationGridHash);
   destinationHashBytes.set(hashData.slice(0, 32));
   
-  // 7. Derive Trip PDA
-  // Updated to match current program: seeds = [b"trip", user.key().as_ref(), start_date.to_le_bytes().as_ref()]
-  const startDateBytes = Buffer.alloc(8);
-  startDateBytes.writeBigInt64LE(BigInt(Math.floor(startDate.getTime() / 1000)));
-  
-  const [tripPDA] = web3.PublicKey.findProgramAddressSync(
-    [
-      Buffer.from('trip'),
-      owner.toBuffer(),
-      startDateBytes,
-    ],
-    program.programId
-  );
+  // 7. Derive Trip PDA from the next index on the owner's profile
+  const [userProfilePDA] = getUserProfilePDA(program.programId, owner);
+  const userProfile = await program.account.userProfile.fetchNullable(userProfilePDA);
+  if (!userProfile) {
+    throw new Error('Create a user profile before creating a trip');
+  }
+  const tripPDA = deriveTripPDA(program.programId, owner, userProfile.nextTripIndex);
   
   console.log('📍 Creating trip on-chain');
   console.log('  Owner:', owner.toString());
@@ -114,18 +210,35 @@ export async function createTrip(
   console.log('  Date Range:', startDate.toISOString(), '→', endDate.toISOString());
   console.log('  Encrypted waypoints:', encryptedDataBytes.length, 'bytes');
   
-  // 8. Submit transaction
+  // 8. Submit transaction, indexed in the destination's open shard.
+  // The public area bucket lets the program reject continent-sized routes
+  const { areaBucket } = computeRouteDensityInputs(waypointsToH3Cells(waypoints));
+  const { shardId, destinationShard, previousShard } = await findOpenDestinationShard(
+    program,
+    destinationHashBytes
+  );
+  // Users who opted into a payload registry get reused ciphertext rejected
+  const registryPDA = derivePayloadRegistryPDA(program.programId, owner);
+  const payloadRegistry = (await program.account.payloadRegistry.fetchNullable(registryPDA))
+    ? registryPDA
+    : null;
   const signature = await program.methods
     .createTrip(
       Array.from(destinationHashBytes),
       new BN(Math.floor(startDate.getTime() / 1000)),
       new BN(Math.floor(endDate.getTime() / 1000)),
       encryptedDataBytes,
-      Array.from(encrypted.publicKey)
+      Array.from(encrypted.publicKey),
+      shardId,
+      areaBucket
     )
     .accountsPartial({
       user: owner,
       trip: tripPDA,
+      userProfile: userProfilePDA,
+      destinationShard,
+      previousShard,
+      payloadRegistry,
       systemProgram: web3.SystemProgram.programId,
     })
     .rpc({ commitment: 'confirmed' });
@@ -136,6 +249,7 @@ export async function createTrip(
     signature,
     tripPDA,
     destinationGridHash,
+    nonce: encrypted.nonce,
   };
 }
 
@@ -11,6 +11,40 @@
 import { Program, web3 } from '@coral-xyz/anchor';
 import type { Triper } from '../anchor/types';
 
+/**
+ * Profile accounts accept/reject credit the match outcome to
+ * 
+ * While the match holds a pending-match slot the initiator's profile also
+ * stands in for trip A's owner, so that owner's profile is left out rather
+ * than passed twice. Profiles that don't exist are passed as null.
+ */
+export async function matchProfileAccounts(
+  program: Program<Triper>,
+  matchPDA: web3.PublicKey
+): Promise<{
+  initiatorProfile: web3.PublicKey | null;
+  ownerProfileA: web3.PublicKey | null;
+  ownerProfileB: web3.PublicKey | null;
+}> {
+  const match = await program.account.matchRecord.fetch(matchPDA);
+  const existingProfile = async (owner: web3.PublicKey) => {
+    const [profilePDA] = web3.PublicKey.findProgramAddressSync(
+      [Buffer.from('user_profile'), owner.toBuffer()],
+      program.programId
+    );
+    return (await program.account.userProfile.fetchNullable(profilePDA)) ? profilePDA : null;
+  };
+  
+  const initiatorProfile = match.pendingSlotHeld
+    ? await existingProfile(match.pendingSlotOwner)
+    : null;
+  return {
+    initiatorProfile,
+    ownerProfileA: initiatorProfile ? null : await existingProfile(match.tripAOwner),
+    ownerProfileB: await existingProfile(match.tripBOwner),
+  };
+}
+
 /**
  * Accept a match
  * PUBLIC transaction - indicates user consent
@@ -36,6 +70,12 @@ export async function acceptMatch(
     throw new Error('Wallet not connected');
   }
   
+  // The other trip is read for its owner's notification preference
+  const match = await program.account.matchRecord.fetch(matchPDA);
+  const counterpartyTrip = match.tripA.equals(tripPDA) ? match.tripB : match.tripA;
+  
+  const profiles = await matchProfileAccounts(program, matchPDA);
+  
   console.log('✅ Accepting match');
   console.log('  Match Account:', matchPDA.toString());
   console.log('  Trip Account:', tripPDA.toString());
@@ -45,6 +85,8 @@ export async function acceptMatch(
     .accountsPartial({
       matchAccount: matchPDA,
       trip: tripPDA,
+      counterpartyTrip,
+      ...profiles,
       user,
     })
     .rpc({ commitment: 'confirmed' });
@@ -69,6 +111,9 @@ export async function rejectMatch(
     throw new Error('Wallet not connected');
   }
   
+  // The rejected owner's reputation records the rejection
+  const profiles = await matchProfileAccounts(program, matchPDA);
+  
   console.log('❌ Rejecting match');
   console.log('  Match Account:', matchPDA.toString());
   console.log('  Trip Account:', tripPDA.toString());
@@ -78,6 +123,7 @@ export async function rejectMatch(
     .accountsPartial({
       matchAccount: matchPDA,
       trip: tripPDA,
+      ...profiles,
       user,
     })
     .rpc({ commitment: 'confirmed' });
@@ -110,12 +156,14 @@ export async function recordMatch(
     throw new Error('Wallet not connected');
   }
   
-  // Derive Match PDA
+  // Derive Match PDA (trips in byte order - one record per unordered pair)
+  const [first, second] =
+    Buffer.compare(tripA.toBuffer(), tripB.toBuffer()) <= 0 ? [tripA, tripB] : [tripB, tripA];
   const [matchPDA] = web3.PublicKey.findProgramAddressSync(
     [
       Buffer.from('match'),
-      tripA.toBuffer(),
-      tripB.toBuffer(),
+      first.toBuffer(),
+      second.toBuffer(),
     ],
     program.programId
   );
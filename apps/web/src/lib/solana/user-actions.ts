@@ -53,7 +53,8 @@ export function getUserProfilePDA(
  * @param interests - Interest tags
  * @param displayName - Optional display name
  * @param bio - Optional bio
- * @returns Transaction signature and UserProfile PDA
+ * @returns Transaction signature, UserProfile PDA and the encryption nonce
+ *          (needed to queue computations over this ciphertext)
  */
 export async function createOrUpdateUserProfile(
   program: Program<Triper>,
@@ -66,6 +67,7 @@ export async function createOrUpdateUserProfile(
 ): Promise<{
   signature: string;
   userProfilePDA: web3.PublicKey;
+  nonce: Uint8Array;
 }> {
   const owner = program.provider.publicKey;
   
@@ -141,6 +143,7 @@ export async function createOrUpdateUserProfile(
   return {
     signature,
     userProfilePDA,
+    nonce: encrypted.nonce,
   };
 }
 
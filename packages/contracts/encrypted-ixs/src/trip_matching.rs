@@ -29,6 +29,17 @@ mod circuits {
         // Each waypoint is represented as a u64 H3 index
         waypoints: [u64; MAX_WAYPOINTS],
         waypoint_count: u8,
+        // Interests only count toward the total when both parties declare
+        // at least this many (0 or 1 = any non-empty set)
+        min_interests: u8,
+        // Traveler age in years (0 = undisclosed, age is then not scored)
+        age: u8,
+        // Daily budget in USD cents (0 = undisclosed, budget is then not scored)
+        daily_budget: u32,
+        // Interests sought in a companion, bit i = interests[i] (0 = no
+        // preference). Packed into one field so the trip ciphertext still
+        // fits the Trip account and a single transaction
+        seeking: u32,
     }
     
     /// User interest data structure - encrypted and stored in UserProfile.encrypted_data
@@ -39,12 +50,117 @@ mod circuits {
         interests: [bool; MAX_INTERESTS],
     }
     
-    /// Compute route similarity using H3 cell Jaccard index
+    /// Match scores re-encrypted to a participant (encrypt_scores mode)
+    /// Same order as the revealed tuple in compute_trip_match
+    pub struct MatchScores {
+        route_score: u8,
+        date_score: u8,
+        interest_score: u8,
+        age_score: u8,
+        budget_score: u8,
+        total_score: u8,
+        coverage_score: u8,
+    }
+    
+    // Shared scoring helpers - every score goes through these so rounding
+    // and clamping can't drift between scoring paths. Mirrored for
+    // plaintext estimates in programs/triper/src/scoring.rs
+    
+    /// Clamp a percentage to 0-100
+    fn clamp_pct(v: u32) -> u8 {
+        let clamped = if v > 100 { 100 } else { v };
+        clamped as u8
+    }
+    
+    /// |A ∩ B| / |A ∪ B| as a floored percentage (0 when the union is empty)
+    fn jaccard_pct(intersection: u32, union: u32) -> u8 {
+        let union_nonzero = if union == 0 { 1 } else { union };
+        let pct = clamp_pct((intersection * 100) / union_nonzero);
+        if union == 0 { 0 } else { pct }
+    }
+    
+    /// Weighted average of (route, date, interest, age, budget) scores, floored
+    /// Weights need not sum to 100; an all-zero weight set scores 0
+    /// With zero age and budget weights this is the (route, date, interest)
+    /// average scoring.rs computes
+    fn weighted_total(
+        scores: (u8, u8, u8, u8, u8),
+        weights: (u32, u32, u32, u32, u32)
+    ) -> u8 {
+        let (route, date, interest, age, budget) = scores;
+        let (route_w, date_w, interest_w, age_w, budget_w) = weights;
+        let weight_sum = route_w + date_w + interest_w + age_w + budget_w;
+        let weight_sum_nonzero = if weight_sum == 0 { 1 } else { weight_sum };
+        clamp_pct(
+            ((route as u32 * route_w)
+                + (date as u32 * date_w)
+                + (interest as u32 * interest_w)
+                + (age as u32 * age_w)
+                + (budget as u32 * budget_w))
+                / weight_sum_nonzero
+        )
+    }
+    
+    /// Count H3 cells shared by two routes (one-to-one multiset intersection:
+    /// each cell in A and each cell in B is matched at most once)
+    fn count_route_intersection(
+        waypoints_a: &[u64; MAX_WAYPOINTS], 
+        count_a: u8,
+        waypoints_b: &[u64; MAX_WAYPOINTS], 
+        count_b: u8
+    ) -> u32 {
+        let mut intersection_count = 0u32;
+        let mut visited = [false; MAX_WAYPOINTS];
+        
+        // Must use constant loop bounds in MPC
+        for i in 0..MAX_WAYPOINTS {
+            let is_valid_b = (i as u8) < count_b;
+            let cell_b = waypoints_b[i];
+            let mut matched_b = false;
+            
+            for j in 0..MAX_WAYPOINTS {
+                let is_valid_a = (j as u8) < count_a;
+                let matches = is_valid_b
+                    && is_valid_a
+                    && !matched_b
+                    && !visited[j]
+                    && waypoints_a[j] == cell_b;
+                
+                if matches {
+                    intersection_count += 1;
+                    visited[j] = true;
+                    // In MPC we can't break, so later A cells skip via matched_b
+                    matched_b = true;
+                }
+            }
+        }
+        
+        intersection_count
+    }
+    
+    /// Meeting-coordination payload - re-encrypted to each matched party
+    /// Contains ONLY the cells both routes share, never the full routes
+    pub struct CoordinationData {
+        // Shared H3 cells aligned to the owner's own waypoint slots (0 = not shared)
+        shared_cells: [u64; MAX_WAYPOINTS],
+        shared_count: u8,
+    }
+    
+    // Credit (percent of an exact match) for waypoints that share a level-6
+    // parent (H3_PARENT_SHIFT) but not the exact level-7 cell
+    // (mirrored in programs/triper/src/constants.rs)
+    const PARENT_MATCH_CREDIT_PCT: u32 = 50;
+    
+    /// Compute route similarity using H3 cells with parent-cell fallback
     /// Returns percentage similarity (0-100)
     /// 
-    /// Algorithm: Jaccard = |A ∩ B| / |A ∪ B|
-    /// - Count matching H3 cells between routes
-    /// - Divide by total unique cells
+    /// Algorithm: Jaccard over level-6 parents, where a parent match that is
+    /// also an exact level-7 match counts fully and a parent-only match counts
+    /// PARENT_MATCH_CREDIT_PCT:
+    /// - exact = |A ∩ B| on level-7 cells
+    /// - parent = |A ∩ B| on level-6 parents (every exact match is one too)
+    /// - score = (exact * 100 + (parent - exact) * credit) / (|A| + |B| - parent)
+    /// Routes with no parent-only matches score their plain Jaccard index
     fn compute_route_similarity(
         waypoints_a: &[u64; MAX_WAYPOINTS], 
         count_a: u8,
@@ -54,82 +170,227 @@ mod circuits {
         // Handle empty routes (can't use return in MPC)
         let has_waypoints = count_a > 0 && count_b > 0;
         
-        // Count overlapping H3 cells (Jaccard similarity)
+        let exact_count = count_route_intersection(waypoints_a, count_a, waypoints_b, count_b);
+        let parent_count = count_parent_intersection(waypoints_a, count_a, waypoints_b, count_b);
+        let parent_only_count = parent_count - exact_count;
+        
+        // |A ∪ B| counts each parent-level match once
+        let union_count = (count_a as u32) + (count_b as u32) - parent_count;
+        let union_nonzero = if union_count == 0 { 1 } else { union_count };
+        let credited = exact_count * 100 + parent_only_count * PARENT_MATCH_CREDIT_PCT;
+        let similarity = clamp_pct(credited / union_nonzero);
+        
+        // If no waypoints, return 0, otherwise return calculated score
+        if has_waypoints {
+            similarity
+        } else {
+            0
+        }
+    }
+    
+    // Level-7 H3 cells under the same level-6 parent differ only in the
+    // level-7 digit (bits 24-26; lower digits are always 0b111), so
+    // shifting those bits out yields a parent key without any H3 lookups
+    // (mirrored in programs/triper/src/constants.rs)
+    const H3_PARENT_SHIFT: u64 = 27;
+    
+    /// Count route cells sharing a level-6 parent, one-to-one
+    /// Several cells of one route can share a parent, so each cell on
+    /// either side is matched at most once (a multiset intersection) to
+    /// keep the Jaccard union non-negative
+    fn count_parent_intersection(
+        waypoints_a: &[u64; MAX_WAYPOINTS], 
+        count_a: u8,
+        waypoints_b: &[u64; MAX_WAYPOINTS], 
+        count_b: u8
+    ) -> u32 {
         let mut intersection_count = 0u32;
         let mut visited = [false; MAX_WAYPOINTS];
         
-        // Must use constant loop bounds in MPC
         for i in 0..MAX_WAYPOINTS {
             let is_valid_b = (i as u8) < count_b;
-            if is_valid_b {
-                let cell_b = waypoints_b[i];
+            let parent_b = waypoints_b[i] >> H3_PARENT_SHIFT;
+            let mut matched_b = false;
+            
+            for j in 0..MAX_WAYPOINTS {
+                let is_valid_a = (j as u8) < count_a;
+                let matches = is_valid_b
+                    && is_valid_a
+                    && !matched_b
+                    && !visited[j]
+                    && (waypoints_a[j] >> H3_PARENT_SHIFT) == parent_b;
                 
-                for j in 0..MAX_WAYPOINTS {
-                    let is_valid_a = (j as u8) < count_a;
-                    let matches = is_valid_a && !visited[j] && waypoints_a[j] == cell_b;
-                    
-                    if matches {
-                        intersection_count += 1;
-                        visited[j] = true;
-                        // In MPC we can't break, so we continue but skip further checks
-                    }
+                if matches {
+                    intersection_count += 1;
+                    visited[j] = true;
+                    matched_b = true;
                 }
             }
         }
         
-        // Jaccard = |A ∩ B| / |A ∪ B|
-        // |A ∪ B| = |A| + |B| - |A ∩ B|
-        let union_count = (count_a as u32) + (count_b as u32) - intersection_count;
-        let union_nonzero = if union_count == 0 { 1 } else { union_count };
+        intersection_count
+    }
+    
+    /// Route similarity with H3 resolution stepping
+    /// Blends the Jaccard index at the native level-7 resolution with the
+    /// Jaccard over level-6 parents, so routes that just miss each other at
+    /// the fine resolution still earn partial credit:
+    ///   score = (fine * (100 - coarse_weight_pct) + coarse * coarse_weight_pct) / 100
+    /// coarse_weight_pct = 0 gives the exact-cell Jaccard index
+    /// 
+    /// Alternative to compute_route_similarity's fixed parent credit - the
+    /// coarse share is tunable per call
+    fn compute_route_similarity_multires(
+        waypoints_a: &[u64; MAX_WAYPOINTS], 
+        count_a: u8,
+        waypoints_b: &[u64; MAX_WAYPOINTS], 
+        count_b: u8,
+        coarse_weight_pct: u8
+    ) -> u8 {
+        let has_waypoints = count_a > 0 && count_b > 0;
         
-        // Return Jaccard index as percentage (0-100)
-        let jaccard_percentage = (intersection_count * 100) / union_nonzero;
-        let clamped = if jaccard_percentage > 100 { 100 } else { jaccard_percentage };
+        let exact_intersection = count_route_intersection(waypoints_a, count_a, waypoints_b, count_b);
+        let exact_union = (count_a as u32) + (count_b as u32) - exact_intersection;
+        let fine = jaccard_pct(exact_intersection, exact_union);
+        
+        let parent_intersection = count_parent_intersection(waypoints_a, count_a, waypoints_b, count_b);
+        let parent_union = (count_a as u32) + (count_b as u32) - parent_intersection;
+        let coarse = jaccard_pct(parent_intersection, parent_union);
+        
+        let coarse_w = clamp_pct(coarse_weight_pct as u32) as u32;
+        let blended = clamp_pct(((fine as u32) * (100 - coarse_w) + (coarse as u32) * coarse_w) / 100);
+        
+        if has_waypoints {
+            blended
+        } else {
+            0
+        }
+    }
+    
+    /// Compute route coverage: |A ∩ B| / min(|A|, |B|)
+    /// Returns percentage (0-100)
+    /// 
+    /// Unlike Jaccard, a short route fully contained in a long one scores 100 -
+    /// the short-route traveler will certainly pass through the other's cells.
+    /// Jaccard answers "how similar are the routes", coverage answers
+    /// "how likely are they to cross paths".
+    fn compute_route_coverage(
+        waypoints_a: &[u64; MAX_WAYPOINTS], 
+        count_a: u8,
+        waypoints_b: &[u64; MAX_WAYPOINTS], 
+        count_b: u8
+    ) -> u8 {
+        let has_waypoints = count_a > 0 && count_b > 0;
+        
+        let intersection_count = count_route_intersection(waypoints_a, count_a, waypoints_b, count_b);
+        
+        let smaller_count = if count_a < count_b { count_a as u32 } else { count_b as u32 };
+        let smaller_nonzero = if smaller_count == 0 { 1 } else { smaller_count };
+        
+        let coverage = clamp_pct((intersection_count * 100) / smaller_nonzero);
         
-        // If no waypoints, return 0, otherwise return calculated score
         if has_waypoints {
-            clamped as u8
+            coverage
         } else {
             0
         }
     }
     
     /// Compute date overlap as percentage
-    /// Returns 0-100 based on how much the date ranges overlap
+    /// Returns 0-100: overlap relative to the average trip duration, computed
+    /// as overlap * 200 / (duration_a + duration_b) so an odd duration sum is
+    /// not floored before dividing
+    ///
+    /// Zero-length semantics: a zero-length overlap (touching ranges, or an
+    /// instant against a range) scores 0, and two zero-duration trips score
+    /// 100 on the same instant and 0 otherwise. create_trip enforces
+    /// end > start, so only the first case is reachable on-chain
+    ///
+    /// Flexibility: `tolerance` (seconds, from the more flexible trip's
+    /// flexibility score) is how far one trip may shift toward the other
+    /// before the overlap is measured - it is added to the signed overlap,
+    /// capped at the shorter trip, and two instants match within it. A
+    /// tolerance of 0 scores the dates as given. Mirrored by
+    /// prefilter::date_overlap_percent
     fn compute_date_overlap(
         start_a: i64,
         end_a: i64,
         start_b: i64,
         end_b: i64,
+        tolerance: i64,
     ) -> u8 {
+        let duration_a = end_a - start_a;
+        let duration_b = end_b - start_b;
+        let shorter_duration = if duration_a < duration_b { duration_a } else { duration_b };
+        
+        // Signed overlap (negative = the gap between the ranges), widened by
+        // the tolerance and capped at the shorter trip - no early returns
+        // allowed in MPC, so both bounds are conditional expressions
         let overlap_start = if start_a > start_b { start_a } else { start_b };
         let overlap_end = if end_a < end_b { end_a } else { end_b };
+        let shifted_overlap = overlap_end - overlap_start + tolerance;
+        let floored_overlap = if shifted_overlap > 0 { shifted_overlap } else { 0 };
+        let overlap_duration = if floored_overlap < shorter_duration { floored_overlap } else { shorter_duration };
         
-        // No early returns allowed in MPC - use conditional expressions
-        let has_overlap = overlap_end >= overlap_start;
+        let duration_sum = duration_a + duration_b;
         
-        let overlap_duration = if has_overlap {
-            overlap_end - overlap_start
-        } else {
-            0
-        };
+        let duration_sum_nonzero = if duration_sum == 0 { 1 } else { duration_sum };
         
-        let duration_a = end_a - start_a;
-        let duration_b = end_b - start_b;
-        let avg_duration = (duration_a + duration_b) / 2;
-        
-        let avg_duration_nonzero = if avg_duration == 0 { 1 } else { avg_duration };
+        // Dates are validated end > start, so the ratio is never negative
+        let percentage = (overlap_duration * 200) / duration_sum_nonzero;
+        let ranged_score = clamp_pct(percentage as u32);
         
-        let percentage = (overlap_duration * 100) / avg_duration_nonzero;
-        let clamped = if percentage > 100 { 100 } else { percentage };
+        // Both trips are instants: all or nothing, within the tolerance
+        let instant_gap = if start_a > start_b { start_a - start_b } else { start_b - start_a };
+        let instant_score = if instant_gap <= tolerance { 100 } else { 0 };
         
-        clamped as u8
+        if duration_sum == 0 { instant_score } else { ranged_score }
+    }
+    
+    /// Count declared interests in a flag array
+    fn count_interests(interests: &[bool; 32]) -> u32 {
+        let mut count = 0u32;
+        for i in 0..32 {
+            if interests[i] {
+                count += 1;
+            }
+        }
+        count
+    }
+    
+    // Credit (percent of a shared interest) for an interest only one party
+    // holds that the InterestMatrix relates to one the other party holds
+    // (mirrored in programs/triper/src/constants.rs)
+    const RELATED_INTEREST_CREDIT_PCT: u32 = 50;
+    
+    /// Whether `interests` holds any interest set in `related_mask`
+    fn holds_related(interests: &[bool; 32], related_mask: u32) -> bool {
+        let mut found = false;
+        for j in 0..32 {
+            let related = ((related_mask >> j) & 1) == 1;
+            found = found || (related && interests[j]);
+        }
+        found
     }
     
     /// Compute interest similarity using Jaccard index on boolean flags
     /// interests are represented as boolean arrays where true = user has that interest
-    fn compute_interest_similarity(interests_a: &[bool; 32], interests_b: &[bool; 32]) -> u8 {
+    /// 
+    /// Related-interest credit: an interest in the symmetric difference counts
+    /// RELATED_INTEREST_CREDIT_PCT of a shared one when `relatedness` (the
+    /// public InterestMatrix rows, bit j of row i = i related to j) links it
+    /// to an interest the other party holds. All-zero rows give plain Jaccard
+    /// 
+    /// Empty-set semantics: if either party declared no interests there is
+    /// nothing to compare, so the score is 0 and compute_trip_match drops the
+    /// interest weight from the total (renormalizing over route + dates)
+    fn compute_interest_similarity(
+        interests_a: &[bool; 32],
+        interests_b: &[bool; 32],
+        relatedness: &[u32; MAX_INTERESTS]
+    ) -> u8 {
         let mut common_count = 0u32;
+        let mut related_count = 0u32;
         let mut total_count = 0u32;
         
         for i in 0..32 {
@@ -138,18 +399,317 @@ mod circuits {
                 total_count += 1;
             } else if interests_a[i] || interests_b[i] {
                 total_count += 1;
+                let related = (interests_a[i] && holds_related(interests_b, relatedness[i]))
+                    || (interests_b[i] && holds_related(interests_a, relatedness[i]));
+                if related {
+                    related_count += 1;
+                }
             }
         }
         
-        // No interests specified by either party - use conditional instead of return
+        let has_interests = count_interests(interests_a) > 0 && count_interests(interests_b) > 0;
+        
+        // Use conditional instead of return. Credit is counted in hundredths
+        // of a shared interest, so with no related credit this is exactly
+        // jaccard_pct(common_count, total_count)
+        let credited = common_count * 100 + related_count * RELATED_INTEREST_CREDIT_PCT;
         let total_nonzero = if total_count == 0 { 1 } else { total_count };
-        let score = if total_count == 0 {
-            100
+        let score = if total_count == 0 { 0 } else { clamp_pct(credited / total_nonzero) };
+        if has_interests {
+            score
         } else {
-            ((common_count * 100) / total_nonzero) as u8
-        };
+            0
+        }
+    }
+    
+    // Interest score points lost for each interest one party holds that the
+    // IncompatibilityMatrix flags as clashing with one the other party holds
+    const INCOMPATIBLE_INTEREST_PENALTY_PCT: u32 = 25;
+    
+    /// Count interests A holds that clash with any interest B holds
+    /// `incompatibility` is the public IncompatibilityMatrix (bit j of row
+    /// i = i clashes with j). The matrix is symmetric, so scanning A's side
+    /// sees every clashing pair; the scan is a fixed 32 x 32 walk
+    fn count_incompatible(
+        interests_a: &[bool; 32],
+        interests_b: &[bool; 32],
+        incompatibility: &[u32; MAX_INTERESTS]
+    ) -> u32 {
+        let mut clashes = 0u32;
+        for i in 0..32 {
+            if interests_a[i] && holds_related(interests_b, incompatibility[i]) {
+                clashes += 1;
+            }
+        }
+        clashes
+    }
+    
+    /// Lower an interest score by INCOMPATIBLE_INTEREST_PENALTY_PCT per clash,
+    /// floored at 0. No clashes leaves the score unchanged
+    fn apply_incompatibility_penalty(score: u8, clashes: u32) -> u8 {
+        // Both branches run in MPC, so cap the penalty rather than
+        // subtracting conditionally
+        let penalty = clashes * INCOMPATIBLE_INTEREST_PENALTY_PCT;
+        let score = score as u32;
+        let capped = if penalty > score { score } else { penalty };
+        (score - capped) as u8
+    }
+    
+    /// Share of the interests `seeking` asks for that `other` holds, as a
+    /// floored percentage. Seeking nothing is always satisfied (100)
+    fn seeking_satisfaction(seeking: u32, other: &[bool; MAX_INTERESTS]) -> u8 {
+        let mut sought: u32 = 0;
+        let mut satisfied: u32 = 0;
+        for i in 0..MAX_INTERESTS {
+            let seeks = ((seeking >> i) & 1) == 1;
+            if seeks {
+                sought += 1;
+            }
+            if seeks && other[i] {
+                satisfied += 1;
+            }
+        }
+        let sought_nonzero = if sought == 0 { 1 } else { sought };
+        let pct = clamp_pct((satisfied * 100) / sought_nonzero);
+        if sought == 0 { 100 } else { pct }
+    }
+    
+    /// How well A's interests satisfy B's seeking AND B's interests satisfy
+    /// A's seeking - the minimum of the two directions, so a match only one
+    /// side wants scores low
+    fn compute_mutual_seeking(
+        seeking_a: u32,
+        interests_a: &[bool; MAX_INTERESTS],
+        seeking_b: u32,
+        interests_b: &[bool; MAX_INTERESTS]
+    ) -> u8 {
+        let a_satisfied = seeking_satisfaction(seeking_a, interests_b);
+        let b_satisfied = seeking_satisfaction(seeking_b, interests_a);
+        if a_satisfied < b_satisfied { a_satisfied } else { b_satisfied }
+    }
+    
+    // Newton iterations for the integer square root used by cosine scoring.
+    // Must stay a compile-time constant (MPC loops need constant bounds).
+    //
+    // The root is seeded with the arithmetic mean (|A| + |B|) / 2, which is
+    // always >= sqrt(|A| * |B|), so Newton converges from above and the
+    // score can only come out low. Worst-case error of the resulting score vs
+    // an f64 cosine over every (|A|, |B|, |A ∩ B|) combination for 32 interest
+    // flags (measured by the host tests against scoring::isqrt_newton in
+    // programs/triper/src/scoring.rs):
+    // - 1 iteration:  -7.68 points (|A| = 1, |B| = 32)
+    // - 2 iterations: -2.68 points
+    // - 3 iterations: -1.00 point, no worse than the integer floor itself
+    // Default 3 keeps interest scores within a 1 point bound.
+    const ISQRT_ITERATIONS: usize = 3;
+    
+    /// Integer square root via a fixed number of Newton steps
+    fn isqrt_newton(n: u32, seed: u32) -> u32 {
+        let mut x = seed;
+        for _ in 0..ISQRT_ITERATIONS {
+            let x_nonzero = if x == 0 { 1 } else { x };
+            x = (x + n / x_nonzero) / 2;
+        }
+        x
+    }
+    
+    /// Compute interest similarity as cosine similarity on boolean flags
+    /// cos = |A ∩ B| / sqrt(|A| * |B|), returned as a percentage (0-100)
+    /// 
+    /// Alternative to the Jaccard score - less punishing when one party
+    /// declares many more interests than the other
+    fn compute_interest_cosine(interests_a: &[bool; 32], interests_b: &[bool; 32]) -> u8 {
+        let mut common_count = 0u32;
+        let mut count_a = 0u32;
+        let mut count_b = 0u32;
+        
+        for i in 0..32 {
+            if interests_a[i] {
+                count_a += 1;
+            }
+            if interests_b[i] {
+                count_b += 1;
+            }
+            if interests_a[i] && interests_b[i] {
+                common_count += 1;
+            }
+        }
+        
+        // sqrt(|A| * |B|) scaled by 100 to keep two digits of precision
+        let norm = isqrt_newton(count_a * count_b * 10000, (count_a + count_b) * 50);
+        let norm_nonzero = if norm == 0 { 1 } else { norm };
+        
+        let cosine = clamp_pct((common_count * 10000) / norm_nonzero);
         
-        score
+        // Same empty-set semantics as the Jaccard score
+        if count_a == 0 || count_b == 0 {
+            0
+        } else {
+            cosine
+        }
+    }
+    
+    /// Compute age compatibility as a percentage
+    /// 100 when the ages are equal, decaying linearly to 0 at max_gap years
+    /// apart (and beyond). max_gap = 0 only credits identical ages
+    fn compute_age_compatibility(age_a: u8, age_b: u8, max_gap: u8) -> u8 {
+        let a = age_a as u32;
+        let b = age_b as u32;
+        // Both branches run in MPC, so neither subtraction may underflow
+        let gap = if a > b { a - b } else { b - a };
+        let max = max_gap as u32;
+        let capped_gap = if gap > max { max } else { gap };
+        let max_nonzero = if max == 0 { 1 } else { max };
+        let decayed = clamp_pct(((max - capped_gap) * 100) / max_nonzero);
+        let exact = if gap == 0 { 100 } else { 0 };
+        if max == 0 {
+            exact
+        } else {
+            decayed
+        }
+    }
+    
+    // Budgets within this share of each other (smaller / larger, percent)
+    // count as fully compatible
+    const BUDGET_FULL_MATCH_RATIO_PCT: u64 = 90;
+    
+    /// Compute budget similarity as a percentage
+    /// 100 when the smaller budget is within 10% of the larger, otherwise
+    /// proportional to the smaller / larger ratio:
+    ///   score = ratio_pct * 100 / BUDGET_FULL_MATCH_RATIO_PCT
+    /// so half the other's budget scores 55 and a tenth scores 11.
+    /// Computed in u64 so cent amounts cannot overflow
+    fn compute_budget_similarity(budget_a: u32, budget_b: u32) -> u8 {
+        let a = budget_a as u64;
+        let b = budget_b as u64;
+        let smaller = if a < b { a } else { b };
+        let larger = if a < b { b } else { a };
+        let larger_nonzero = if larger == 0 { 1 } else { larger };
+        let ratio_pct = (smaller * 100) / larger_nonzero;
+        let scaled = (ratio_pct * 100) / BUDGET_FULL_MATCH_RATIO_PCT;
+        let capped = if scaled > 100 { 100 } else { scaled };
+        capped as u8
+    }
+    
+    /// Route score reported for destination-only pairs, where route overlap
+    /// is deliberately ignored - neither a match nor a mismatch
+    const NEUTRAL_ROUTE_SCORE: u8 = 50;
+    
+    /// Score one trip pair - shared by compute_trip_match, self_match_test and
+    /// check_match_threshold so every caller runs the production scoring path
+    /// Changing the math here requires bumping SCORING_VERSION in
+    /// programs/triper/src/constants.rs
+    /// Returns (route_score, date_score, interest_score, age_score, budget_score,
+    /// total_score, coverage_score)
+    fn score_match(
+        waypoints_a: &WaypointData,
+        waypoints_b: &WaypointData,
+        interests_a: &UserInterests,
+        interests_b: &UserInterests,
+        weights: (u8, u8, u8),
+        age_params: (u8, u8),
+        budget_weight: u8,
+        destination_only: bool,
+        dates: (i64, i64, i64, i64),
+        date_tolerance: i64,
+        interest_relatedness: &[u32; MAX_INTERESTS],
+        interest_incompatibility: &[u32; MAX_INTERESTS],
+    ) -> (u8, u8, u8, u8, u8, u8, u8) {
+        // Compute route similarity (encrypted waypoints). Destination-only
+        // pairs already share a destination and opted out of route scoring,
+        // so they report a neutral route score that the total ignores
+        let route_similarity = compute_route_similarity(
+            &waypoints_a.waypoints,
+            waypoints_a.waypoint_count,
+            &waypoints_b.waypoints,
+            waypoints_b.waypoint_count
+        );
+        let route_score = if destination_only { NEUTRAL_ROUTE_SCORE } else { route_similarity };
+        
+        // Compute route coverage (share of the shorter route inside the other)
+        let coverage_score = compute_route_coverage(
+            &waypoints_a.waypoints,
+            waypoints_a.waypoint_count,
+            &waypoints_b.waypoints,
+            waypoints_b.waypoint_count
+        );
+        
+        // Compute date overlap (using PUBLIC dates - no encryption needed)
+        let (start_date_a, end_date_a, start_date_b, end_date_b) = dates;
+        let date_score = compute_date_overlap(
+            start_date_a,
+            end_date_a,
+            start_date_b,
+            end_date_b,
+            date_tolerance
+        );
+        
+        // Compute interest similarity (encrypted interests), less the
+        // penalty for lifestyle clashes between the two parties
+        let interest_score = compute_interest_similarity(
+            &interests_a.interests,
+            &interests_b.interests,
+            interest_relatedness
+        );
+        let clashes = count_incompatible(
+            &interests_a.interests,
+            &interests_b.interests,
+            interest_incompatibility
+        );
+        let interest_score = apply_incompatibility_penalty(interest_score, clashes);
+        
+        // With seeking preferences on either trip, the interest score is
+        // averaged with the mutual seeking score, so having interests in
+        // common counts for less when only one side wants the other's
+        let mutual_seeking = compute_mutual_seeking(
+            waypoints_a.seeking,
+            &interests_a.interests,
+            waypoints_b.seeking,
+            &interests_b.interests
+        );
+        let has_seeking = waypoints_a.seeking > 0 || waypoints_b.seeking > 0;
+        let blended = ((interest_score as u32 + mutual_seeking as u32) / 2) as u8;
+        let interest_score = if has_seeking { blended } else { interest_score };
+        
+        // Interests only count when both parties declared enough of them.
+        // The stricter of the two trips' minimums applies, and at least one
+        // interest is always required (empty sets have nothing to compare)
+        let min_a = waypoints_a.min_interests as u32;
+        let min_b = waypoints_b.min_interests as u32;
+        let stricter_min = if min_a > min_b { min_a } else { min_b };
+        let min_interests = if stricter_min > 1 { stricter_min } else { 1 };
+        let has_interests = count_interests(&interests_a.interests) >= min_interests
+            && count_interests(&interests_b.interests) >= min_interests;
+        
+        // Age compatibility - optional, scored only when both trips
+        // disclose an age
+        let (age_weight, max_age_gap) = age_params;
+        let has_ages = waypoints_a.age > 0 && waypoints_b.age > 0;
+        let age_compat = compute_age_compatibility(waypoints_a.age, waypoints_b.age, max_age_gap);
+        let age_score = if has_ages { age_compat } else { 0 };
+        
+        // Budget compatibility - opted into per match via budget_weight,
+        // scored only when both trips disclose a budget
+        let has_budgets = waypoints_a.daily_budget > 0 && waypoints_b.daily_budget > 0;
+        let budget_compat = compute_budget_similarity(waypoints_a.daily_budget, waypoints_b.daily_budget);
+        let budget_score = if has_budgets { budget_compat } else { 0 };
+        
+        // Weighted average using the match's scoring profile
+        // (Balanced = 40% route, 35% dates, 25% interests), plus age_weight
+        // and budget_weight when those are scored. With insufficient interest
+        // data the interest weight is redistributed over the other
+        // components, and likewise the age / budget weight without both values
+        // and the route weight for destination-only pairs
+        let route_w = if destination_only { 0 } else { weights.0 as u32 };
+        let interest_w = if has_interests { weights.2 as u32 } else { 0 };
+        let age_w = if has_ages { age_weight as u32 } else { 0 };
+        let budget_w = if has_budgets { budget_weight as u32 } else { 0 };
+        let total_score = weighted_total(
+            (route_score, date_score, interest_score, age_score, budget_score),
+            (route_w, weights.1 as u32, interest_w, age_w, budget_w)
+        );
+        
+        (route_score, date_score, interest_score, age_score, budget_score, total_score, coverage_score)
     }
     
     /// Main encrypted instruction: compute trip match score
@@ -157,66 +717,351 @@ mod circuits {
     /// - Waypoint data (encrypted) from Trip.encrypted_waypoints
     /// - Interest data (encrypted) from UserProfile.encrypted_data
     /// - Date data (PUBLIC) passed as plaintext parameters
+    /// - Score weights (PUBLIC) from the match's scoring profile
+    /// - Age weight and decay gap (PUBLIC) from program constants
+    /// - Budget weight (PUBLIC) chosen at initiate_match (0 = off)
+    /// - Destination-only flag (PUBLIC) - both trips opted in and share a
+    ///   destination, so route_score is neutral and excluded from the total
+    /// - Date tolerance (PUBLIC) in seconds, from the more flexible trip's
+    ///   flexibility score
+    /// - Interest relatedness (PUBLIC) from the InterestMatrix taxonomy
+    /// - Interest clashes (PUBLIC) from the IncompatibilityMatrix
     ///
     /// Computes compatibility scores:
-    /// - Route similarity (H3 cell Jaccard for privacy)
-    /// - Date overlap (using public dates for efficient pre-filtering)
-    /// - Interest alignment
+    /// - Route similarity (H3 cell Jaccard with parent-cell fallback)
+    /// - Date overlap (using public dates for efficient pre-filtering,
+    ///   widened by the more flexible trip's date tolerance)
+    /// - Interest alignment (less a penalty for flagged clashes, blended
+    ///   with mutual seeking when either trip seeks specific interests)
+    /// - Age compatibility (only when both trips disclose an age)
+    /// - Budget compatibility (only with a budget weight and both budgets)
+    /// - Route coverage (overlap relative to the shorter route)
     ///
-    /// Returns (route_score, date_score, interest_score, total_score) all 0-100
+    /// Returns (route_score, date_score, interest_score, age_score, budget_score,
+    /// total_score, coverage_score, passes_threshold, scores_for_a, scores_for_b):
+    /// - the seven scores are 0-100; coverage_score is informational and does
+    ///   not feed into total_score; age_score / budget_score are 0 unless
+    ///   both trips set an age / budget
+    /// - with encrypt_scores set the seven revealed scores are zeroed and only
+    ///   passes_threshold (total_score >= min_total_score) is public
+    /// - scores_for_a / scores_for_b carry the scores re-encrypted to each
+    ///   participant's profile key, so only the two parties can read them
+    ///
+    /// The tuple order and arity are a contract with
+    /// compute_trip_match_callback, which destructures the output exhaustively:
+    /// changing either requires updating the callback in the same change
     #[instruction]
     pub fn compute_trip_match(
         waypoints_a_ctxt: Enc<Shared, WaypointData>,
         waypoints_b_ctxt: Enc<Shared, WaypointData>,
         interests_a_ctxt: Enc<Shared, UserInterests>,
         interests_b_ctxt: Enc<Shared, UserInterests>,
+        route_weight: u8,
+        date_weight: u8,
+        interest_weight: u8,
+        age_weight: u8,
+        max_age_gap: u8,
+        budget_weight: u8,
+        destination_only: bool,
+        date_tolerance_secs: u32,
+        encrypt_scores: bool,
+        min_total_score: u8,
+        interest_relatedness: [u32; MAX_INTERESTS],
+        interest_incompatibility: [u32; MAX_INTERESTS],
         start_date_a: i64,
         end_date_a: i64,
         start_date_b: i64,
         end_date_b: i64,
-    ) -> (u8, u8, u8, u8) {
+    ) -> (u8, u8, u8, u8, u8, u8, u8, bool, Enc<Shared, MatchScores>, Enc<Shared, MatchScores>) {
         let waypoints_a = waypoints_a_ctxt.to_arcis();
         let waypoints_b = waypoints_b_ctxt.to_arcis();
         let interests_a = interests_a_ctxt.to_arcis();
         let interests_b = interests_b_ctxt.to_arcis();
         
-        // Compute route similarity (encrypted waypoints)
-        let route_score = compute_route_similarity(
-            &waypoints_a.waypoints,
-            waypoints_a.waypoint_count,
-            &waypoints_b.waypoints,
-            waypoints_b.waypoint_count
+        let (
+            route_score,
+            date_score,
+            interest_score,
+            age_score,
+            budget_score,
+            total_score,
+            coverage_score,
+        ) = score_match(
+            &waypoints_a,
+            &waypoints_b,
+            &interests_a,
+            &interests_b,
+            (route_weight, date_weight, interest_weight),
+            (age_weight, max_age_gap),
+            budget_weight,
+            destination_only,
+            (start_date_a, end_date_a, start_date_b, end_date_b),
+            date_tolerance_secs as i64,
+            &interest_relatedness,
+            &interest_incompatibility
         );
         
-        // Compute date overlap (using PUBLIC dates - no encryption needed)
-        let date_score = compute_date_overlap(
-            start_date_a,
-            end_date_a,
-            start_date_b,
-            end_date_b
-        );
+        let passes_threshold = total_score >= min_total_score;
         
-        // Compute interest similarity (encrypted interests)
-        let interest_score = compute_interest_similarity(
-            &interests_a.interests,
-            &interests_b.interests
-        );
+        // Participant copies are always produced; the callback stores them
+        // only in encrypt_scores mode
+        let scores_a = MatchScores {
+            route_score,
+            date_score,
+            interest_score,
+            age_score,
+            budget_score,
+            total_score,
+            coverage_score,
+        };
+        let scores_b = MatchScores {
+            route_score,
+            date_score,
+            interest_score,
+            age_score,
+            budget_score,
+            total_score,
+            coverage_score,
+        };
         
-        // Weighted average: 40% route, 35% dates, 25% interests
-        let total_score = (
-            (route_score as u32 * 40) + 
-            (date_score as u32 * 35) + 
-            (interest_score as u32 * 25)
-        ) / 100;
+        // Plaintext mode reveals the scores themselves; encrypted mode reveals
+        // zeros plus the threshold flag the program needs for the match status.
+        // The individual trip/user data remains encrypted either way
+        let public_route = if encrypt_scores { 0 } else { route_score };
+        let public_date = if encrypt_scores { 0 } else { date_score };
+        let public_interest = if encrypt_scores { 0 } else { interest_score };
+        let public_age = if encrypt_scores { 0 } else { age_score };
+        let public_budget = if encrypt_scores { 0 } else { budget_score };
+        let public_total = if encrypt_scores { 0 } else { total_score };
+        let public_coverage = if encrypt_scores { 0 } else { coverage_score };
+        
+        (
+            public_route.reveal(),
+            public_date.reveal(),
+            public_interest.reveal(),
+            public_age.reveal(),
+            public_budget.reveal(),
+            public_total.reveal(),
+            public_coverage.reveal(),
+            passes_threshold.reveal(),
+            interests_a_ctxt.owner.from_arcis(scores_a),
+            interests_b_ctxt.owner.from_arcis(scores_b)
+        )
+    }
+    
+    /// Encrypted instruction: MPC health check - a trip scored against itself
+    ///
+    /// Takes the same inputs as compute_trip_match (the program passes one
+    /// trip and profile in both slots) and runs the same scoring, so a
+    /// healthy network must return route_score = coverage_score = 100.
+    /// Both sides hold the same interests, age and budget, so no
+    /// relatedness, clashes, age or budget weight are passed
+    /// Returns (route_score, date_score, interest_score, total_score, coverage_score)
+    #[instruction]
+    pub fn self_match_test(
+        waypoints_a_ctxt: Enc<Shared, WaypointData>,
+        waypoints_b_ctxt: Enc<Shared, WaypointData>,
+        interests_a_ctxt: Enc<Shared, UserInterests>,
+        interests_b_ctxt: Enc<Shared, UserInterests>,
+        route_weight: u8,
+        date_weight: u8,
+        interest_weight: u8,
+        start_date_a: i64,
+        end_date_a: i64,
+        start_date_b: i64,
+        end_date_b: i64,
+    ) -> (u8, u8, u8, u8, u8) {
+        let waypoints_a = waypoints_a_ctxt.to_arcis();
+        let waypoints_b = waypoints_b_ctxt.to_arcis();
+        let interests_a = interests_a_ctxt.to_arcis();
+        let interests_b = interests_b_ctxt.to_arcis();
+        
+        let (route_score, date_score, interest_score, _, _, total_score, coverage_score) = score_match(
+            &waypoints_a,
+            &waypoints_b,
+            &interests_a,
+            &interests_b,
+            (route_weight, date_weight, interest_weight),
+            (0, 0),
+            0,
+            false,
+            (start_date_a, end_date_a, start_date_b, end_date_b),
+            0,
+            &[0u32; MAX_INTERESTS],
+            &[0u32; MAX_INTERESTS]
+        );
         
-        // Return all scores revealed (not encrypted)
-        // The individual trip/user data remains encrypted - only scores are revealed
         (
             route_score.reveal(),
             date_score.reveal(),
             interest_score.reveal(),
-            (total_score as u8).reveal()
+            total_score.reveal(),
+            coverage_score.reveal()
         )
     }
+    
+    /// Encrypted instruction: reveal only whether a pair clears a threshold
+    ///
+    /// Runs the production scoring path but discloses a single bit -
+    /// total_score >= threshold. The component scores and the total itself
+    /// never leave MPC, so participants can learn "good enough" without
+    /// learning why
+    #[instruction]
+    pub fn check_match_threshold(
+        waypoints_a_ctxt: Enc<Shared, WaypointData>,
+        waypoints_b_ctxt: Enc<Shared, WaypointData>,
+        interests_a_ctxt: Enc<Shared, UserInterests>,
+        interests_b_ctxt: Enc<Shared, UserInterests>,
+        route_weight: u8,
+        date_weight: u8,
+        interest_weight: u8,
+        age_weight: u8,
+        max_age_gap: u8,
+        budget_weight: u8,
+        destination_only: bool,
+        date_tolerance_secs: u32,
+        threshold: u8,
+        interest_relatedness: [u32; MAX_INTERESTS],
+        interest_incompatibility: [u32; MAX_INTERESTS],
+        start_date_a: i64,
+        end_date_a: i64,
+        start_date_b: i64,
+        end_date_b: i64,
+    ) -> bool {
+        let waypoints_a = waypoints_a_ctxt.to_arcis();
+        let waypoints_b = waypoints_b_ctxt.to_arcis();
+        let interests_a = interests_a_ctxt.to_arcis();
+        let interests_b = interests_b_ctxt.to_arcis();
+        
+        let (_, _, _, _, _, total_score, _) = score_match(
+            &waypoints_a,
+            &waypoints_b,
+            &interests_a,
+            &interests_b,
+            (route_weight, date_weight, interest_weight),
+            (age_weight, max_age_gap),
+            budget_weight,
+            destination_only,
+            (start_date_a, end_date_a, start_date_b, end_date_b),
+            date_tolerance_secs as i64,
+            &interest_relatedness,
+            &interest_incompatibility
+        );
+        
+        (total_score >= threshold).reveal()
+    }
+    
+    /// Encrypted instruction: check that a trip ciphertext decrypts to a
+    /// well-formed WaypointData layout
+    ///
+    /// Lets an owner confirm their payload is usable for matching before
+    /// paying for match computations. Only a pass/fail flag is revealed:
+    /// - 1..=MAX_WAYPOINTS waypoints
+    /// - every used slot holds a non-zero H3 cell
+    /// - every padding slot is zero
+    /// - min_interests is at most MAX_INTERESTS
+    #[instruction]
+    pub fn verify_trip_encryption(waypoints_ctxt: Enc<Shared, WaypointData>) -> bool {
+        let data = waypoints_ctxt.to_arcis();
+        
+        let count_valid = data.waypoint_count > 0 && (data.waypoint_count as usize) <= MAX_WAYPOINTS;
+        
+        let mut slots_valid = true;
+        for i in 0..MAX_WAYPOINTS {
+            let is_used = (i as u8) < data.waypoint_count;
+            let slot_ok = if is_used {
+                data.waypoints[i] != 0
+            } else {
+                data.waypoints[i] == 0
+            };
+            slots_valid = slots_valid && slot_ok;
+        }
+        
+        let min_interests_valid = (data.min_interests as usize) <= MAX_INTERESTS;
+        
+        (count_valid && slots_valid && min_interests_valid).reveal()
+    }
+    
+    /// Encrypted instruction: coordination payload for a mutual match
+    ///
+    /// Instead of revealing both routes, outputs only the intersecting cells,
+    /// each party's copy re-encrypted to their own key. Cells are reported in
+    /// the recipient's own waypoint order (zeros where not shared), which keeps
+    /// the computation free of secret-indexed writes.
+    #[instruction]
+    pub fn reveal_coordination(
+        waypoints_a_ctxt: Enc<Shared, WaypointData>,
+        waypoints_b_ctxt: Enc<Shared, WaypointData>,
+    ) -> (Enc<Shared, CoordinationData>, Enc<Shared, CoordinationData>) {
+        let waypoints_a = waypoints_a_ctxt.to_arcis();
+        let waypoints_b = waypoints_b_ctxt.to_arcis();
+        
+        let mut shared_a = [0u64; MAX_WAYPOINTS];
+        let mut shared_b = [0u64; MAX_WAYPOINTS];
+        let mut count_a = 0u8;
+        let mut count_b = 0u8;
+        
+        for i in 0..MAX_WAYPOINTS {
+            let valid_a = (i as u8) < waypoints_a.waypoint_count;
+            let valid_b = (i as u8) < waypoints_b.waypoint_count;
+            let mut a_in_b = false;
+            let mut b_in_a = false;
+            
+            for j in 0..MAX_WAYPOINTS {
+                let other_valid_b = (j as u8) < waypoints_b.waypoint_count;
+                let other_valid_a = (j as u8) < waypoints_a.waypoint_count;
+                a_in_b = a_in_b || (other_valid_b && waypoints_b.waypoints[j] == waypoints_a.waypoints[i]);
+                b_in_a = b_in_a || (other_valid_a && waypoints_a.waypoints[j] == waypoints_b.waypoints[i]);
+            }
+            
+            if valid_a && a_in_b {
+                shared_a[i] = waypoints_a.waypoints[i];
+                count_a += 1;
+            }
+            if valid_b && b_in_a {
+                shared_b[i] = waypoints_b.waypoints[i];
+                count_b += 1;
+            }
+        }
+        
+        let coordination_a = CoordinationData {
+            shared_cells: shared_a,
+            shared_count: count_a,
+        };
+        let coordination_b = CoordinationData {
+            shared_cells: shared_b,
+            shared_count: count_b,
+        };
+        
+        (
+            waypoints_a_ctxt.owner.from_arcis(coordination_a),
+            waypoints_b_ctxt.owner.from_arcis(coordination_b)
+        )
+    }
+    
+    /// Encrypted instruction: interests both parties share, for a mutual match
+    ///
+    /// Reveals only the AND of the two interest sets as a bitmask
+    /// (bit i = interests[i]). Interests held by only one party never
+    /// leave MPC, so neither side learns the other's unshared interests.
+    #[instruction]
+    pub fn reveal_shared_interests(
+        interests_a_ctxt: Enc<Shared, UserInterests>,
+        interests_b_ctxt: Enc<Shared, UserInterests>,
+    ) -> u32 {
+        let interests_a = interests_a_ctxt.to_arcis();
+        let interests_b = interests_b_ctxt.to_arcis();
+        
+        let mut shared_mask = 0u32;
+        for i in 0..MAX_INTERESTS {
+            let bit = if interests_a.interests[i] && interests_b.interests[i] {
+                1u32 << i
+            } else {
+                0
+            };
+            shared_mask = shared_mask | bit;
+        }
+        
+        shared_mask.reveal()
+    }
 }
-
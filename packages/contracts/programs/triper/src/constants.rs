@@ -0,0 +1,184 @@
+// Program-wide matching parameters
+
+use anchor_lang::prelude::*;
+
+/// Maximum number of match computations a trip can take part in
+pub const MAX_MATCHES_PER_TRIP: u32 = 100;
+
+/// Matches whose total score lands below this are auto-rejected by the
+/// MPC callback and the quota unit spent on them is refunded
+#[constant]
+pub const MIN_TOTAL_SCORE: u8 = 20;
+
+/// Version of the scoring algorithm, stamped on every scored match
+/// Bump whenever the scoring math changes (circuit formulas, profile
+/// weights, thresholds, on-chain weight adjustments) so scores from
+/// different versions are never compared as equals
+/// - 1: route/date/interest Jaccard scoring
+/// - 2: related-interest partial credit from the InterestMatrix
+/// - 3: clash penalty from the IncompatibilityMatrix
+/// - 4: half route credit for waypoints sharing a level-6 parent
+/// - 5: optional age compatibility component
+/// - 6: optional budget compatibility component
+/// - 7: interest score blended with mutual seeking preferences
+/// - 8: neutral, unweighted route score for destination-only pairs
+/// - 9: date overlap over the exact (unfloored) average duration
+/// - 10: date overlap widened by the more flexible trip's date tolerance
+pub const SCORING_VERSION: u8 = 10;
+
+/// How long the rejecting party can undo a rejection (seconds)
+pub const UNDO_REJECT_WINDOW_SECS: i64 = 5 * 60;
+
+/// How long after a rejection the same trip pair can be re-initiated (seconds)
+pub const REMATCH_COOLDOWN_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// How long both parties have to accept once scores land (seconds)
+pub const ACCEPT_WINDOW_SECS: i64 = 7 * 24 * 60 * 60;
+
+/// How long a match can stay Pending before anyone may expire it (seconds)
+pub const MATCH_EXPIRY_SECS: i64 = 14 * 24 * 60 * 60;
+
+/// Size of one encrypted field element (one circuit argument)
+pub const ENCRYPTED_FIELD_SIZE: usize = 32;
+
+/// Maximum waypoints per route - mirrors MAX_WAYPOINTS in the circuit
+pub const MAX_WAYPOINTS: usize = 20;
+
+/// Encrypted fields expected per trip by compute_trip_match:
+/// WaypointData = waypoints[MAX_WAYPOINTS] + waypoint_count + min_interests +
+/// age + daily_budget + seeking.
+/// Dates are public and interests live in UserProfile, so neither is
+/// part of the trip ciphertext.
+pub const MAX_WAYPOINT_FIELDS: usize = MAX_WAYPOINTS + 5;
+
+/// Largest trip ciphertext create_trip accepts - must equal the
+/// #[max_len] on Trip::encrypted_waypoints
+pub const MAX_ENCRYPTED_WAYPOINTS_LEN: usize = 800;
+
+/// Interest flags per profile - mirrors MAX_INTERESTS in the circuit
+pub const INTEREST_COUNT: usize = 32;
+
+/// Bits shifted off a level-7 H3 cell to key its level-6 parent - mirrors
+/// H3_PARENT_SHIFT in the circuit
+pub const H3_PARENT_SHIFT: u64 = 27;
+
+/// Credit (percent of an exact match) for waypoints sharing only a level-6
+/// parent - mirrors PARENT_MATCH_CREDIT_PCT in the circuit
+pub const PARENT_MATCH_CREDIT_PCT: u32 = 50;
+
+/// Newton steps in the cosine isqrt - mirrors ISQRT_ITERATIONS in the circuit
+pub const ISQRT_ITERATIONS: usize = 3;
+
+/// Credit (percent of a shared interest) for a related but unshared
+/// interest - mirrors RELATED_INTEREST_CREDIT_PCT in the circuit
+pub const RELATED_INTEREST_CREDIT_PCT: u32 = 50;
+
+/// Most trips create_trips_batch imports in one transaction
+/// (each entry creates an account; transaction size binds first anyway)
+pub const MAX_TRIPS_PER_BATCH: usize = 4;
+
+/// Extra date gap a fully flexible trip (flexibility 100) bridges, in days
+/// Scales linearly: flexibility 50 bridges half of it
+pub const MAX_FLEXIBILITY_TOLERANCE_DAYS: i64 = 14;
+
+/// Maximum number of match records the same trip pair can create
+pub const MAX_MATCHES_PER_PAIR: u8 = 3;
+
+/// Default lifetime cap on matches between the same two owners, across all
+/// of their trips (ProgramConfig::max_owner_pair_matches)
+pub const DEFAULT_MAX_OWNER_PAIR_MATCHES: u16 = 10;
+
+/// Price of one day of ranking boost (lamports)
+pub const BOOST_LAMPORTS_PER_DAY: u64 = 10_000_000;
+
+/// Longest boost that can be bought in one instruction (days)
+pub const MAX_BOOST_DAYS: u16 = 30;
+
+/// Ranking bonus for boosted trips - added to the 0-100 display score
+pub const BOOST_RANK_BONUS: u16 = 25;
+
+/// Suggestion draw weight of an unboosted trip - the top of the 0-100
+/// display score, so a boosted trip's BOOST_RANK_BONUS lifts its odds by 25%
+pub const SUGGESTION_BASE_WEIGHT: u64 = 100;
+
+/// Default fee per match computation when a destination has no override (lamports)
+pub const BASE_MATCH_FEE_LAMPORTS: u64 = 1_000_000;
+
+/// Refundable deposit locked per queued match computation (lamports)
+/// Returned when the callback lands, forfeited to the treasury on abort/timeout
+pub const COMPUTE_ESCROW_LAMPORTS: u64 = 5_000_000;
+
+/// Default time a locked compute escrow waits for its callback (seconds)
+pub const DEFAULT_COMPUTE_ESCROW_TIMEOUT_SECS: i64 = 60 * 60;
+
+/// Default inactivity period before a user's trips can be swept (seconds)
+pub const DEFAULT_INACTIVITY_PERIOD_SECS: i64 = 90 * 24 * 60 * 60;
+
+/// Default cooling-off between creating a trip and matching it (seconds, 0 = none)
+pub const DEFAULT_MIN_TRIP_AGE_SECS: i64 = 0;
+
+/// Compute units recommended for queueing compute_trip_match, before per-field cost
+pub const BASE_MATCH_COMPUTE_UNITS: u32 = 150_000;
+
+/// Additional compute units per 32-byte encrypted field passed to the circuit
+pub const COMPUTE_UNITS_PER_ENCRYPTED_FIELD: u32 = 2_500;
+
+/// Solana's per-transaction compute unit ceiling
+pub const MAX_TRANSACTION_COMPUTE_UNITS: u32 = 1_400_000;
+
+/// Trip traveler types (Trip.traveler_type / Trip.seeking_type)
+pub const TRAVELER_TYPE_LOCAL: u8 = 0;
+pub const TRAVELER_TYPE_TOURIST: u8 = 1;
+pub const TRAVELER_TYPE_NOMAD: u8 = 2;
+
+/// seeking_type wildcard - accepts counterparties of any traveler type
+pub const SEEKING_ANY: u8 = u8::MAX;
+
+/// Trip.notify_on bits - which match events the owner wants alerts for
+pub const NOTIFY_NEW_MATCH: u8 = 1 << 0;
+pub const NOTIFY_MUTUAL: u8 = 1 << 1;
+/// Reserved for match messaging; nothing emits it yet
+pub const NOTIFY_MESSAGE: u8 = 1 << 2;
+pub const NOTIFY_ALL: u8 = NOTIFY_NEW_MATCH | NOTIFY_MUTUAL | NOTIFY_MESSAGE;
+
+/// Route density scale: density = waypoint_count * scale / (area_bucket + 1)
+pub const ROUTE_DENSITY_SCALE: u32 = 10;
+
+/// Largest bounding-box area bucket, floor(log2(area in waypoint cells + 1))
+/// 2^24 level-7 cells (~5 km² each) comfortably covers a continent
+pub const MAX_ROUTE_AREA_BUCKET: u8 = 24;
+
+/// Default largest route area bucket create_trip accepts
+/// 2^16 level-7 cells is ~340,000 km² - a large region, not a continent
+pub const DEFAULT_MAX_ROUTE_AREA_BUCKET: u8 = 16;
+
+/// Lowest total a healthy self-match may report (route and coverage must be 100)
+/// Balanced weights with no interest data and no date overlap still give 53
+pub const SELF_MATCH_MIN_TOTAL_SCORE: u8 = 50;
+
+/// Trips held by one DestinationIndexShard; a destination that outgrows a
+/// shard spills into the next shard id
+pub const DESTINATION_SHARD_CAPACITY: usize = 32;
+
+/// Most trip pubkeys read_destination_shard emits per page
+pub const DESTINATION_PAGE_SIZE: u8 = 16;
+
+/// Weight of the age compatibility score in total_score, on the same scale
+/// as the scoring profile weights. Only applies when both trips disclose an
+/// age; otherwise the total is the profile's three-way average
+pub const AGE_WEIGHT: u8 = 15;
+
+/// Age gap (years) at which age compatibility reaches 0
+pub const MAX_AGE_GAP_YEARS: u8 = 20;
+
+/// Largest budget weight initiate_match accepts, on the same scale as the
+/// scoring profile weights (a weight of 100 counts as much as the whole
+/// profile)
+pub const MAX_BUDGET_WEIGHT: u8 = 100;
+
+/// Most owners one Blocklist can hold
+pub const MAX_BLOCKED_USERS: usize = 64;
+
+/// Recent waypoint payload checksums one PayloadRegistry remembers; the
+/// oldest is dropped once it is full
+pub const MAX_PAYLOAD_CHECKSUMS: usize = 32;
@@ -26,7 +26,7 @@ pub enum ErrorCode {
     #[msg("Cluster not set")]
     ClusterNotSet,
     
-    #[msg("Encrypted data exceeds 2048 bytes")]
+    #[msg("Encrypted data exceeds the account's maximum length")]
     EncryptedDataTooLarge,
     
     #[msg("End date must be after start date")]
@@ -43,5 +43,157 @@ pub enum ErrorCode {
     
     #[msg("Invalid score value (must be 0-100)")]
     InvalidScore,
+    
+    #[msg("Timestamp is outside the supported range")]
+    InvalidTimestamp,
+    
+    #[msg("Encrypted payload has more fields than the circuit expects")]
+    TooManyEncryptedFields,
+    
+    #[msg("This trip pair has reached its rematch limit")]
+    RematchLimitReached,
+    
+    #[msg("Counterparty does not meet the required verification tier")]
+    InsufficientVerification,
+    
+    #[msg("Invalid x25519 public key")]
+    InvalidPublicKey,
+    
+    #[msg("Match scores are stale - recompute before accepting")]
+    StaleMatch,
+    
+    #[msg("Boost duration must be between 1 and 30 days")]
+    InvalidBoostDuration,
+    
+    #[msg("The acceptance deadline for this match has passed")]
+    AcceptanceExpired,
+    
+    #[msg("The acceptance deadline for this match has not passed yet")]
+    MatchNotExpired,
+    
+    #[msg("Invalid traveler type")]
+    InvalidTravelerType,
+    
+    #[msg("A trip's seeking type excludes the other trip's traveler type")]
+    TypeMismatch,
+    
+    #[msg("Route density inputs are implausible for the waypoint count")]
+    InvalidRouteDensity,
+    
+    #[msg("Encrypted payload is not framed as whole 32-byte fields")]
+    MalformedEncryptedPayload,
+    
+    #[msg("A compute escrow is already locked for this match")]
+    EscrowAlreadyLocked,
+    
+    #[msg("No compute escrow is locked for this match")]
+    NoEscrow,
+    
+    #[msg("The compute escrow has not timed out yet")]
+    EscrowNotExpired,
+    
+    #[msg("Both participants must submit reveal keys before anything is revealed")]
+    NotMutualMatch,
+    
+    #[msg("Batch is empty, too large, or its accounts do not match its entries")]
+    InvalidBatch,
+    
+    #[msg("Too many unresolved matches - resolve some before initiating more")]
+    TooManyPendingMatches,
+    
+    #[msg("The initiator's profile is required to track pending matches")]
+    InitiatorProfileRequired,
+    
+    #[msg("Interest index must be below 32")]
+    InvalidInterestIndex,
+    
+    #[msg("Flexibility must be between 0 and 100")]
+    InvalidFlexibility,
+    
+    #[msg("Match data has not been revealed yet")]
+    RevealNotComplete,
+    
+    #[msg("Salt commitment must be non-zero and differ from the current one")]
+    InvalidSaltCommitment,
+    
+    #[msg("Trip was created too recently to be matched")]
+    TripTooNew,
+    
+    #[msg("Trip has already ended")]
+    TripExpired,
+    
+    #[msg("Destination index shard is full")]
+    DestinationShardFull,
+    
+    #[msg("Destination index shards must be filled in order")]
+    DestinationShardOutOfOrder,
+    
+    #[msg("Page size must be between 1 and DESTINATION_PAGE_SIZE")]
+    InvalidPageSize,
+    
+    #[msg("Revealed seed does not match the committed hash")]
+    SeedCommitmentMismatch,
+    
+    #[msg("Seed can only be revealed in a later slot than its commitment")]
+    SeedRevealTooEarly,
+    
+    #[msg("Commit slot hash is no longer available - commit a new seed")]
+    SeedExpired,
+    
+    #[msg("Destination shard holds no other trips to suggest")]
+    NoSuggestionCandidates,
+    
+    #[msg("Budget weight exceeds MAX_BUDGET_WEIGHT")]
+    InvalidBudgetWeight,
+    
+    #[msg("Route bounding box exceeds the maximum area")]
+    RouteTooLarge,
+    
+    #[msg("Match score is below the minimum set for acceptance")]
+    ScoreBelowThreshold,
+    
+    #[msg("Notification flags contain unknown bits")]
+    InvalidNotificationFlags,
+    
+    #[msg("One of the trip owners has blocked the other")]
+    UserBlocked,
+    
+    #[msg("Blocklist is full - unblock someone first")]
+    BlocklistFull,
+    
+    #[msg("Users can't block themselves")]
+    CannotBlockSelf,
+    
+    #[msg("This trip pair was rejected recently - wait for the rematch cooldown")]
+    RematchCooldown,
+    
+    #[msg("A live match record already exists for this trip pair")]
+    MatchAlreadyExists,
+    
+    #[msg("This encrypted payload was already used by another of the owner's trips")]
+    DuplicatePayload,
+    
+    #[msg("Unknown scoring profile index")]
+    InvalidScoringProfile,
+    
+    #[msg("The initiator's profile was passed twice - omit the owner profile")]
+    DuplicateProfileAccount,
+    
+    #[msg("These two owners have reached their lifetime match limit")]
+    OwnerPairLimitReached,
+    
+    #[msg("Encrypted waypoints are empty")]
+    InvalidEncryptedData,
+    
+    #[msg("The owner has no trip indices left")]
+    TripIndexExhausted,
+    
+    #[msg("The rejected owner's profile is required to track reputation")]
+    RejectedProfileRequired,
+    
+    #[msg("Pass every trip in the destination shard, in shard order")]
+    SuggestionCandidatesMismatch,
+    
+    #[msg("Trips must be passed in the match record's order")]
+    TripOrderMismatch,
 }
-
@@ -1,6 +1,7 @@
 // Events for match computation flow
 
 use anchor_lang::prelude::*;
+use crate::state::MatchStatus;
 
 /// Emitted when a match computation is requested
 /// Off-chain MPC service listens for this event
@@ -34,7 +35,8 @@ pub struct MatchComputationRequested {
     pub timestamp: i64,
 }
 
-/// Emitted when MPC computation completes and scores are stored
+/// Emitted by the compute_trip_match callback alongside MatchComputedEvent
+/// Scores are 0 in encrypted-scores mode, as on the match record
 #[event]
 pub struct MatchComputationCompleted {
     /// Match record PDA
@@ -55,16 +57,46 @@ pub struct MatchComputationCompleted {
     /// Interest similarity score (0-100)
     pub interest_score: u8,
     
+    /// Age compatibility score (0-100), 0 unless both trips disclose an age
+    pub age_score: u8,
+    
+    /// Budget compatibility score (0-100), 0 unless the match weights budget
+    /// and both trips disclose one
+    pub budget_score: u8,
+    
     /// Total match score (0-100)
     pub total_score: u8,
     
-    /// Arcium computation ID
+    /// (route, date, interest) weights the total was computed with
+    pub weights: [u8; 3],
+    
+    /// Interest weight multiplier (0-100) applied at queue time
+    pub interest_freshness: u8,
+    
+    /// Arcium computation ID (the computation account's address)
     pub computation_id: [u8; 32],
     
     /// Completion timestamp
     pub timestamp: i64,
 }
 
+/// Emitted when compute_trip_match settles a pair from public data alone
+/// The match is written as a zero-score Completed match and no MPC runs
+#[event]
+pub struct MatchComputationSkipped {
+    /// Match record PDA
+    pub match_record: Pubkey,
+    
+    /// First trip
+    pub trip_a: Pubkey,
+    
+    /// Second trip
+    pub trip_b: Pubkey,
+    
+    /// Skip timestamp
+    pub timestamp: i64,
+}
+
 /// Emitted when a trip is created
 #[event]
 pub struct TripCreated {
@@ -87,14 +119,227 @@ pub struct TripCreated {
     pub timestamp: i64,
 }
 
-/// Legacy event for MPC callback (from compute_trip_match callback)
+/// Emitted when a trip's encryption key is rotated
+#[event]
+pub struct TripKeyRotated {
+    /// Trip PDA
+    pub trip: Pubkey,
+    
+    /// Trip owner
+    pub owner: Pubkey,
+    
+    /// New x25519 public key
+    pub public_key: [u8; 32],
+    
+    /// Number of match records marked stale
+    pub stale_matches: u32,
+    
+    /// Rotation timestamp
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner edits a trip's dates and route
+#[event]
+pub struct TripUpdated {
+    /// Trip PDA
+    pub trip: Pubkey,
+    
+    /// Trip owner
+    pub owner: Pubkey,
+    
+    /// New date range
+    pub start_date: i64,
+    pub end_date: i64,
+    
+    /// Update timestamp
+    pub timestamp: i64,
+}
+
+/// Public-data-only compatibility estimate between two trips (no MPC)
+#[event]
+pub struct CompatibilityEstimate {
+    /// Trip the estimate is for
+    pub trip_a: Pubkey,
+    
+    /// Candidate trip
+    pub trip_b: Pubkey,
+    
+    /// Date overlap percentage (0-100), equal to the MPC date_score
+    pub date_overlap: u8,
+    
+    /// Whether both trips share the same destination grid hash
+    pub destination_match: bool,
+    
+    /// false when the hashes are from different salt epochs - destination_match
+    /// is then false because the hashes can't be compared, not because they differ
+    pub destination_comparable: bool,
+    
+    /// Seasonal distance between the start weeks (0-26, wraps at New Year)
+    pub week_distance: u8,
+}
+
+/// Emitted when a trip buys a ranking boost
+#[event]
+pub struct TripBoosted {
+    /// Trip PDA
+    pub trip: Pubkey,
+    
+    /// Trip owner
+    pub owner: Pubkey,
+    
+    /// Lamports paid to the treasury
+    pub amount: u64,
+    
+    /// New boost expiry
+    pub boost_until: i64,
+}
+
+/// Emitted when a trip is cloned with a new date window
+#[event]
+pub struct TripCloned {
+    /// Trip the route payload was copied from
+    pub original_trip: Pubkey,
+    
+    /// New trip PDA
+    pub trip: Pubkey,
+    
+    /// Trip owner
+    pub owner: Pubkey,
+    
+    /// Owner's trip index used in the PDA seeds
+    pub trip_index: u32,
+    
+    /// Destination grid hash (shared with the original)
+    pub destination_grid_hash: [u8; 32],
+    
+    /// Clone start date
+    pub start_date: i64,
+    
+    /// Clone end date
+    pub end_date: i64,
+    
+    /// Creation timestamp
+    pub timestamp: i64,
+}
+
+/// Emitted by the compute_trip_match callback - the single definition
+/// clients listen for (matchComputedEvent)
 #[event]
 pub struct MatchComputedEvent {
     pub computation_account: Pubkey,
     pub route_score: u8,
     pub date_score: u8,
     pub interest_score: u8,
+    pub age_score: u8,
+    pub budget_score: u8,
     pub total_score: u8,
+    pub coverage_score: u8,
+    /// Scores above are 0 and stored encrypted for the participants
+    pub scores_encrypted: bool,
+    /// Scoring algorithm version that produced the scores
+    pub scoring_version: u8,
+}
+
+/// Emitted when a match computation fee is charged
+#[event]
+pub struct MatchFeeCharged {
+    /// Match record PDA
+    pub match_record: Pubkey,
+    
+    /// Wallet that paid the fee
+    pub payer: Pubkey,
+    
+    /// Destination the fee was priced for (trip A's)
+    pub destination_grid_hash: [u8; 32],
+    
+    /// Fee charged (lamports)
+    pub fee_lamports: u64,
+}
+
+/// Emitted when a match record is closed and its rent refunded
+#[event]
+pub struct MatchClosed {
+    /// Match record PDA
+    pub match_record: Pubkey,
+    
+    /// Participant who closed it
+    pub closed_by: Pubkey,
+    
+    /// Wallet the rent was refunded to
+    pub rent_payer: Pubkey,
+    
+    /// Lamports refunded to rent_payer
+    pub lamports: u64,
+    
+    /// Whether it was a pending match cancelled before scoring
+    pub cancelled: bool,
+}
+
+/// Emitted when an unaccepted match is expired after its deadline
+#[event]
+pub struct MatchExpired {
+    /// Match record PDA
+    pub match_record: Pubkey,
+    
+    /// Deadline that was missed (0 if the match was never scored)
+    pub accept_deadline: i64,
+    
+    /// Pending expiry that was missed (set at initiate_match)
+    pub expires_at: i64,
+    
+    /// Expiry timestamp
+    pub timestamp: i64,
+}
+
+/// Emitted when an owner's encryption self-check completes
+/// Only pass/fail is published - no trip data is revealed
+#[event]
+pub struct SelfVerificationResult {
+    /// Trip whose ciphertext was checked
+    pub trip: Pubkey,
+    
+    /// Arcium computation account
+    pub computation_account: Pubkey,
+    
+    /// Whether the ciphertext decrypted to a well-formed layout
+    pub is_valid: bool,
+}
+
+/// Emitted when a coordination payload is ready for a mutual match
+/// Each party decrypts only their own copy with their x25519 key
+#[event]
+pub struct CoordinationRevealed {
+    /// Match record PDA
+    pub match_record: Pubkey,
+    
+    /// Trip A owner's payload nonce
+    pub nonce_a: u128,
+    
+    /// Trip A owner's encrypted CoordinationData
+    pub ciphertexts_a: Vec<[u8; 32]>,
+    
+    /// Trip B owner's payload nonce
+    pub nonce_b: u128,
+    
+    /// Trip B owner's encrypted CoordinationData
+    pub ciphertexts_b: Vec<[u8; 32]>,
+    
+    /// Start of the overlapping date window (0 if none)
+    pub window_start: i64,
+    
+    /// End of the overlapping date window (0 if none)
+    pub window_end: i64,
+}
+
+/// Emitted with the interests both parties of a mutual match share
+/// Bit i set = both hold interests[i]; unshared interests are never revealed
+#[event]
+pub struct SharedInterestsRevealed {
+    /// Match record PDA
+    pub match_record: Pubkey,
+    
+    /// AND of the two interest bitsets
+    pub shared_mask: u32,
 }
 
 /// Emitted when a user profile is created
@@ -123,3 +368,365 @@ pub struct UserProfileUpdated {
     pub updated_at: i64,
 }
 
+
+/// Emitted when the verifier sets a user's verification tier
+#[event]
+pub struct VerificationTierSet {
+    /// UserProfile PDA
+    pub user_profile: Pubkey,
+    
+    /// Profile owner
+    pub owner: Pubkey,
+    
+    /// New verification tier
+    pub tier: u8,
+    
+    /// Verifier that set the tier
+    pub verifier: Pubkey,
+}
+
+/// Emitted when an inactive user's trips are deactivated by a cranker
+#[event]
+pub struct UserSwept {
+    /// Inactive user
+    pub owner: Pubkey,
+    
+    /// Last recorded activity
+    pub last_active_at: i64,
+    
+    /// Trips deactivated in this sweep
+    pub trips_deactivated: u32,
+    
+    /// Sweep timestamp
+    pub timestamp: i64,
+}
+
+/// Emitted by estimate_match_args - argument sizing for compute_trip_match
+#[event]
+pub struct MatchArgsEstimate {
+    /// First trip
+    pub trip_a: Pubkey,
+    
+    /// Second trip
+    pub trip_b: Pubkey,
+    
+    /// 32-byte encrypted fields across both routes and both profiles
+    pub encrypted_fields: u32,
+    
+    /// Total Arcium arguments compute_trip_match will queue
+    pub argument_count: u32,
+    
+    /// Compute unit limit the client should request for compute_trip_match
+    pub recommended_compute_units: u32,
+}
+
+/// export_user_data: profile metadata (ciphertext is never exported)
+#[event]
+pub struct UserProfileExported {
+    pub owner: Pubkey,
+    pub user_profile: Pubkey,
+    pub created_at: i64,
+    pub updated_at: i64,
+    pub last_active_at: i64,
+    pub trip_count: u32,
+    pub total_matches: u32,
+    pub is_active: bool,
+    pub verification_tier: u8,
+}
+
+/// export_user_data: one of the user's trips (public fields only)
+#[event]
+pub struct UserTripExported {
+    pub owner: Pubkey,
+    pub trip: Pubkey,
+    pub destination_grid_hash: [u8; 32],
+    pub start_date: i64,
+    pub end_date: i64,
+    pub is_active: bool,
+    pub match_count: u32,
+    pub created_at: i64,
+}
+
+/// export_user_data: summary of a match involving one of the user's trips
+#[event]
+pub struct UserMatchExported {
+    pub owner: Pubkey,
+    pub match_record: Pubkey,
+    pub trip_a: Pubkey,
+    pub trip_b: Pubkey,
+    pub status: MatchStatus,
+    pub total_score: u8,
+    pub created_at: i64,
+}
+
+/// export_user_data: end of the export sequence
+#[event]
+pub struct UserDataExportCompleted {
+    pub owner: Pubkey,
+    pub trips_exported: u32,
+    pub matches_exported: u32,
+    /// Passed accounts that were not the user's trips or matches
+    pub skipped: u32,
+}
+
+/// Emitted by the self_match_test callback - MPC pipeline health
+#[event]
+pub struct SelfMatchHealth {
+    /// Trip scored against itself
+    pub trip: Pubkey,
+    
+    pub route_score: u8,
+    pub date_score: u8,
+    pub interest_score: u8,
+    pub total_score: u8,
+    pub coverage_score: u8,
+    
+    /// Whether the scores are within self-match bounds
+    pub healthy: bool,
+    
+    /// Callback timestamp
+    pub timestamp: i64,
+}
+
+/// Emitted by split_encrypted_payload - the two halves of a combined payload
+#[event]
+pub struct EncryptedPayloadSplit {
+    pub waypoint_fields: u8,
+    pub interest_fields: u8,
+    pub waypoints: Vec<u8>,
+    pub interests: Vec<u8>,
+}
+
+/// Emitted when a match's compute escrow is refunded or forfeited
+#[event]
+pub struct ComputeEscrowSettled {
+    pub match_record: Pubkey,
+    
+    /// Wallet that locked the escrow
+    pub payer: Pubkey,
+    
+    pub lamports: u64,
+    
+    /// true = returned to the payer, false = forfeited to the treasury
+    pub refunded: bool,
+}
+
+/// Emitted when a participant submits a reveal key for a mutual match
+#[event]
+pub struct RevealKeySubmitted {
+    pub match_record: Pubkey,
+    
+    /// Participant who submitted
+    pub participant: Pubkey,
+    
+    /// Whether both keys are now present (reveals are unblocked)
+    pub both_submitted: bool,
+}
+
+/// Emitted when a participant confirms they fetched a match's revealed data
+#[event]
+pub struct RevealAcknowledged {
+    pub match_record: Pubkey,
+    
+    /// Participant who acknowledged
+    pub participant: Pubkey,
+    
+    /// Whether both participants have now acknowledged
+    pub both_acknowledged: bool,
+}
+
+/// Emitted by create_trips_batch once the batch is processed
+#[event]
+pub struct TripsBatchImported {
+    pub owner: Pubkey,
+    
+    /// Trips created
+    pub created: u8,
+    
+    /// Malformed entries skipped
+    pub skipped: u8,
+}
+
+/// Emitted by the check_match_threshold callback - the only score data revealed
+#[event]
+pub struct MatchThresholdChecked {
+    pub match_record: Pubkey,
+    
+    /// Threshold the hidden total score was compared against
+    pub threshold: u8,
+    
+    /// total_score >= threshold
+    pub passed: bool,
+}
+
+/// Emitted by preview_match - a public-data partial score, not an MPC result
+#[event]
+pub struct MatchPreviewed {
+    pub match_preview: Pubkey,
+    
+    pub trip_a: Pubkey,
+    
+    pub trip_b: Pubkey,
+    
+    /// Date overlap (0-100)
+    pub date_score: u8,
+    
+    /// Public-interest Jaccard, None unless both owners publish interests
+    pub interest_score: Option<u8>,
+    
+    /// Balanced total over the dimensions present (route is never included)
+    pub partial_score: u8,
+}
+
+/// Emitted when the admin starts a new destination salt epoch
+#[event]
+pub struct DestinationSaltRotated {
+    /// New epoch
+    pub epoch: u32,
+    
+    /// SHA256 of the new salt
+    pub salt_commitment: [u8; 32],
+    
+    /// Rotation timestamp
+    pub timestamp: i64,
+}
+
+/// Emitted when the admin changes an interest's row in the InterestMatrix
+#[event]
+pub struct InterestRelatednessUpdated {
+    pub interest: u8,
+    
+    /// Interests now related to it (bit j = interest j)
+    pub related_mask: u32,
+}
+
+/// Emitted when the admin changes an interest's row in the IncompatibilityMatrix
+#[event]
+pub struct InterestIncompatibilityUpdated {
+    pub interest: u8,
+    
+    /// Interests now flagged as clashing with it (bit j = interest j)
+    pub incompatible_mask: u32,
+}
+
+/// Emitted by read_destination_shard - one page of a destination's trips
+#[event]
+pub struct DestinationShardPage {
+    pub destination_grid_hash: [u8; 32],
+    
+    pub shard_id: u16,
+    
+    /// Index of the first trip in this page within the shard
+    pub offset: u16,
+    
+    /// Trips in the page (empty past the end of the shard)
+    pub trips: Vec<Pubkey>,
+    
+    /// Trips in the whole shard
+    pub shard_len: u16,
+    
+    /// Whether the shard is full, so shard_id + 1 may hold more trips
+    pub shard_full: bool,
+}
+
+/// Emitted when a trip owner commits a suggestion seed
+#[event]
+pub struct SuggestionSeedCommitted {
+    pub trip: Pubkey,
+    
+    /// SHA256 of the seed
+    pub commitment: [u8; 32],
+    
+    /// Slot whose hash will be mixed into the randomness
+    pub commit_slot: u64,
+    
+    /// Destination index shard the suggestion will be drawn from
+    pub shard_id: u16,
+}
+
+/// Emitted by reveal_and_suggest - a fair draw from the committed shard
+#[event]
+pub struct MatchSuggested {
+    /// Trip the suggestion is for
+    pub trip: Pubkey,
+    
+    /// Suggested counterpart trip
+    pub suggested_trip: Pubkey,
+    
+    pub shard_id: u16,
+    
+    /// SHA256(seed || commit slot hash || trip) the draw was taken from
+    pub randomness: [u8; 32],
+    
+    /// Whether the suggested trip's boost was active, weighting the draw
+    pub boosted: bool,
+}
+
+/// Emitted per participant trip when a match lands (NOTIFY_NEW_MATCH) or
+/// becomes Mutual (NOTIFY_MUTUAL)
+#[event]
+pub struct MatchNotification {
+    pub match_record: Pubkey,
+    
+    /// Trip the notification is for
+    pub trip: Pubkey,
+    
+    /// Owner of that trip
+    pub owner: Pubkey,
+    
+    /// NOTIFY_* bit this notification is about
+    pub kind: u8,
+    
+    /// Whether the trip's notify_on enables `kind` - clients alert only when set
+    pub notify: bool,
+    
+    pub timestamp: i64,
+}
+
+/// Emitted by describe_trip - every public Trip field in a stable shape,
+/// so clients don't depend on the account layout. The ciphertext and its
+/// x25519 key are left out
+#[event]
+pub struct TripDescribed {
+    pub trip: Pubkey,
+    
+    pub owner: Pubkey,
+    
+    pub destination_grid_hash: [u8; 32],
+    
+    /// Salt epoch destination_grid_hash was computed under
+    pub salt_epoch: u32,
+    
+    pub start_date: i64,
+    
+    pub end_date: i64,
+    
+    pub is_active: bool,
+    
+    pub match_count: u32,
+    
+    pub min_counterparty_tier: u8,
+    
+    pub traveler_type: u8,
+    
+    pub seeking_type: u8,
+    
+    pub route_density: u8,
+    
+    pub flexibility: u8,
+    
+    pub notify_on: u8,
+    
+    pub destination_only_match: bool,
+    
+    pub boost_until: i64,
+    
+    /// Whether the boost is active at `timestamp`
+    pub boosted: bool,
+    
+    pub created_at: i64,
+    
+    pub updated_at: i64,
+    
+    pub timestamp: i64,
+}
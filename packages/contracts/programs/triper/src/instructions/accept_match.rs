@@ -1,12 +1,16 @@
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
-use crate::state::{MatchRecord, MatchStatus, Trip};
+use crate::instructions::{notify_participants, participant_profile, release_pending_slot};
+use crate::constants::NOTIFY_MUTUAL;
+use crate::state::{MatchRecord, MatchStatus, Trip, UserProfile};
 
 #[derive(Accounts)]
 pub struct AcceptMatch<'info> {
+    /// Only scored matches can be accepted - a Pending match has no scores yet
     #[account(
         mut,
-        constraint = match_account.status == MatchStatus::Pending @ ErrorCode::InvalidMatchStatus
+        constraint = !match_account.is_stale @ ErrorCode::StaleMatch,
+        constraint = match_account.status == MatchStatus::Completed @ ErrorCode::InvalidMatchStatus
     )]
     pub match_account: Account<'info, MatchRecord>,
     
@@ -16,17 +20,61 @@ pub struct AcceptMatch<'info> {
     )]
     pub trip: Account<'info, Trip>,
     
+    /// The match's other trip - read for its owner's notification preference
+    #[account(
+        constraint = (counterparty_trip.key() == match_account.trip_a
+            || counterparty_trip.key() == match_account.trip_b)
+            && counterparty_trip.key() != trip.key() @ ErrorCode::UnauthorizedAccess
+    )]
+    pub counterparty_trip: Account<'info, Trip>,
+    
+    /// Initiator's profile - required while the match holds a pending-match slot
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.pending_slot_owner.as_ref()],
+        bump = initiator_profile.bump
+    )]
+    pub initiator_profile: Option<Account<'info, UserProfile>>,
+    
+    /// Trip A owner's profile - credited with the outcome when passed
+    /// (omit it while initiator_profile already stands in for it)
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.trip_a_owner.as_ref()],
+        bump = owner_profile_a.bump
+    )]
+    pub owner_profile_a: Option<Account<'info, UserProfile>>,
+    
+    /// Trip B owner's profile - credited with the outcome when passed
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.trip_b_owner.as_ref()],
+        bump = owner_profile_b.bump
+    )]
+    pub owner_profile_b: Option<Account<'info, UserProfile>>,
+    
     pub user: Signer<'info>,
 }
 
 pub fn accept_match_handler(ctx: Context<AcceptMatch>) -> Result<()> {
-    let match_account = &mut ctx.accounts.match_account;
-    let user_key = ctx.accounts.user.key();
+    let accounts = &mut *ctx.accounts;
+    let match_account = &mut accounts.match_account;
+    let user_key = accounts.user.key();
+    
+    // The callback sets the deadline when it moves the match to Completed
+    require!(
+        Clock::get()?.unix_timestamp <= match_account.accept_deadline,
+        ErrorCode::AcceptanceExpired
+    );
+    
+    // Low-scoring matches can't be accepted, whatever both parties want
+    require!(match_account.meets_min_score(), ErrorCode::ScoreBelowThreshold);
     
     // Check if user is one of the trip owners
-    if user_key == ctx.accounts.trip.owner && ctx.accounts.trip.key() == match_account.trip_a {
+    let trip_key = accounts.trip.key();
+    if user_key == match_account.trip_a_owner && trip_key == match_account.trip_a {
         match_account.trip_a_accepted = true;
-    } else if user_key == ctx.accounts.trip.owner && ctx.accounts.trip.key() == match_account.trip_b {
+    } else if user_key == match_account.trip_b_owner && trip_key == match_account.trip_b {
         match_account.trip_b_accepted = true;
     } else {
         return Err(ErrorCode::Unauthorized.into());
@@ -35,6 +83,23 @@ pub fn accept_match_handler(ctx: Context<AcceptMatch>) -> Result<()> {
     // If both parties accepted, update status to Mutual
     if match_account.trip_a_accepted && match_account.trip_b_accepted {
         match_account.status = MatchStatus::Mutual;
+        release_pending_slot(match_account, accounts.initiator_profile.as_mut())?;
+        
+        // Both owners' reputations count the mutual match
+        let (owner_a, owner_b) = (match_account.trip_a_owner, match_account.trip_b_owner);
+        if let Some(profile) = participant_profile(&mut accounts.initiator_profile, &mut accounts.owner_profile_a, owner_a)? {
+            profile.record_mutual_match();
+        }
+        if let Some(profile) = participant_profile(&mut accounts.initiator_profile, &mut accounts.owner_profile_b, owner_b)? {
+            profile.record_mutual_match();
+        }
+        
+        notify_participants(
+            accounts.match_account.key(),
+            &accounts.trip,
+            &accounts.counterparty_trip,
+            NOTIFY_MUTUAL,
+        )?;
         msg!("🎉 Match mutually accepted! Encrypted trip details will be revealed via Arcium MXE.");
     } else {
         msg!("✓ Match accepted by one party. Waiting for the other party.");
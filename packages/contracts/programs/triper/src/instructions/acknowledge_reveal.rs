@@ -0,0 +1,50 @@
+// Acknowledge Reveal Instruction
+// Each participant records that they fetched a mutual match's revealed data
+
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::RevealAcknowledged;
+use crate::state::{MatchRecord, MatchStatus, Trip};
+
+#[derive(Accounts)]
+pub struct AcknowledgeReveal<'info> {
+    #[account(
+        mut,
+        constraint = match_record.status == MatchStatus::Mutual @ ErrorCode::InvalidMatchStatus,
+        constraint = match_record.revealed @ ErrorCode::RevealNotComplete
+    )]
+    pub match_record: Account<'info, MatchRecord>,
+    
+    /// Acknowledging participant's trip in this match
+    #[account(
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn acknowledge_reveal_handler(ctx: Context<AcknowledgeReveal>) -> Result<()> {
+    let match_record = &mut ctx.accounts.match_record;
+    let trip_key = ctx.accounts.trip.key();
+    
+    if trip_key == match_record.trip_a {
+        match_record.acknowledged_by_a = true;
+    } else if trip_key == match_record.trip_b {
+        match_record.acknowledged_by_b = true;
+    } else {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    
+    let both_acknowledged = match_record.acknowledged_by_a && match_record.acknowledged_by_b;
+    
+    emit!(RevealAcknowledged {
+        match_record: match_record.key(),
+        participant: ctx.accounts.user.key(),
+        both_acknowledged,
+    });
+    
+    msg!("Reveal acknowledged for match {}", match_record.key());
+    
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// Block / Unblock User Instructions
+// Owner-managed list of wallets that can't start a match with them
+
+use anchor_lang::prelude::*;
+use crate::state::Blocklist;
+use crate::error::ErrorCode;
+use crate::constants::MAX_BLOCKED_USERS;
+
+#[derive(Accounts)]
+pub struct BlockUser<'info> {
+    /// Blocklist PDA: [b"blocklist", user] - created on first block
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + Blocklist::INIT_SPACE,
+        seeds = [b"blocklist", user.key().as_ref()],
+        bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Blocking an owner that is already blocked is a no-op
+pub fn block_user_handler(ctx: Context<BlockUser>, blocked: Pubkey) -> Result<()> {
+    let user = ctx.accounts.user.key();
+    require!(blocked != user, ErrorCode::CannotBlockSelf);
+    
+    let blocklist = &mut ctx.accounts.blocklist;
+    blocklist.owner = user;
+    blocklist.bump = ctx.bumps.blocklist;
+    
+    if !blocklist.is_blocked(&blocked) {
+        require!(blocklist.blocked.len() < MAX_BLOCKED_USERS, ErrorCode::BlocklistFull);
+        blocklist.blocked.push(blocked);
+    }
+    
+    msg!("{} blocked {}", user, blocked);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnblockUser<'info> {
+    #[account(
+        mut,
+        seeds = [b"blocklist", user.key().as_ref()],
+        bump = blocklist.bump
+    )]
+    pub blocklist: Account<'info, Blocklist>,
+    
+    pub user: Signer<'info>,
+}
+
+/// Unblocking an owner that isn't blocked is a no-op
+pub fn unblock_user_handler(ctx: Context<UnblockUser>, blocked: Pubkey) -> Result<()> {
+    let blocklist = &mut ctx.accounts.blocklist;
+    blocklist.blocked.retain(|owner| owner != &blocked);
+    
+    msg!("{} unblocked {}", blocklist.owner, blocked);
+    
+    Ok(())
+}
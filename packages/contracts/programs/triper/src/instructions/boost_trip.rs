@@ -0,0 +1,70 @@
+// Boost Trip Instruction
+// Pays the treasury for a time-limited ranking boost (display only, scores untouched)
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+use crate::state::Trip;
+use crate::error::ErrorCode;
+use crate::events::TripBoosted;
+use crate::constants::{BOOST_LAMPORTS_PER_DAY, MAX_BOOST_DAYS};
+
+#[derive(Accounts)]
+pub struct BoostTrip<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized,
+        constraint = trip.is_active @ ErrorCode::TripNotActive
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    /// Treasury PDA: [b"treasury"] - lamport sink for platform fees
+    /// CHECK: only receives lamports, address enforced by seeds
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn boost_trip_handler(ctx: Context<BoostTrip>, days: u16) -> Result<()> {
+    require!(
+        days > 0 && days <= MAX_BOOST_DAYS,
+        ErrorCode::InvalidBoostDuration
+    );
+    
+    let amount = BOOST_LAMPORTS_PER_DAY * days as u64;
+    
+    transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+    
+    // Extending an active boost stacks on top of the current expiry
+    let now = Clock::get()?.unix_timestamp;
+    let trip = &mut ctx.accounts.trip;
+    let base = trip.boost_until.max(now);
+    trip.boost_until = base + days as i64 * 24 * 60 * 60;
+    
+    emit!(TripBoosted {
+        trip: trip.key(),
+        owner: trip.owner,
+        amount,
+        boost_until: trip.boost_until,
+    });
+    
+    msg!("Trip {} boosted until {}", trip.key(), trip.boost_until);
+    
+    Ok(())
+}
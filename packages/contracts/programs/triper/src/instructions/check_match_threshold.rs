@@ -0,0 +1,244 @@
+// Threshold-only match check
+// Reveals whether a pending match clears a score threshold - no component score leaves MPC
+
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::SignerAccount;
+use crate::ID;
+use crate::ID_CONST;
+use crate::COMP_DEF_OFFSET_CHECK_MATCH_THRESHOLD;
+use crate::constants::{AGE_WEIGHT, ENCRYPTED_FIELD_SIZE, MAX_AGE_GAP_YEARS, MAX_WAYPOINT_FIELDS};
+use crate::error::ErrorCode;
+use crate::utils::push_encrypted_input;
+use crate::state::{IncompatibilityMatrix, InterestMatrix, MatchRecord, MatchStatus, Trip, UserProfile};
+
+/// Initialize the computation definition account
+#[init_computation_definition_accounts("check_match_threshold", payer)]
+#[derive(Accounts)]
+pub struct InitCheckMatchThresholdCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a threshold-only check for a pending match
+#[queue_computation_accounts("check_match_threshold", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct CheckMatchThreshold<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_MATCH_THRESHOLD)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+
+    /// Pending match to check - scored in full later by compute_trip_match
+    #[account(
+        mut,
+        constraint = match_record.status == MatchStatus::Pending @ ErrorCode::InvalidMatchStatus,
+    )]
+    pub match_record: Account<'info, MatchRecord>,
+
+    #[account(address = match_record.trip_a)]
+    pub trip_a: Account<'info, Trip>,
+
+    #[account(address = match_record.trip_b)]
+    pub trip_b: Account<'info, Trip>,
+
+    #[account(
+        seeds = [b"user_profile", trip_a.owner.as_ref()],
+        bump = user_profile_a.bump
+    )]
+    pub user_profile_a: Account<'info, UserProfile>,
+
+    #[account(
+        seeds = [b"user_profile", trip_b.owner.as_ref()],
+        bump = user_profile_b.bump
+    )]
+    pub user_profile_b: Account<'info, UserProfile>,
+
+    /// Interest taxonomy: [b"interest_matrix"] - related-interest credit
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"interest_matrix"],
+        bump
+    )]
+    pub interest_matrix: UncheckedAccount<'info>,
+
+    /// Interest clashes: [b"incompatibility_matrix"] - interest score penalty
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"incompatibility_matrix"],
+        bump
+    )]
+    pub incompatibility_matrix: UncheckedAccount<'info>,
+}
+
+/// Callback after the threshold check completes
+#[callback_accounts("check_match_threshold")]
+#[derive(Accounts)]
+pub struct CheckMatchThresholdCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_CHECK_MATCH_THRESHOLD)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: computation_account for emitting events
+    pub computation_account: UncheckedAccount<'info>,
+
+    /// Match the result is stored on (passed via callback accounts)
+    #[account(mut)]
+    pub match_record: Account<'info, MatchRecord>,
+}
+
+pub fn check_match_threshold_handler(
+    ctx: Context<CheckMatchThreshold>,
+    computation_offset: u64,
+    threshold: u8,
+    nonce_trip_a: u128,
+    nonce_trip_b: u128,
+    nonce_profile_a: u128,
+    nonce_profile_b: u128,
+) -> Result<()> {
+    require!(threshold <= 100, ErrorCode::InvalidScore);
+
+    let payer_key = ctx.accounts.payer.key();
+    let trip_a = &ctx.accounts.trip_a;
+    let trip_b = &ctx.accounts.trip_b;
+
+    // Only the two participants may probe their match
+    require!(
+        payer_key == trip_a.owner || payer_key == trip_b.owner,
+        ErrorCode::Unauthorized
+    );
+
+    require!(
+        trip_a.encrypted_waypoints.len() / ENCRYPTED_FIELD_SIZE <= MAX_WAYPOINT_FIELDS
+            && trip_b.encrypted_waypoints.len() / ENCRYPTED_FIELD_SIZE <= MAX_WAYPOINT_FIELDS,
+        ErrorCode::TooManyEncryptedFields
+    );
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let user_profile_a = &ctx.accounts.user_profile_a;
+    let user_profile_b = &ctx.accounts.user_profile_b;
+
+    // Same input layout as compute_trip_match
+    let segments: [(&[u8], [u8; 32], u128); 4] = [
+        (&trip_a.encrypted_waypoints, trip_a.public_key, nonce_trip_a),
+        (&trip_b.encrypted_waypoints, trip_b.public_key, nonce_trip_b),
+        (&user_profile_a.encrypted_data, user_profile_a.public_key, nonce_profile_a),
+        (&user_profile_b.encrypted_data, user_profile_b.public_key, nonce_profile_b),
+    ];
+
+    let mut args = Vec::new();
+    for (ciphertext, public_key, nonce) in segments {
+        push_encrypted_input(&mut args, public_key, nonce, ciphertext)?;
+    }
+
+    let [route_weight, date_weight, interest_weight] = ctx.accounts.match_record.weights;
+    args.push(Argument::PlaintextU8(route_weight));
+    args.push(Argument::PlaintextU8(date_weight));
+    args.push(Argument::PlaintextU8(interest_weight));
+    args.push(Argument::PlaintextU8(AGE_WEIGHT));
+    args.push(Argument::PlaintextU8(MAX_AGE_GAP_YEARS));
+    args.push(Argument::PlaintextU8(ctx.accounts.match_record.budget_weight));
+    args.push(Argument::PlaintextBool(trip_a.destination_only_with(trip_b)));
+    args.push(Argument::PlaintextU32(trip_a.date_tolerance_with(trip_b) as u32));
+    args.push(Argument::PlaintextU8(threshold));
+
+    // Same relatedness the production score uses
+    for row in InterestMatrix::rows_or_default(&ctx.accounts.interest_matrix)? {
+        args.push(Argument::PlaintextU32(row));
+    }
+    for row in IncompatibilityMatrix::rows_or_default(&ctx.accounts.incompatibility_matrix)? {
+        args.push(Argument::PlaintextU32(row));
+    }
+
+    // Stage the threshold for the callback; any earlier result is void
+    // until this computation lands
+    let match_record = &mut ctx.accounts.match_record;
+    match_record.score_threshold = threshold;
+    match_record.threshold_checked = false;
+    match_record.threshold_passed = false;
+
+    let match_record_key = match_record.key();
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![CheckMatchThresholdCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: match_record_key,
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    msg!("Queued threshold check (>= {}) for match: {}", threshold, match_record_key);
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+// Clone Trip Instruction
+// Creates a date variant of an existing trip without re-encrypting the route
+
+use anchor_lang::prelude::*;
+use crate::state::{DestinationIndexShard, Trip, UserProfile};
+use crate::error::ErrorCode;
+use crate::events::TripCloned;
+use super::create_trip::require_open_shard;
+
+#[derive(Accounts)]
+#[instruction(start_date: i64, end_date: i64, shard_id: u16)]
+pub struct CloneTrip<'info> {
+    /// Trip whose route payload is copied
+    #[account(
+        constraint = source_trip.owner == user.key() @ ErrorCode::Unauthorized,
+    )]
+    pub source_trip: Account<'info, Trip>,
+
+    /// Clone PDA: [b"trip", user, user_profile.next_trip_index] - clones
+    /// share the owner's trip index sequence with create_trip
+    #[account(
+        init,
+        payer = user,
+        space = 8 + Trip::INIT_SPACE,
+        seeds = [
+            b"trip",
+            user.key().as_ref(),
+            &user_profile.next_trip_index.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub trip: Account<'info, Trip>,
+
+    /// Owner's profile - hands out the trip index
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+
+    /// Open shard of the source destination's index, created on first use
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DestinationIndexShard::INIT_SPACE,
+        seeds = [b"dest", source_trip.destination_grid_hash.as_ref(), &shard_id.to_le_bytes()],
+        bump
+    )]
+    pub destination_shard: Account<'info, DestinationIndexShard>,
+
+    /// Shard shard_id - 1, which must be full (omitted for shard 0)
+    #[account(
+        seeds = [b"dest", source_trip.destination_grid_hash.as_ref(), &shard_id.saturating_sub(1).to_le_bytes()],
+        bump = previous_shard.bump
+    )]
+    pub previous_shard: Option<Account<'info, DestinationIndexShard>>,
+
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn clone_trip_handler(
+    ctx: Context<CloneTrip>,
+    start_date: i64,
+    end_date: i64,
+    shard_id: u16,
+) -> Result<()> {
+    require!(
+        end_date > start_date,
+        ErrorCode::InvalidDateRange
+    );
+    require_open_shard(&ctx.accounts.destination_shard, ctx.accounts.previous_shard.as_ref(), shard_id)?;
+
+    let user_profile = &mut ctx.accounts.user_profile;
+    let trip_index = user_profile.reserve_trip_indices(1)?;
+    user_profile.trip_count = user_profile.trip_count.saturating_add(1);
+
+    let source_trip = &ctx.accounts.source_trip;
+    let trip = &mut ctx.accounts.trip;
+
+    trip.owner = source_trip.owner;
+    trip.destination_grid_hash = source_trip.destination_grid_hash;
+    trip.start_date = start_date;
+    trip.end_date = end_date;
+    trip.encrypted_waypoints = source_trip.encrypted_waypoints.clone();
+    trip.public_key = source_trip.public_key;
+    trip.is_active = true;
+    trip.match_count = 0;
+    trip.min_counterparty_tier = source_trip.min_counterparty_tier;
+    trip.traveler_type = source_trip.traveler_type;
+    trip.seeking_type = source_trip.seeking_type;
+    trip.route_density = source_trip.route_density;
+    trip.flexibility = source_trip.flexibility;
+    trip.salt_epoch = source_trip.salt_epoch;
+    trip.boost_until = 0;
+    trip.created_at = Clock::get()?.unix_timestamp;
+    trip.updated_at = trip.created_at;
+    trip.notify_on = source_trip.notify_on;
+    trip.destination_only_match = source_trip.destination_only_match;
+    trip.bump = ctx.bumps.trip;
+
+    ctx.accounts.destination_shard.push_trip(
+        trip.destination_grid_hash,
+        shard_id,
+        ctx.bumps.destination_shard,
+        trip.key(),
+    );
+
+    msg!("Trip cloned: {} -> {} (index {})", source_trip.key(), trip.key(), trip_index);
+    msg!("Dates: {} to {}", start_date, end_date);
+
+    emit!(TripCloned {
+        original_trip: source_trip.key(),
+        trip: trip.key(),
+        owner: trip.owner,
+        trip_index,
+        destination_grid_hash: trip.destination_grid_hash,
+        start_date,
+        end_date,
+        timestamp: trip.created_at,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,172 @@
+// Close Match Instructions
+// Rent always goes back to the wallet that funded the record, which may be
+// a relayer rather than either participant
+
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::MatchClosed;
+use crate::instructions::release_pending_slot;
+use crate::state::{canonical_pair, MatchRecord, MatchStatus, PairHistory, Trip, UserProfile};
+
+#[derive(Accounts)]
+pub struct CancelPendingMatch<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = match_account.status == MatchStatus::Pending @ ErrorCode::InvalidMatchStatus,
+        // A locked escrow settles through the callback or forfeit_compute_escrow
+        constraint = match_account.escrow_lamports == 0 @ ErrorCode::EscrowAlreadyLocked
+    )]
+    pub match_account: Account<'info, MatchRecord>,
+    
+    #[account(mut, address = match_account.trip_a)]
+    pub trip_a: Account<'info, Trip>,
+    
+    #[account(mut, address = match_account.trip_b)]
+    pub trip_b: Account<'info, Trip>,
+    
+    /// Initiator's profile - required while the match holds a pending-match slot
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.pending_slot_owner.as_ref()],
+        bump = initiator_profile.bump
+    )]
+    pub initiator_profile: Option<Account<'info, UserProfile>>,
+    
+    /// CHECK: receives the rent, must be the original funder
+    #[account(mut, address = match_account.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn cancel_pending_match_handler(ctx: Context<CancelPendingMatch>) -> Result<()> {
+    let user_key = ctx.accounts.user.key();
+    
+    // Either participant may cancel
+    require!(
+        user_key == ctx.accounts.trip_a.owner || user_key == ctx.accounts.trip_b.owner,
+        ErrorCode::Unauthorized
+    );
+    
+    // Nothing was scored, so give back the quota unit and the pending slot
+    release_pending_slot(&mut ctx.accounts.match_account, ctx.accounts.initiator_profile.as_mut())?;
+    let trip_a = &mut ctx.accounts.trip_a;
+    trip_a.match_count = trip_a.match_count.saturating_sub(1);
+    let trip_b = &mut ctx.accounts.trip_b;
+    trip_b.match_count = trip_b.match_count.saturating_sub(1);
+    
+    emit!(MatchClosed {
+        match_record: ctx.accounts.match_account.key(),
+        closed_by: user_key,
+        rent_payer: ctx.accounts.rent_payer.key(),
+        lamports: ctx.accounts.match_account.to_account_info().lamports(),
+        cancelled: true,
+    });
+    
+    msg!("Pending match cancelled: {}", ctx.accounts.match_account.key());
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseMatch<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = (match_account.status == MatchStatus::Rejected
+            || match_account.status == MatchStatus::Expired) @ ErrorCode::InvalidMatchStatus,
+        constraint = match_account.escrow_lamports == 0 @ ErrorCode::EscrowAlreadyLocked
+    )]
+    pub match_account: Account<'info, MatchRecord>,
+    
+    /// Trip account to verify ownership
+    #[account(
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    /// Pair history: [b"pair_history", first, second] (canonical_pair order)
+    /// Carries the rejection forward so closing doesn't skip the rematch cooldown
+    #[account(
+        mut,
+        seeds = [
+            b"pair_history",
+            canonical_pair(match_account.trip_a, match_account.trip_b).0.as_ref(),
+            canonical_pair(match_account.trip_a, match_account.trip_b).1.as_ref(),
+        ],
+        bump = pair_history.bump
+    )]
+    pub pair_history: Account<'info, PairHistory>,
+    
+    /// CHECK: receives the rent, must be the original funder
+    #[account(mut, address = match_account.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn close_match_handler(ctx: Context<CloseMatch>) -> Result<()> {
+    let match_account = &ctx.accounts.match_account;
+    let trip_key = ctx.accounts.trip.key();
+    
+    // Check if user owns one of the trips in the match
+    require!(
+        trip_key == match_account.trip_a || trip_key == match_account.trip_b,
+        ErrorCode::Unauthorized
+    );
+    
+    if match_account.status == MatchStatus::Rejected {
+        let pair_history = &mut ctx.accounts.pair_history;
+        pair_history.last_rejected_at = pair_history.last_rejected_at.max(match_account.rejected_at);
+    }
+    
+    emit!(MatchClosed {
+        match_record: match_account.key(),
+        closed_by: ctx.accounts.user.key(),
+        rent_payer: ctx.accounts.rent_payer.key(),
+        lamports: match_account.to_account_info().lamports(),
+        cancelled: false,
+    });
+    
+    msg!("Match closed: {}", match_account.key());
+    
+    Ok(())
+}
+
+/// A mutual match is shared history, so both owners must sign to close it
+#[derive(Accounts)]
+pub struct CloseMutualMatch<'info> {
+    #[account(
+        mut,
+        close = rent_payer,
+        constraint = match_account.status == MatchStatus::Mutual @ ErrorCode::InvalidMatchStatus
+    )]
+    pub match_account: Account<'info, MatchRecord>,
+    
+    #[account(address = match_account.trip_a_owner @ ErrorCode::Unauthorized)]
+    pub owner_a: Signer<'info>,
+    
+    #[account(address = match_account.trip_b_owner @ ErrorCode::Unauthorized)]
+    pub owner_b: Signer<'info>,
+    
+    /// CHECK: receives the rent, must be the original funder
+    #[account(mut, address = match_account.rent_payer)]
+    pub rent_payer: UncheckedAccount<'info>,
+}
+
+pub fn close_mutual_match_handler(ctx: Context<CloseMutualMatch>) -> Result<()> {
+    let match_account = &ctx.accounts.match_account;
+    
+    emit!(MatchClosed {
+        match_record: match_account.key(),
+        closed_by: ctx.accounts.owner_a.key(),
+        rent_payer: ctx.accounts.rent_payer.key(),
+        lamports: match_account.to_account_info().lamports(),
+        cancelled: false,
+    });
+    
+    msg!("Mutual match closed by both owners: {}", match_account.key());
+    
+    Ok(())
+}
@@ -9,6 +9,9 @@ use crate::ID;
 use crate::ID_CONST;
 use crate::COMP_DEF_OFFSET_COMPUTE_TRIP_MATCH;
 use crate::error::ErrorCode;
+use crate::state::{DestinationFee, MatchStatus, ProgramConfig};
+use crate::scoring::interest_freshness_pct;
+use crate::constants::{BASE_MATCH_FEE_LAMPORTS, DEFAULT_COMPUTE_ESCROW_TIMEOUT_SECS};
 
 /// Initialize the computation definition account
 #[init_computation_definition_accounts("compute_trip_match", payer)]
@@ -88,7 +91,14 @@ pub struct ComputeTripMatch<'info> {
     pub arcium_program: Program<'info, Arcium>,
     
     /// Match record to be updated in callback
-    #[account(mut)]
+    /// Only an unscored match, or a completed one whose scores went stale,
+    /// can be (re)computed - settled outcomes are never overwritten
+    #[account(
+        mut,
+        constraint = (match_record.status == MatchStatus::Pending
+            || (match_record.status == MatchStatus::Completed && match_record.is_stale))
+            @ ErrorCode::InvalidMatchStatus
+    )]
     pub match_record: Account<'info, crate::state::MatchRecord>,
     
     /// Trip A - contains encrypted_waypoints and public_key
@@ -100,22 +110,113 @@ pub struct ComputeTripMatch<'info> {
     /// Trip B - contains encrypted_waypoints and public_key
     #[account(
         constraint = trip_b.is_active @ ErrorCode::TripNotActive,
+        constraint = trip_b.key() != trip_a.key() @ ErrorCode::SameTripMatch,
     )]
     pub trip_b: Account<'info, crate::state::Trip>,
     
     /// UserProfile for Trip A owner - contains encrypted interests
+    /// (total_matches bumped when the prefilter settles the match)
     #[account(
+        mut,
         constraint = user_profile_a.owner == trip_a.owner @ ErrorCode::UnauthorizedAccess,
         constraint = user_profile_a.is_active @ ErrorCode::UserProfileNotActive,
     )]
     pub user_profile_a: Account<'info, crate::state::UserProfile>,
     
     /// UserProfile for Trip B owner - contains encrypted interests
+    /// (total_matches bumped when the prefilter settles the match)
     #[account(
+        mut,
         constraint = user_profile_b.owner == trip_b.owner @ ErrorCode::UnauthorizedAccess,
         constraint = user_profile_b.is_active @ ErrorCode::UserProfileNotActive,
     )]
     pub user_profile_b: Account<'info, crate::state::UserProfile>,
+    
+    /// Treasury PDA: [b"treasury"] - receives the match fee
+    /// CHECK: only receives lamports, address enforced by seeds
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Fee override for trip A's destination: [b"destination_fee", destination_grid_hash]
+    /// Always required so callers can't dodge an override by omitting it;
+    /// if uninitialized the base fee applies
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"destination_fee", trip_a.destination_grid_hash.as_ref()],
+        bump
+    )]
+    pub destination_fee: UncheckedAccount<'info>,
+    
+    /// Program config: [b"program_config"] - interest half-life and escrow timeout
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Interest taxonomy: [b"interest_matrix"] - related-interest credit
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"interest_matrix"],
+        bump
+    )]
+    pub interest_matrix: UncheckedAccount<'info>,
+    
+    /// Interest clashes: [b"incompatibility_matrix"] - interest score penalty
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"incompatibility_matrix"],
+        bump
+    )]
+    pub incompatibility_matrix: UncheckedAccount<'info>,
+}
+
+impl<'info> ComputeTripMatch<'info> {
+    /// Fee for this computation: the destination override if set, else the base fee
+    pub fn match_fee(&self) -> Result<u64> {
+        let info = &self.destination_fee;
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(BASE_MATCH_FEE_LAMPORTS);
+        }
+        let data = info.try_borrow_data()?;
+        let override_fee = DestinationFee::try_deserialize(&mut &data[..])?;
+        Ok(override_fee.fee_lamports)
+    }
+    
+    /// The program config, if it has been initialized
+    fn program_config(&self) -> Result<Option<ProgramConfig>> {
+        let info = &self.config;
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(ProgramConfig::try_deserialize(&mut &data[..])?))
+    }
+    
+    /// Interest weight multiplier from the staler of the two interest sets
+    /// 100 (no decay) while the config is missing or decay is disabled
+    pub fn interest_freshness(&self, now: i64) -> Result<u8> {
+        let Some(config) = self.program_config()? else {
+            return Ok(100);
+        };
+        let half_life = config.interest_half_life_secs;
+        
+        let freshness_a = interest_freshness_pct(now, self.user_profile_a.interests_updated_at, half_life);
+        let freshness_b = interest_freshness_pct(now, self.user_profile_b.interests_updated_at, half_life);
+        Ok(freshness_a.min(freshness_b))
+    }
+    
+    /// How long the compute escrow waits for the callback before it can be forfeited
+    pub fn compute_escrow_timeout(&self) -> Result<i64> {
+        Ok(self
+            .program_config()?
+            .map_or(DEFAULT_COMPUTE_ESCROW_TIMEOUT_SECS, |config| config.compute_escrow_timeout_secs))
+    }
 }
 
 /// Callback after computation completes
@@ -134,7 +235,54 @@ pub struct ComputeTripMatchCallback<'info> {
     /// CHECK: computation_account for emitting events
     pub computation_account: UncheckedAccount<'info>,
     
-    /// Match record to update with scores (passed via remaining accounts)
+    /// Match record to update with scores (passed via callback accounts)
     #[account(mut)]
     pub match_record: Account<'info, crate::state::MatchRecord>,
+    
+    /// Trip A - quota refunded if the match is auto-rejected
+    #[account(
+        mut,
+        address = match_record.trip_a,
+    )]
+    pub trip_a: Account<'info, crate::state::Trip>,
+    
+    /// Trip B - quota refunded if the match is auto-rejected
+    #[account(
+        mut,
+        address = match_record.trip_b,
+    )]
+    pub trip_b: Account<'info, crate::state::Trip>,
+    
+    /// Trip A owner's profile - total_matches bumped when the match completes
+    #[account(
+        mut,
+        seeds = [b"user_profile", trip_a.owner.as_ref()],
+        bump = user_profile_a.bump
+    )]
+    pub user_profile_a: Account<'info, crate::state::UserProfile>,
+    
+    /// Trip B owner's profile - total_matches bumped when the match completes
+    #[account(
+        mut,
+        seeds = [b"user_profile", trip_b.owner.as_ref()],
+        bump = user_profile_b.bump
+    )]
+    pub user_profile_b: Account<'info, crate::state::UserProfile>,
+    
+    /// Wallet that locked the compute escrow - refunded on success
+    /// CHECK: only receives lamports, address checked against the match record
+    #[account(
+        mut,
+        address = match_record.escrow_payer,
+    )]
+    pub escrow_payer: UncheckedAccount<'info>,
+    
+    /// Treasury PDA: [b"treasury"] - receives a forfeited escrow
+    /// CHECK: only receives lamports, address enforced by seeds
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
 }
@@ -1,11 +1,23 @@
 use anchor_lang::prelude::*;
-use crate::state::Trip;
+use crate::state::{DestinationIndexShard, PayloadRegistry, ProgramConfig, Trip, UserProfile};
 use crate::error::ErrorCode;
 use crate::events::TripCreated;
+use crate::constants::{MAX_ENCRYPTED_WAYPOINTS_LEN, MAX_ROUTE_AREA_BUCKET, NOTIFY_ALL, SEEKING_ANY, TRAVELER_TYPE_TOURIST};
+use crate::utils::encrypted_fields;
 
 #[derive(Accounts)]
-#[instruction(destination_grid_hash: [u8; 32], start_date: i64)]
+#[instruction(
+    destination_grid_hash: [u8; 32],
+    start_date: i64,
+    end_date: i64,
+    encrypted_waypoints: Vec<u8>,
+    public_key: [u8; 32],
+    shard_id: u16,
+    route_area_bucket: u8,
+)]
 pub struct CreateTrip<'info> {
+    /// Trip PDA: [b"trip", user, user_profile.next_trip_index] - a user's
+    /// trips are enumerable by index, and dates can repeat across trips
     #[account(
         init,
         payer = user,
@@ -13,18 +25,76 @@ pub struct CreateTrip<'info> {
         seeds = [
             b"trip",
             user.key().as_ref(),
-            &start_date.to_le_bytes(),
+            &user_profile.next_trip_index.to_le_bytes(),
         ],
         bump
     )]
     pub trip: Account<'info, Trip>,
     
+    /// Owner's profile - hands out the trip index
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
     #[account(mut)]
     pub user: Signer<'info>,
     
+    /// Program config: [b"program_config"] - current destination salt epoch
+    /// and route area limit
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Open shard of the destination index, created on first use
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DestinationIndexShard::INIT_SPACE,
+        seeds = [b"dest", destination_grid_hash.as_ref(), &shard_id.to_le_bytes()],
+        bump
+    )]
+    pub destination_shard: Account<'info, DestinationIndexShard>,
+    
+    /// Shard shard_id - 1, which must be full (omitted for shard 0)
+    #[account(
+        seeds = [b"dest", destination_grid_hash.as_ref(), &shard_id.saturating_sub(1).to_le_bytes()],
+        bump = previous_shard.bump
+    )]
+    pub previous_shard: Option<Account<'info, DestinationIndexShard>>,
+    
+    /// Owner's payload registry - when passed, a reused waypoint payload
+    /// is rejected
+    #[account(
+        mut,
+        seeds = [b"payload_registry", user.key().as_ref()],
+        bump = payload_registry.bump
+    )]
+    pub payload_registry: Option<Account<'info, PayloadRegistry>>,
+    
     pub system_program: Program<'info, System>,
 }
 
+impl<'info> CreateTrip<'info> {
+    /// The program config, if it has been initialized
+    fn program_config(&self) -> Result<Option<ProgramConfig>> {
+        let info = &self.config;
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(ProgramConfig::try_deserialize(&mut &data[..])?))
+    }
+}
+
+/// `route_area_bucket` is floor(log2(bounding-box area in waypoint cells + 1)),
+/// computed client-side from the plaintext route as for set_route_density
+#[allow(clippy::too_many_arguments)]
 pub fn create_trip_handler(
     ctx: Context<CreateTrip>,
     destination_grid_hash: [u8; 32],
@@ -32,34 +102,59 @@ pub fn create_trip_handler(
     end_date: i64,
     encrypted_waypoints: Vec<u8>,
     public_key: [u8; 32],
+    shard_id: u16,
+    route_area_bucket: u8,
 ) -> Result<()> {
-    require!(
-        encrypted_waypoints.len() <= 800,
-        ErrorCode::EncryptedDataTooLarge
-    );
+    validate_trip_input(start_date, end_date, &encrypted_waypoints)?;
+    
+    // Without a config there is no route area limit and no salt epoch
+    let (max_route_area_bucket, salt_epoch) = ctx
+        .accounts
+        .program_config()?
+        .map_or((0, 0), |config| (config.max_route_area_bucket, config.destination_salt_epoch));
     
+    // A continent-sized route would make every grid cell "on the way"
+    require!(route_area_bucket <= MAX_ROUTE_AREA_BUCKET, ErrorCode::InvalidRouteDensity);
     require!(
-        end_date > start_date,
-        ErrorCode::InvalidDateRange
+        max_route_area_bucket == 0 || route_area_bucket <= max_route_area_bucket,
+        ErrorCode::RouteTooLarge
     );
     
+    require_open_shard(&ctx.accounts.destination_shard, ctx.accounts.previous_shard.as_ref(), shard_id)?;
+    
+    // Identical ciphertext would link this trip to an earlier one
+    if let Some(registry) = ctx.accounts.payload_registry.as_mut() {
+        let checksum = PayloadRegistry::checksum(&encrypted_waypoints);
+        require!(!registry.contains(&checksum), ErrorCode::DuplicatePayload);
+        registry.record(checksum);
+    }
+    
+    let user_profile = &mut ctx.accounts.user_profile;
+    let trip_index = user_profile.reserve_trip_indices(1)?;
+    user_profile.trip_count = user_profile.trip_count.saturating_add(1);
+    
     let trip = &mut ctx.accounts.trip;
     
-    trip.owner = ctx.accounts.user.key();
-    trip.destination_grid_hash = destination_grid_hash;
-    trip.start_date = start_date;
-    trip.end_date = end_date;
-    trip.encrypted_waypoints = encrypted_waypoints;
-    trip.public_key = public_key;
-    trip.is_active = true;
-    trip.match_count = 0;
-    trip.created_at = Clock::get()?.unix_timestamp;
-    trip.bump = ctx.bumps.trip;
-    
-    msg!("Trip created: {}", trip.key());
+    trip.set_inner(new_trip(
+        ctx.accounts.user.key(),
+        destination_grid_hash,
+        start_date,
+        end_date,
+        encrypted_waypoints,
+        public_key,
+        salt_epoch,
+        Clock::get()?.unix_timestamp,
+        ctx.bumps.trip,
+    ));
+    
+    let destination_shard = &mut ctx.accounts.destination_shard;
+    destination_shard.push_trip(destination_grid_hash, shard_id, ctx.bumps.destination_shard, trip.key());
+    
+    msg!("Trip created: {} (index {})", trip.key(), trip_index);
     msg!("Destination: {:?}", destination_grid_hash);
     msg!("Dates: {} to {}", start_date, end_date);
     msg!("Encrypted waypoints size: {} bytes", trip.encrypted_waypoints.len());
+    msg!("Indexed in destination shard {} ({} trips)", shard_id, destination_shard.trips.len());
     
     // Emit event for off-chain indexing
     emit!(TripCreated {
@@ -74,3 +169,76 @@ pub fn create_trip_handler(
     Ok(())
 }
 
+/// Require `destination_shard` to be its destination's open shard
+/// Shards fill in order, so the only shard with room is the least full:
+/// past shard 0 the previous shard must be passed and full. Shared by
+/// create_trip, create_trips_batch and clone_trip
+pub fn require_open_shard(
+    destination_shard: &DestinationIndexShard,
+    previous_shard: Option<&Account<DestinationIndexShard>>,
+    shard_id: u16,
+) -> Result<()> {
+    if shard_id > 0 {
+        let previous_full = previous_shard.is_some_and(|shard| shard.is_full());
+        require!(previous_full, ErrorCode::DestinationShardOutOfOrder);
+    }
+    require!(!destination_shard.is_full(), ErrorCode::DestinationShardFull);
+    Ok(())
+}
+
+/// Input checks shared by create_trip and create_trips_batch
+pub fn validate_trip_input(start_date: i64, end_date: i64, encrypted_waypoints: &[u8]) -> Result<()> {
+    require!(
+        encrypted_waypoints.len() <= MAX_ENCRYPTED_WAYPOINTS_LEN,
+        ErrorCode::EncryptedDataTooLarge
+    );
+    
+    // Must frame as at least one whole encrypted field, or compute_trip_match
+    // could not pass it to the circuit
+    require!(!encrypted_waypoints.is_empty(), ErrorCode::InvalidEncryptedData);
+    encrypted_fields(encrypted_waypoints)?;
+    
+    require!(
+        end_date > start_date,
+        ErrorCode::InvalidDateRange
+    );
+    
+    Ok(())
+}
+
+/// A freshly created, active trip with default matching preferences
+#[allow(clippy::too_many_arguments)]
+pub fn new_trip(
+    owner: Pubkey,
+    destination_grid_hash: [u8; 32],
+    start_date: i64,
+    end_date: i64,
+    encrypted_waypoints: Vec<u8>,
+    public_key: [u8; 32],
+    salt_epoch: u32,
+    created_at: i64,
+    bump: u8,
+) -> Trip {
+    Trip {
+        owner,
+        destination_grid_hash,
+        start_date,
+        end_date,
+        encrypted_waypoints,
+        public_key,
+        is_active: true,
+        match_count: 0,
+        min_counterparty_tier: 0,
+        traveler_type: TRAVELER_TYPE_TOURIST,
+        seeking_type: SEEKING_ANY,
+        route_density: 0,
+        boost_until: 0,
+        flexibility: 0,
+        salt_epoch,
+        created_at,
+        updated_at: created_at,
+        notify_on: NOTIFY_ALL,
+        destination_only_match: false,
+        bump,
+    }
+}
@@ -0,0 +1,163 @@
+// Create Trips Batch Instruction
+// Bulk import of already-encrypted trips (e.g. migrating from another platform)
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{create_account, CreateAccount};
+use crate::state::{DestinationIndexShard, ProgramConfig, Trip, UserProfile};
+use crate::events::{TripCreated, TripsBatchImported};
+use crate::constants::MAX_TRIPS_PER_BATCH;
+use crate::error::ErrorCode;
+use super::create_trip::{new_trip, require_open_shard, validate_trip_input};
+
+/// One trip to import - same inputs as create_trip (the destination is
+/// shared by the whole batch)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TripImport {
+    pub start_date: i64,
+    pub end_date: i64,
+    pub encrypted_waypoints: Vec<u8>,
+}
+
+/// Trip PDAs ([b"trip", user, index]) are passed as writable remaining
+/// accounts, one per entry and in entry order. Entry i takes index
+/// user_profile.next_trip_index + i; a skipped entry leaves its index unused
+#[derive(Accounts)]
+#[instruction(trips: Vec<TripImport>, public_key: [u8; 32], destination_grid_hash: [u8; 32], shard_id: u16)]
+pub struct CreateTripsBatch<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// Owner's profile - hands out the trip indices
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    /// Program config: [b"program_config"] - current destination salt epoch
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Open shard of the destination index, created on first use
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + DestinationIndexShard::INIT_SPACE,
+        seeds = [b"dest", destination_grid_hash.as_ref(), &shard_id.to_le_bytes()],
+        bump
+    )]
+    pub destination_shard: Account<'info, DestinationIndexShard>,
+    
+    /// Shard shard_id - 1, which must be full (omitted for shard 0)
+    #[account(
+        seeds = [b"dest", destination_grid_hash.as_ref(), &shard_id.saturating_sub(1).to_le_bytes()],
+        bump = previous_shard.bump
+    )]
+    pub previous_shard: Option<Account<'info, DestinationIndexShard>>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn create_trips_batch_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateTripsBatch<'info>>,
+    trips: Vec<TripImport>,
+    public_key: [u8; 32],
+    destination_grid_hash: [u8; 32],
+    shard_id: u16,
+) -> Result<()> {
+    require!(
+        !trips.is_empty()
+            && trips.len() <= MAX_TRIPS_PER_BATCH
+            && trips.len() == ctx.remaining_accounts.len(),
+        ErrorCode::InvalidBatch
+    );
+    require_open_shard(&ctx.accounts.destination_shard, ctx.accounts.previous_shard.as_ref(), shard_id)?;
+    let shard_bump = ctx.bumps.destination_shard;
+    
+    let user = &ctx.accounts.user;
+    let now = Clock::get()?.unix_timestamp;
+    let salt_epoch = ProgramConfig::salt_epoch_or_default(&ctx.accounts.config)?;
+    let space = 8 + Trip::INIT_SPACE;
+    let rent = Rent::get()?.minimum_balance(space);
+    let first_index = ctx.accounts.user_profile.reserve_trip_indices(trips.len() as u32)?;
+    
+    let mut created = 0u8;
+    let mut skipped = 0u8;
+    
+    for ((entry, trip_info), trip_index) in trips.into_iter().zip(ctx.remaining_accounts.iter()).zip(first_index..) {
+        let trip_index_bytes = trip_index.to_le_bytes();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"trip", user.key().as_ref(), &trip_index_bytes],
+            &crate::ID,
+        );
+        
+        // Malformed entries are skipped so one bad trip doesn't sink the
+        // import, as are entries past the point the shard fills up
+        let valid = trip_info.key() == expected
+            && !ctx.accounts.destination_shard.is_full()
+            && trip_info.is_writable
+            && trip_info.lamports() == 0
+            && validate_trip_input(entry.start_date, entry.end_date, &entry.encrypted_waypoints).is_ok();
+        if !valid {
+            msg!("Skipping trip entry for {}", trip_info.key());
+            skipped += 1;
+            continue;
+        }
+        
+        create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: user.to_account_info(),
+                    to: trip_info.clone(),
+                },
+                &[&[b"trip", user.key().as_ref(), &trip_index_bytes, &[bump]]],
+            ),
+            rent,
+            space as u64,
+            &crate::ID,
+        )?;
+        
+        let trip = new_trip(
+            user.key(),
+            destination_grid_hash,
+            entry.start_date,
+            entry.end_date,
+            entry.encrypted_waypoints,
+            public_key,
+            salt_epoch,
+            now,
+            bump,
+        );
+        trip.try_serialize(&mut &mut trip_info.try_borrow_mut_data()?[..])?;
+        ctx.accounts.destination_shard.push_trip(destination_grid_hash, shard_id, shard_bump, trip_info.key());
+        
+        emit!(TripCreated {
+            trip: trip_info.key(),
+            owner: trip.owner,
+            destination_grid_hash: trip.destination_grid_hash,
+            start_date: trip.start_date,
+            end_date: trip.end_date,
+            timestamp: now,
+        });
+        created += 1;
+    }
+    
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.trip_count = user_profile.trip_count.saturating_add(created as u32);
+    
+    emit!(TripsBatchImported {
+        owner: user.key(),
+        created,
+        skipped,
+    });
+    
+    msg!("Imported {} trips, skipped {}", created, skipped);
+    
+    Ok(())
+}
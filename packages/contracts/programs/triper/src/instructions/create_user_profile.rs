@@ -41,9 +41,18 @@ pub fn create_user_profile_handler(
     user_profile.public_key = public_key;
     user_profile.created_at = clock.unix_timestamp;
     user_profile.updated_at = clock.unix_timestamp;
+    user_profile.interests_updated_at = clock.unix_timestamp;
+    user_profile.last_active_at = clock.unix_timestamp;
     user_profile.trip_count = 0;
+    user_profile.next_trip_index = 0;
     user_profile.total_matches = 0;
     user_profile.is_active = true;
+    user_profile.verification_tier = 0;
+    user_profile.pending_match_count = 0;
+    user_profile.public_interests = None;
+    user_profile.mutual_matches = 0;
+    user_profile.rejections_received = 0;
+    user_profile.reputation = 100;
     user_profile.bump = ctx.bumps.user_profile;
     
     emit!(UserProfileCreated {
@@ -26,3 +26,30 @@ pub fn deactivate_trip_handler(ctx: Context<DeactivateTrip>) -> Result<()> {
     
     Ok(())
 }
+
+/// Same as DeactivateTrip but tolerates an already inactive trip
+#[derive(Accounts)]
+pub struct DeactivateTripIdempotent<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn deactivate_trip_idempotent_handler(ctx: Context<DeactivateTripIdempotent>) -> Result<()> {
+    let trip = &mut ctx.accounts.trip;
+    
+    if !trip.is_active {
+        msg!("Trip already inactive: {}", trip.key());
+        return Ok(());
+    }
+    
+    trip.is_active = false;
+    
+    msg!("Trip deactivated: {}", trip.key());
+    
+    Ok(())
+}
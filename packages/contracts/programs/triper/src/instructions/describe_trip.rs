@@ -0,0 +1,41 @@
+// Describe Trip Instruction
+// Emits a trip's public metadata as one event (read-only)
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::events::TripDescribed;
+
+#[derive(Accounts)]
+pub struct DescribeTrip<'info> {
+    pub trip: Account<'info, Trip>,
+}
+
+pub fn describe_trip_handler(ctx: Context<DescribeTrip>) -> Result<()> {
+    let trip = &ctx.accounts.trip;
+    let timestamp = Clock::get()?.unix_timestamp;
+    
+    emit!(TripDescribed {
+        trip: trip.key(),
+        owner: trip.owner,
+        destination_grid_hash: trip.destination_grid_hash,
+        salt_epoch: trip.salt_epoch,
+        start_date: trip.start_date,
+        end_date: trip.end_date,
+        is_active: trip.is_active,
+        match_count: trip.match_count,
+        min_counterparty_tier: trip.min_counterparty_tier,
+        traveler_type: trip.traveler_type,
+        seeking_type: trip.seeking_type,
+        route_density: trip.route_density,
+        flexibility: trip.flexibility,
+        notify_on: trip.notify_on,
+        destination_only_match: trip.destination_only_match,
+        boost_until: trip.boost_until,
+        boosted: trip.is_boosted(timestamp),
+        created_at: trip.created_at,
+        updated_at: trip.updated_at,
+        timestamp,
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,52 @@
+// Estimate Compatibility Instruction
+// Read-only, MPC-free estimate from PUBLIC trip data (dates, destination hash)
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::events::CompatibilityEstimate;
+use crate::prefilter;
+
+#[derive(Accounts)]
+pub struct EstimateCompatibility<'info> {
+    pub trip_a: Account<'info, Trip>,
+    
+    pub trip_b: Account<'info, Trip>,
+}
+
+pub fn estimate_compatibility_handler(ctx: Context<EstimateCompatibility>) -> Result<()> {
+    let trip_a = &ctx.accounts.trip_a;
+    let trip_b = &ctx.accounts.trip_b;
+    
+    let date_overlap = prefilter::date_overlap_percent(
+        trip_a.start_date,
+        trip_a.end_date,
+        trip_b.start_date,
+        trip_b.end_date,
+        trip_a.date_tolerance_with(trip_b),
+    );
+    let destination = prefilter::same_destination(
+        &trip_a.destination_grid_hash,
+        trip_a.salt_epoch,
+        &trip_b.destination_grid_hash,
+        trip_b.salt_epoch,
+    );
+    let destination_comparable = destination.is_some();
+    let destination_match = destination.unwrap_or(false);
+    let week_distance = prefilter::week_of_year_distance(
+        prefilter::week_of_year(trip_a.start_date)?,
+        prefilter::week_of_year(trip_b.start_date)?,
+    );
+    
+    emit!(CompatibilityEstimate {
+        trip_a: trip_a.key(),
+        trip_b: trip_b.key(),
+        date_overlap,
+        destination_match,
+        destination_comparable,
+        week_distance,
+    });
+    
+    msg!("Estimate: {}% date overlap, destination match: {}", date_overlap, destination_match);
+    
+    Ok(())
+}
@@ -0,0 +1,76 @@
+// Estimate Match Args Instruction
+// Read-only sizing of a compute_trip_match call, so clients can request enough compute units
+
+use anchor_lang::prelude::*;
+use crate::state::{Trip, UserProfile};
+use crate::events::MatchArgsEstimate;
+use crate::constants::{
+    BASE_MATCH_COMPUTE_UNITS,
+    COMPUTE_UNITS_PER_ENCRYPTED_FIELD,
+    ENCRYPTED_FIELD_SIZE,
+    MAX_TRANSACTION_COMPUTE_UNITS,
+};
+
+/// Plaintext arguments besides the ciphertext chunks:
+/// 4 x (pubkey + nonce), 3 weights, age weight + gap, budget weight,
+/// encrypt_scores, min_total_score
+pub const MATCH_PLAINTEXT_ARGS: u32 = 4 * 2 + 3 + 2 + 1 + 2;
+
+#[derive(Accounts)]
+pub struct EstimateMatchArgs<'info> {
+    pub trip_a: Account<'info, Trip>,
+    
+    pub trip_b: Account<'info, Trip>,
+    
+    #[account(
+        seeds = [b"user_profile", trip_a.owner.as_ref()],
+        bump = user_profile_a.bump
+    )]
+    pub user_profile_a: Account<'info, UserProfile>,
+    
+    #[account(
+        seeds = [b"user_profile", trip_b.owner.as_ref()],
+        bump = user_profile_b.bump
+    )]
+    pub user_profile_b: Account<'info, UserProfile>,
+}
+
+/// Compute unit limit to request for a compute_trip_match carrying
+/// `encrypted_fields` ciphertext chunks, capped at the transaction maximum
+pub fn recommended_compute_units(encrypted_fields: u32) -> u32 {
+    BASE_MATCH_COMPUTE_UNITS
+        .saturating_add(encrypted_fields.saturating_mul(COMPUTE_UNITS_PER_ENCRYPTED_FIELD))
+        .min(MAX_TRANSACTION_COMPUTE_UNITS)
+}
+
+pub fn estimate_match_args_handler(ctx: Context<EstimateMatchArgs>) -> Result<()> {
+    let encrypted_fields = [
+        ctx.accounts.trip_a.encrypted_waypoints.len(),
+        ctx.accounts.trip_b.encrypted_waypoints.len(),
+        ctx.accounts.user_profile_a.encrypted_data.len(),
+        ctx.accounts.user_profile_b.encrypted_data.len(),
+    ]
+    .iter()
+    .map(|len| (len / ENCRYPTED_FIELD_SIZE) as u32)
+    .sum::<u32>();
+    
+    let argument_count = encrypted_fields + MATCH_PLAINTEXT_ARGS;
+    let recommended_compute_units = recommended_compute_units(encrypted_fields);
+    
+    emit!(MatchArgsEstimate {
+        trip_a: ctx.accounts.trip_a.key(),
+        trip_b: ctx.accounts.trip_b.key(),
+        encrypted_fields,
+        argument_count,
+        recommended_compute_units,
+    });
+    
+    msg!(
+        "Match args: {} encrypted fields, {} arguments, {} CU recommended",
+        encrypted_fields,
+        argument_count,
+        recommended_compute_units
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,63 @@
+// Expire Match Instruction
+// Permissionless cleanup of completed matches nobody accepted in time and
+// pending matches that were never scored
+
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::MatchExpired;
+use crate::instructions::release_pending_slot;
+use crate::state::{MatchRecord, MatchStatus, UserProfile};
+
+#[derive(Accounts)]
+pub struct ExpireMatch<'info> {
+    #[account(
+        mut,
+        constraint = (match_account.status == MatchStatus::Completed
+            || match_account.status == MatchStatus::Pending) @ ErrorCode::InvalidMatchStatus
+    )]
+    pub match_account: Account<'info, MatchRecord>,
+    
+    /// Initiator's profile - required while the match holds a pending-match slot
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.pending_slot_owner.as_ref()],
+        bump = initiator_profile.bump
+    )]
+    pub initiator_profile: Option<Account<'info, UserProfile>>,
+    
+    /// Anyone can crank expiry
+    pub cranker: Signer<'info>,
+}
+
+pub fn expire_match_handler(ctx: Context<ExpireMatch>) -> Result<()> {
+    let match_account = &mut ctx.accounts.match_account;
+    let now = Clock::get()?.unix_timestamp;
+    
+    if match_account.status == MatchStatus::Pending {
+        require!(
+            match_account.expires_at != 0 && now > match_account.expires_at,
+            ErrorCode::MatchNotExpired
+        );
+        // A computation still in flight settles through its own escrow path
+        require!(match_account.escrow_lamports == 0, ErrorCode::EscrowAlreadyLocked);
+    } else {
+        require!(
+            match_account.accept_deadline != 0 && now > match_account.accept_deadline,
+            ErrorCode::MatchNotExpired
+        );
+    }
+    
+    match_account.status = MatchStatus::Expired;
+    release_pending_slot(match_account, ctx.accounts.initiator_profile.as_mut())?;
+    
+    emit!(MatchExpired {
+        match_record: match_account.key(),
+        accept_deadline: match_account.accept_deadline,
+        expires_at: match_account.expires_at,
+        timestamp: now,
+    });
+    
+    msg!("Match expired: {}", match_account.key());
+    
+    Ok(())
+}
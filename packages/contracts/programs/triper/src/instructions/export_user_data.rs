@@ -0,0 +1,115 @@
+// Export User Data Instruction
+// Read-only: emits the user's on-chain footprint (metadata only, never ciphertext) for portability
+
+use anchor_lang::prelude::*;
+use crate::state::{MatchRecord, Trip, UserProfile};
+use crate::events::{
+    UserDataExportCompleted,
+    UserMatchExported,
+    UserProfileExported,
+    UserTripExported,
+};
+
+#[derive(Accounts)]
+pub struct ExportUserData<'info> {
+    /// Profile whose footprint is exported
+    #[account(
+        seeds = [b"user_profile", user_profile.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+/// Remaining accounts: the user's Trips and MatchRecords, in any order
+/// Trips owned by someone else, matches not involving one of the passed
+/// user trips, and accounts of any other type are skipped and counted
+pub fn export_user_data_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExportUserData<'info>>,
+) -> Result<()> {
+    let user_profile = &ctx.accounts.user_profile;
+    let owner = user_profile.owner;
+    
+    emit!(UserProfileExported {
+        owner,
+        user_profile: user_profile.key(),
+        created_at: user_profile.created_at,
+        updated_at: user_profile.updated_at,
+        last_active_at: user_profile.last_active_at,
+        trip_count: user_profile.trip_count,
+        total_matches: user_profile.total_matches,
+        is_active: user_profile.is_active,
+        verification_tier: user_profile.verification_tier,
+    });
+    
+    let mut user_trips: Vec<Pubkey> = Vec::new();
+    let mut match_infos = Vec::new();
+    let mut skipped: u32 = 0;
+    
+    // Trips first, so matches can be attributed regardless of account order
+    for account_info in ctx.remaining_accounts.iter() {
+        if let Ok(trip) = Account::<Trip>::try_from(account_info) {
+            if trip.owner != owner {
+                skipped += 1;
+                continue;
+            }
+            
+            emit!(UserTripExported {
+                owner,
+                trip: trip.key(),
+                destination_grid_hash: trip.destination_grid_hash,
+                start_date: trip.start_date,
+                end_date: trip.end_date,
+                is_active: trip.is_active,
+                match_count: trip.match_count,
+                created_at: trip.created_at,
+            });
+            user_trips.push(trip.key());
+        } else {
+            match_infos.push(account_info);
+        }
+    }
+    
+    let mut matches_exported: u32 = 0;
+    for account_info in match_infos {
+        let match_record = match Account::<MatchRecord>::try_from(account_info) {
+            Ok(match_record) => match_record,
+            Err(_) => {
+                skipped += 1;
+                continue;
+            }
+        };
+        
+        if !user_trips.contains(&match_record.trip_a) && !user_trips.contains(&match_record.trip_b) {
+            skipped += 1;
+            continue;
+        }
+        
+        emit!(UserMatchExported {
+            owner,
+            match_record: match_record.key(),
+            trip_a: match_record.trip_a,
+            trip_b: match_record.trip_b,
+            status: match_record.status.clone(),
+            total_score: match_record.total_score,
+            created_at: match_record.created_at,
+        });
+        matches_exported += 1;
+    }
+    
+    emit!(UserDataExportCompleted {
+        owner,
+        trips_exported: user_trips.len() as u32,
+        matches_exported,
+        skipped,
+    });
+    
+    msg!(
+        "Exported {} trips and {} matches for {} ({} accounts skipped)",
+        user_trips.len(),
+        matches_exported,
+        owner,
+        skipped
+    );
+    
+    Ok(())
+}
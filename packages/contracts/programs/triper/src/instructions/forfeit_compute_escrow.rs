@@ -0,0 +1,74 @@
+// Forfeit Compute Escrow Instruction
+// Permissionless: a computation whose callback never landed forfeits its escrow to the treasury
+
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::ComputeEscrowSettled;
+use crate::state::MatchRecord;
+
+#[derive(Accounts)]
+pub struct ForfeitComputeEscrow<'info> {
+    #[account(
+        mut,
+        constraint = match_record.escrow_lamports > 0 @ ErrorCode::NoEscrow
+    )]
+    pub match_record: Account<'info, MatchRecord>,
+    
+    /// Treasury PDA: [b"treasury"] - receives the forfeited escrow
+    /// CHECK: only receives lamports, address enforced by seeds
+    #[account(
+        mut,
+        seeds = [b"treasury"],
+        bump
+    )]
+    pub treasury: UncheckedAccount<'info>,
+    
+    /// Anyone can crank a timed-out escrow
+    pub cranker: Signer<'info>,
+}
+
+pub fn forfeit_compute_escrow_handler(ctx: Context<ForfeitComputeEscrow>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp >= ctx.accounts.match_record.escrow_expires_at,
+        ErrorCode::EscrowNotExpired
+    );
+    
+    settle_compute_escrow(
+        &mut ctx.accounts.match_record,
+        &ctx.accounts.treasury.to_account_info(),
+        false,
+    )
+}
+
+/// Move a match's locked escrow out of the match record
+/// `refunded` = going back to the payer (true) or to the treasury (false)
+/// No-op when nothing is locked, so a late callback after a forfeit is harmless
+pub fn settle_compute_escrow<'info>(
+    match_record: &mut Account<'info, MatchRecord>,
+    recipient: &AccountInfo<'info>,
+    refunded: bool,
+) -> Result<()> {
+    let lamports = match_record.escrow_lamports;
+    if lamports == 0 {
+        return Ok(());
+    }
+    
+    match_record.sub_lamports(lamports)?;
+    recipient.add_lamports(lamports)?;
+    match_record.escrow_lamports = 0;
+    
+    emit!(ComputeEscrowSettled {
+        match_record: match_record.key(),
+        payer: match_record.escrow_payer,
+        lamports,
+        refunded,
+    });
+    
+    msg!(
+        "Compute escrow of {} lamports {}",
+        lamports,
+        if refunded { "refunded" } else { "forfeited to treasury" }
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,34 @@
+// Init Payload Registry Instruction
+// Opt in to create_trip rejecting reused waypoint ciphertext
+
+use anchor_lang::prelude::*;
+use crate::state::PayloadRegistry;
+
+#[derive(Accounts)]
+pub struct InitPayloadRegistry<'info> {
+    /// Payload registry PDA: [b"payload_registry", user]
+    #[account(
+        init,
+        payer = user,
+        space = 8 + PayloadRegistry::INIT_SPACE,
+        seeds = [b"payload_registry", user.key().as_ref()],
+        bump
+    )]
+    pub payload_registry: Account<'info, PayloadRegistry>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_payload_registry_handler(ctx: Context<InitPayloadRegistry>) -> Result<()> {
+    let registry = &mut ctx.accounts.payload_registry;
+    registry.owner = ctx.accounts.user.key();
+    registry.checksums = Vec::new();
+    registry.bump = ctx.bumps.payload_registry;
+    
+    msg!("Payload registry created for {}", registry.owner);
+    
+    Ok(())
+}
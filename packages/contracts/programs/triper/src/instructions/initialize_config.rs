@@ -0,0 +1,273 @@
+// Initialize Config Instruction
+// Creates the singleton ProgramConfig; the signer becomes admin
+
+use anchor_lang::prelude::*;
+use crate::state::ProgramConfig;
+use crate::error::ErrorCode;
+use crate::events::DestinationSaltRotated;
+use crate::constants::{
+    DEFAULT_COMPUTE_ESCROW_TIMEOUT_SECS,
+    DEFAULT_INACTIVITY_PERIOD_SECS,
+    DEFAULT_MAX_OWNER_PAIR_MATCHES,
+    DEFAULT_MAX_ROUTE_AREA_BUCKET,
+    DEFAULT_MIN_TRIP_AGE_SECS,
+};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ProgramConfig::INIT_SPACE,
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_config_handler(
+    ctx: Context<InitializeConfig>,
+    verifier: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    
+    config.admin = ctx.accounts.admin.key();
+    config.verifier = verifier;
+    config.inactivity_period_secs = DEFAULT_INACTIVITY_PERIOD_SECS;
+    config.interest_half_life_secs = 0;
+    config.compute_escrow_timeout_secs = DEFAULT_COMPUTE_ESCROW_TIMEOUT_SECS;
+    config.max_pending_matches = 0;
+    config.destination_salt_epoch = 0;
+    config.destination_salt_commitment = [0u8; 32];
+    config.min_trip_age_secs = DEFAULT_MIN_TRIP_AGE_SECS;
+    config.max_route_area_bucket = DEFAULT_MAX_ROUTE_AREA_BUCKET;
+    config.max_owner_pair_matches = DEFAULT_MAX_OWNER_PAIR_MATCHES;
+    config.bump = ctx.bumps.config;
+    
+    msg!("Program config initialized, admin: {}", config.admin);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetInactivityPeriod<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn set_inactivity_period_handler(
+    ctx: Context<SetInactivityPeriod>,
+    inactivity_period_secs: i64,
+) -> Result<()> {
+    require!(inactivity_period_secs >= 0, ErrorCode::InvalidTimestamp);
+    
+    ctx.accounts.config.inactivity_period_secs = inactivity_period_secs;
+    
+    msg!("Inactivity period set to {} seconds", inactivity_period_secs);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetInterestDecay<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn set_interest_decay_handler(
+    ctx: Context<SetInterestDecay>,
+    interest_half_life_secs: i64,
+) -> Result<()> {
+    require!(interest_half_life_secs >= 0, ErrorCode::InvalidTimestamp);
+    
+    ctx.accounts.config.interest_half_life_secs = interest_half_life_secs;
+    
+    msg!("Interest half-life set to {} seconds (0 = no decay)", interest_half_life_secs);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetComputeEscrowTimeout<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn set_compute_escrow_timeout_handler(
+    ctx: Context<SetComputeEscrowTimeout>,
+    compute_escrow_timeout_secs: i64,
+) -> Result<()> {
+    require!(compute_escrow_timeout_secs >= 0, ErrorCode::InvalidTimestamp);
+    
+    ctx.accounts.config.compute_escrow_timeout_secs = compute_escrow_timeout_secs;
+    
+    msg!("Compute escrow timeout set to {} seconds", compute_escrow_timeout_secs);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxPendingMatches<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn set_max_pending_matches_handler(
+    ctx: Context<SetMaxPendingMatches>,
+    max_pending_matches: u16,
+) -> Result<()> {
+    ctx.accounts.config.max_pending_matches = max_pending_matches;
+    
+    msg!("Max pending matches per user set to {} (0 = no cap)", max_pending_matches);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMinTripAge<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn set_min_trip_age_handler(
+    ctx: Context<SetMinTripAge>,
+    min_trip_age_secs: i64,
+) -> Result<()> {
+    require!(min_trip_age_secs >= 0, ErrorCode::InvalidTimestamp);
+    
+    ctx.accounts.config.min_trip_age_secs = min_trip_age_secs;
+    
+    msg!("Minimum trip age before matching set to {} seconds", min_trip_age_secs);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxRouteArea<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn set_max_route_area_handler(
+    ctx: Context<SetMaxRouteArea>,
+    max_route_area_bucket: u8,
+) -> Result<()> {
+    ctx.accounts.config.max_route_area_bucket = max_route_area_bucket;
+    
+    msg!("Max route area bucket set to {} (0 = no limit)", max_route_area_bucket);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMaxOwnerPairMatches<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+pub fn set_max_owner_pair_matches_handler(
+    ctx: Context<SetMaxOwnerPairMatches>,
+    max_owner_pair_matches: u16,
+) -> Result<()> {
+    ctx.accounts.config.max_owner_pair_matches = max_owner_pair_matches;
+    
+    msg!("Max matches per owner pair set to {} (0 = no limit)", max_owner_pair_matches);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RotateDestinationSalt<'info> {
+    #[account(
+        mut,
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    pub admin: Signer<'info>,
+}
+
+/// Start a new salt epoch. Trips hashed under earlier epochs keep their
+/// hashes but stop comparing equal to new ones until rehashed
+pub fn rotate_destination_salt_handler(
+    ctx: Context<RotateDestinationSalt>,
+    salt_commitment: [u8; 32],
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    require!(
+        salt_commitment != [0u8; 32] && salt_commitment != config.destination_salt_commitment,
+        ErrorCode::InvalidSaltCommitment
+    );
+    
+    config.destination_salt_epoch = config
+        .destination_salt_epoch
+        .checked_add(1)
+        .ok_or(ErrorCode::InvalidSaltCommitment)?;
+    config.destination_salt_commitment = salt_commitment;
+    
+    emit!(DestinationSaltRotated {
+        epoch: config.destination_salt_epoch,
+        salt_commitment,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
+    msg!("Destination salt rotated to epoch {}", config.destination_salt_epoch);
+    
+    Ok(())
+}
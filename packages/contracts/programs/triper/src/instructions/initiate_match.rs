@@ -2,8 +2,9 @@
 // User then calls compute_trip_match to queue Arcium MPC
 
 use anchor_lang::prelude::*;
-use crate::state::{Trip, MatchRecord, MatchStatus};
+use crate::state::{canonical_pair, Blocklist, DestinationProfile, Trip, MatchRecord, MatchStatus, OwnerPairHistory, PairHistory, ProgramConfig, ScoringProfile, UserProfile, EncryptedScores};
 use crate::error::ErrorCode;
+use crate::constants::{MATCH_EXPIRY_SECS, MAX_BUDGET_WEIGHT, MAX_MATCHES_PER_TRIP, MAX_MATCHES_PER_PAIR};
 
 /// Initiate a match computation between two trips
 /// Creates MatchRecord in Pending status
@@ -19,33 +20,160 @@ pub struct InitiateMatch<'info> {
     )]
     pub trip_a: Account<'info, Trip>,
     
-    /// Second trip (potential match)
-    #[account(mut)]
+    /// Second trip (potential match) - must be a different account than trip_a
+    #[account(
+        mut,
+        constraint = trip_b.key() != trip_a.key() @ ErrorCode::SameTripMatch
+    )]
     pub trip_b: Account<'info, Trip>,
     
-    /// Match record PDA: [b"match", trip_a, trip_b]
+    /// Match record PDA: [b"match", first, second] with the trips in
+    /// canonical_pair order, so there is one record per unordered pair
+    /// An existing record is reused only once its rejection has cooled off
     #[account(
-        init,
+        init_if_needed,
         payer = payer,
         space = MatchRecord::LEN,
         seeds = [
             b"match",
-            trip_a.key().as_ref(),
-            trip_b.key().as_ref(),
+            canonical_pair(trip_a.key(), trip_b.key()).0.as_ref(),
+            canonical_pair(trip_a.key(), trip_b.key()).1.as_ref(),
         ],
         bump
     )]
     pub match_record: Account<'info, MatchRecord>,
     
+    /// Pair history PDA: [b"pair_history", first, second] (canonical_pair order)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PairHistory::INIT_SPACE,
+        seeds = [
+            b"pair_history",
+            canonical_pair(trip_a.key(), trip_b.key()).0.as_ref(),
+            canonical_pair(trip_a.key(), trip_b.key()).1.as_ref(),
+        ],
+        bump
+    )]
+    pub pair_history: Account<'info, PairHistory>,
+    
+    /// Owner pair history PDA: [b"owner_pair_history", first, second] with the
+    /// two trip owners in canonical_pair order - survives trip recreation
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + OwnerPairHistory::INIT_SPACE,
+        seeds = [
+            b"owner_pair_history",
+            canonical_pair(trip_a.owner, trip_b.owner).0.as_ref(),
+            canonical_pair(trip_a.owner, trip_b.owner).1.as_ref(),
+        ],
+        bump
+    )]
+    pub owner_pair_history: Account<'info, OwnerPairHistory>,
+    
+    /// Trip A owner's profile - required if trip B sets a minimum tier or a
+    /// pending-match cap is configured
+    #[account(
+        mut,
+        seeds = [b"user_profile", trip_a.owner.as_ref()],
+        bump = profile_a.bump
+    )]
+    pub profile_a: Option<Account<'info, UserProfile>>,
+    
+    /// Trip B owner's profile - required only if trip A sets a minimum tier
+    #[account(
+        seeds = [b"user_profile", trip_b.owner.as_ref()],
+        bump = profile_b.bump
+    )]
+    pub profile_b: Option<Account<'info, UserProfile>>,
+    
+    /// Trip A owner's blocklist: [b"blocklist", trip_a.owner]
+    /// Always required so neither side's list can be left out
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"blocklist", trip_a.owner.as_ref()],
+        bump
+    )]
+    pub blocklist_a: UncheckedAccount<'info>,
+    
+    /// Trip B owner's blocklist: [b"blocklist", trip_b.owner]
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"blocklist", trip_b.owner.as_ref()],
+        bump
+    )]
+    pub blocklist_b: UncheckedAccount<'info>,
+    
+    /// Program config: [b"program_config"] - pending-match cap and minimum trip age
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    /// Default scoring profile for trip A's destination:
+    /// [b"destination_profile", destination_grid_hash]
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"destination_profile", trip_a.destination_grid_hash.as_ref()],
+        bump
+    )]
+    pub destination_profile: UncheckedAccount<'info>,
+    
     pub system_program: Program<'info, System>,
 }
 
+impl<'info> InitiateMatch<'info> {
+    /// The program config, if it has been initialized
+    fn program_config(&self) -> Result<Option<ProgramConfig>> {
+        let info = &self.config;
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(Some(ProgramConfig::try_deserialize(&mut &data[..])?))
+    }
+    
+    /// The destination's default scoring profile, else Balanced
+    fn default_scoring_profile(&self) -> Result<ScoringProfile> {
+        let info = &self.destination_profile;
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(ScoringProfile::Balanced);
+        }
+        let data = info.try_borrow_data()?;
+        let destination = DestinationProfile::try_deserialize(&mut &data[..])?;
+        Ok(ScoringProfile::from_index(destination.default_profile).unwrap_or(ScoringProfile::Balanced))
+    }
+}
+
 pub fn initiate_match_handler(
     ctx: Context<InitiateMatch>,
+    scoring_profile: Option<ScoringProfile>,
+    encrypt_scores: bool,
+    budget_weight: u8,
+    min_score: u8,
 ) -> Result<()> {
+    // Without a config there is no pending-match cap, no minimum trip age
+    // and no owner-pair limit
+    let (max_pending_matches, min_trip_age_secs, max_owner_pair_matches) = ctx
+        .accounts
+        .program_config()?
+        .map_or((0, 0, 0), |config| {
+            (config.max_pending_matches, config.min_trip_age_secs, config.max_owner_pair_matches)
+        });
+    // An explicit profile overrides the destination's default
+    let scoring_profile = match scoring_profile {
+        Some(profile) => profile,
+        None => ctx.accounts.default_scoring_profile()?,
+    };
+    let now = Clock::get()?.unix_timestamp;
     let trip_a = &mut ctx.accounts.trip_a;
     let trip_b = &mut ctx.accounts.trip_b;
     let match_record = &mut ctx.accounts.match_record;
+    let pair_history = &mut ctx.accounts.pair_history;
+    let owner_pair_history = &mut ctx.accounts.owner_pair_history;
     
     // Validation: Can't match with yourself
     require!(
@@ -53,12 +181,98 @@ pub fn initiate_match_handler(
         ErrorCode::SameTripMatch
     );
     
+    // Only live trips that haven't already ended can be matched
+    require!(
+        trip_a.is_active && trip_b.is_active,
+        ErrorCode::TripNotActive
+    );
+    require!(
+        now < trip_a.end_date && now < trip_b.end_date,
+        ErrorCode::TripExpired
+    );
+    
+    require!(
+        budget_weight <= MAX_BUDGET_WEIGHT,
+        ErrorCode::InvalidBudgetWeight
+    );
+    require!(min_score <= 100, ErrorCode::InvalidScore);
+    
     // Check user's quota
     require!(
-        trip_a.match_count < 100,
+        trip_a.match_count < MAX_MATCHES_PER_TRIP,
         ErrorCode::QuotaExceeded
     );
     
+    // Neither owner may have blocked the other
+    check_not_blocked(&ctx.accounts.blocklist_a, &trip_b.owner)?;
+    check_not_blocked(&ctx.accounts.blocklist_b, &trip_a.owner)?;
+    
+    // A record that already exists must be a settled rejection; it is
+    // reset below rather than re-created
+    let reusing = match_record.trip_a != Pubkey::default();
+    if reusing {
+        require!(
+            match_record.status == MatchStatus::Rejected,
+            ErrorCode::MatchAlreadyExists
+        );
+        require!(match_record.escrow_lamports == 0, ErrorCode::EscrowAlreadyLocked);
+    }
+    
+    // Rejected pairs cool off before they can be re-initiated, whether the
+    // rejected record is still open or has been closed
+    let last_rejected_at = if reusing { match_record.rejected_at } else { 0 }
+        .max(pair_history.last_rejected_at);
+    require!(
+        !PairHistory::in_cooldown(last_rejected_at, now),
+        ErrorCode::RematchCooldown
+    );
+    
+    // Cap repeated re-initiation of the same pair
+    require!(
+        pair_history.rematch_count < MAX_MATCHES_PER_PAIR,
+        ErrorCode::RematchLimitReached
+    );
+    
+    // Lifetime cap between the same two owners, whichever trips they use
+    require!(
+        max_owner_pair_matches == 0 || owner_pair_history.match_count < max_owner_pair_matches as u32,
+        ErrorCode::OwnerPairLimitReached
+    );
+    
+    // Each trip's "looking for" filter must admit the other trip's type
+    require!(
+        trip_a.accepts_traveler(trip_b.traveler_type)
+            && trip_b.accepts_traveler(trip_a.traveler_type),
+        ErrorCode::TypeMismatch
+    );
+    
+    // Cooling-off period: neither trip can be matched straight after creation
+    require!(
+        now - trip_a.created_at >= min_trip_age_secs && now - trip_b.created_at >= min_trip_age_secs,
+        ErrorCode::TripTooNew
+    );
+    
+    // Each trip's verification requirement applies to the other owner
+    check_counterparty_tier(trip_a.min_counterparty_tier, ctx.accounts.profile_b.as_ref())?;
+    check_counterparty_tier(trip_b.min_counterparty_tier, ctx.accounts.profile_a.as_ref())?;
+    
+    // With a cap in force the initiator's unresolved matches are tracked on
+    // their profile; the slot is freed once the match resolves
+    match_record.pending_slot_owner = Pubkey::default();
+    match_record.pending_slot_held = false;
+    if max_pending_matches > 0 {
+        let profile_a = ctx
+            .accounts
+            .profile_a
+            .as_mut()
+            .ok_or(ErrorCode::InitiatorProfileRequired)?;
+        require!(
+            profile_a.pending_match_count < max_pending_matches,
+            ErrorCode::TooManyPendingMatches
+        );
+        acquire_pending_slot(match_record, profile_a);
+    }
+    
     // Initialize match record
     match_record.trip_a = trip_a.key();
     match_record.trip_b = trip_b.key();
@@ -66,13 +280,59 @@ pub fn initiate_match_handler(
     match_record.route_score = 0;
     match_record.date_score = 0;
     match_record.interest_score = 0;
+    match_record.coverage_score = 0;
     match_record.status = MatchStatus::Pending;
     match_record.trip_a_accepted = false;
     match_record.trip_b_accepted = false;
-    match_record.created_at = Clock::get()?.unix_timestamp;
+    match_record.created_at = now;
     match_record.computation_id = [0; 32]; // Will be set by callback
+    match_record.rejected_at = 0;
+    match_record.rejected_by = Pubkey::default();
+    match_record.is_stale = false;
+    match_record.accept_deadline = 0; // Set by callback
+    match_record.expires_at = now + MATCH_EXPIRY_SECS;
+    match_record.scoring_profile = scoring_profile;
+    match_record.interest_freshness = 100; // Set by compute_trip_match
+    match_record.scoring_version = 0; // Stamped by the callback
+    if !reusing {
+        // A reused record stays refundable to whoever funded it
+        match_record.rent_payer = ctx.accounts.payer.key();
+    }
+    match_record.encrypt_scores = encrypt_scores;
+    match_record.encrypted_scores_a = EncryptedScores::default();
+    match_record.encrypted_scores_b = EncryptedScores::default();
+    match_record.escrow_lamports = 0;
+    match_record.escrow_payer = Pubkey::default();
+    match_record.escrow_expires_at = 0;
+    match_record.reveal_key_a = [0u8; 32];
+    match_record.reveal_key_b = [0u8; 32];
+    match_record.threshold_checked = false;
+    match_record.score_threshold = 0;
+    match_record.threshold_passed = false;
+    match_record.trip_a_owner = trip_a.owner;
+    match_record.trip_b_owner = trip_b.owner;
+    match_record.revealed = false;
+    match_record.acknowledged_by_a = false;
+    match_record.acknowledged_by_b = false;
+    match_record.weights = scoring_profile.weight_array();
+    match_record.age_score = 0; // Set by the callback
+    match_record.budget_weight = budget_weight;
+    match_record.budget_score = 0; // Set by the callback
+    match_record.min_score = min_score;
     match_record.bump = ctx.bumps.match_record;
     
+    // Record this match in the pair history
+    pair_history.trip_a = trip_a.key();
+    pair_history.trip_b = trip_b.key();
+    pair_history.rematch_count += 1;
+    pair_history.bump = ctx.bumps.pair_history;
+    
+    let (first_owner, second_owner) = canonical_pair(trip_a.owner, trip_b.owner);
+    owner_pair_history.owner_a = first_owner;
+    owner_pair_history.owner_b = second_owner;
+    owner_pair_history.match_count += 1;
+    owner_pair_history.bump = ctx.bumps.owner_pair_history;
+    
     // Increment match counts
     trip_a.match_count += 1;
     trip_b.match_count += 1;
@@ -83,3 +343,69 @@ pub fn initiate_match_handler(
     
     Ok(())
 }
+
+/// A missing profile counts as tier 0
+fn check_counterparty_tier(
+    min_tier: u8,
+    profile: Option<&Account<UserProfile>>,
+) -> Result<()> {
+    let tier = profile.map(|p| p.verification_tier).unwrap_or(0);
+    require!(tier >= min_tier, ErrorCode::InsufficientVerification);
+    Ok(())
+}
+
+/// A blocklist that was never created blocks no one
+fn check_not_blocked(blocklist: &UncheckedAccount, counterparty: &Pubkey) -> Result<()> {
+    if blocklist.owner != &crate::ID || blocklist.data_is_empty() {
+        return Ok(());
+    }
+    let data = blocklist.try_borrow_data()?;
+    let list = Blocklist::try_deserialize(&mut &data[..])?;
+    require!(!list.is_blocked(counterparty), ErrorCode::UserBlocked);
+    Ok(())
+}
+
+/// Occupy one of `profile`'s pending-match slots with `match_record`
+pub fn acquire_pending_slot(match_record: &mut MatchRecord, profile: &mut UserProfile) {
+    profile.pending_match_count = profile.pending_match_count.saturating_add(1);
+    match_record.pending_slot_owner = profile.owner;
+    match_record.pending_slot_held = true;
+}
+
+/// The profile to credit `owner`'s match outcomes to, if one was passed
+/// While a match holds a pending slot the initiator's profile is the trip A
+/// owner's, so it stands in for that owner's profile; passing the same
+/// account twice would let one stale copy overwrite the other on exit
+pub fn participant_profile<'a, 'info>(
+    initiator_profile: &'a mut Option<Account<'info, UserProfile>>,
+    owner_profile: &'a mut Option<Account<'info, UserProfile>>,
+    owner: Pubkey,
+) -> Result<Option<&'a mut Account<'info, UserProfile>>> {
+    if let (Some(initiator), Some(profile)) = (initiator_profile.as_ref(), owner_profile.as_ref()) {
+        require!(initiator.key() != profile.key(), ErrorCode::DuplicateProfileAccount);
+    }
+    if initiator_profile.as_ref().is_some_and(|profile| profile.owner == owner) {
+        return Ok(initiator_profile.as_mut());
+    }
+    Ok(owner_profile.as_mut())
+}
+
+/// Free the pending-match slot `match_record` holds, if any
+/// Call whenever a match leaves Pending/Completed. The profile may be
+/// omitted only for matches that don't hold a slot
+pub fn release_pending_slot(
+    match_record: &mut MatchRecord,
+    profile: Option<&mut Account<UserProfile>>,
+) -> Result<()> {
+    if !match_record.pending_slot_held {
+        return Ok(());
+    }
+    let profile = profile.ok_or(ErrorCode::InitiatorProfileRequired)?;
+    require!(
+        profile.owner == match_record.pending_slot_owner,
+        ErrorCode::InitiatorProfileRequired
+    );
+    profile.pending_match_count = profile.pending_match_count.saturating_sub(1);
+    match_record.pending_slot_held = false;
+    Ok(())
+}
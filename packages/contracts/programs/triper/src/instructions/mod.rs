@@ -6,6 +6,46 @@ pub mod compute_match;
 pub mod accept_match;
 pub mod reject_match;
 pub mod deactivate_trip;
+pub mod clone_trip;
+pub mod verify_encryption;
+pub mod undo_reject;
+pub mod reveal_coordination;
+pub mod initialize_config;
+pub mod set_verification_tier;
+pub mod rotate_trip_key;
+pub mod boost_trip;
+pub mod estimate_compatibility;
+pub mod expire_match;
+pub mod set_destination_fee;
+pub mod close_match;
+pub mod reveal_shared_interests;
+pub mod sweep_inactive_user;
+pub mod estimate_match_args;
+pub mod export_user_data;
+pub mod set_trip_type;
+pub mod set_route_density;
+pub mod self_match_test;
+pub mod split_encrypted_payload;
+pub mod forfeit_compute_escrow;
+pub mod submit_reveal_key;
+pub mod create_trips_batch;
+pub mod check_match_threshold;
+pub mod set_interest_relatedness;
+pub mod set_trip_flexibility;
+pub mod update_trip;
+pub mod acknowledge_reveal;
+pub mod rehash_trip_destination;
+pub mod set_public_interests;
+pub mod preview_match;
+pub mod set_interest_incompatibility;
+pub mod read_destination_shard;
+pub mod suggest_match;
+pub mod set_trip_notifications;
+pub mod block_user;
+pub mod init_payload_registry;
+pub mod set_destination_profile;
+pub mod describe_trip;
+pub mod set_trip_destination_only;
 
 pub use create_user_profile::*;
 pub use update_user_profile::*;
@@ -15,3 +55,43 @@ pub use compute_match::*;
 pub use accept_match::*;
 pub use reject_match::*;
 pub use deactivate_trip::*;
+pub use clone_trip::*;
+pub use verify_encryption::*;
+pub use undo_reject::*;
+pub use reveal_coordination::*;
+pub use initialize_config::*;
+pub use set_verification_tier::*;
+pub use rotate_trip_key::*;
+pub use boost_trip::*;
+pub use estimate_compatibility::*;
+pub use expire_match::*;
+pub use set_destination_fee::*;
+pub use close_match::*;
+pub use reveal_shared_interests::*;
+pub use sweep_inactive_user::*;
+pub use estimate_match_args::*;
+pub use export_user_data::*;
+pub use set_trip_type::*;
+pub use set_route_density::*;
+pub use self_match_test::*;
+pub use split_encrypted_payload::*;
+pub use forfeit_compute_escrow::*;
+pub use submit_reveal_key::*;
+pub use create_trips_batch::*;
+pub use check_match_threshold::*;
+pub use set_interest_relatedness::*;
+pub use set_trip_flexibility::*;
+pub use update_trip::*;
+pub use acknowledge_reveal::*;
+pub use rehash_trip_destination::*;
+pub use set_public_interests::*;
+pub use preview_match::*;
+pub use set_interest_incompatibility::*;
+pub use read_destination_shard::*;
+pub use suggest_match::*;
+pub use set_trip_notifications::*;
+pub use block_user::*;
+pub use init_payload_registry::*;
+pub use set_destination_profile::*;
+pub use describe_trip::*;
+pub use set_trip_destination_only::*;
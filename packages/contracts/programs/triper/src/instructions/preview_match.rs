@@ -0,0 +1,106 @@
+// Preview Match Instruction
+// Partial score from public dates and opted-in public interests - no MPC
+
+use anchor_lang::prelude::*;
+use crate::state::{canonical_pair, MatchPreview, ScoringProfile, Trip, UserProfile};
+use crate::error::ErrorCode;
+use crate::events::MatchPreviewed;
+use crate::prefilter;
+use crate::scoring::{jaccard_pct, weighted_total};
+
+#[derive(Accounts)]
+pub struct PreviewMatch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// Requester's trip
+    #[account(
+        constraint = trip_a.owner == payer.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip_a: Account<'info, Trip>,
+    
+    /// Candidate trip
+    #[account(
+        constraint = trip_b.key() != trip_a.key() @ ErrorCode::SameTripMatch
+    )]
+    pub trip_b: Account<'info, Trip>,
+    
+    #[account(
+        seeds = [b"user_profile", trip_a.owner.as_ref()],
+        bump = profile_a.bump
+    )]
+    pub profile_a: Account<'info, UserProfile>,
+    
+    #[account(
+        seeds = [b"user_profile", trip_b.owner.as_ref()],
+        bump = profile_b.bump
+    )]
+    pub profile_b: Account<'info, UserProfile>,
+    
+    /// Preview PDA: [b"match_preview", first, second] (canonical_pair order)
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MatchPreview::INIT_SPACE,
+        seeds = [
+            b"match_preview",
+            canonical_pair(trip_a.key(), trip_b.key()).0.as_ref(),
+            canonical_pair(trip_a.key(), trip_b.key()).1.as_ref(),
+        ],
+        bump
+    )]
+    pub match_preview: Account<'info, MatchPreview>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn preview_match_handler(ctx: Context<PreviewMatch>) -> Result<()> {
+    let trip_a = &ctx.accounts.trip_a;
+    let trip_b = &ctx.accounts.trip_b;
+    
+    let date_score = prefilter::date_overlap_percent(
+        trip_a.start_date,
+        trip_a.end_date,
+        trip_b.start_date,
+        trip_b.end_date,
+        trip_a.date_tolerance_with(trip_b),
+    );
+    
+    // Interests count only when both owners have published theirs
+    let interest_score = match (ctx.accounts.profile_a.public_interests, ctx.accounts.profile_b.public_interests) {
+        (Some(a), Some(b)) => Some(jaccard_pct((a & b).count_ones(), (a | b).count_ones())),
+        _ => None,
+    };
+    
+    // Balanced weights with route (and interest, when absent) left out
+    let (_, date_weight, interest_weight) = ScoringProfile::Balanced.weights();
+    let partial_score = weighted_total(
+        0,
+        date_score,
+        interest_score.unwrap_or(0),
+        (0, date_weight, if interest_score.is_some() { interest_weight } else { 0 }),
+    );
+    
+    let match_preview = &mut ctx.accounts.match_preview;
+    match_preview.trip_a = trip_a.key();
+    match_preview.trip_b = trip_b.key();
+    match_preview.date_score = date_score;
+    match_preview.interest_score = interest_score;
+    match_preview.route_score = None;
+    match_preview.partial_score = partial_score;
+    match_preview.previewed_at = Clock::get()?.unix_timestamp;
+    match_preview.bump = ctx.bumps.match_preview;
+    
+    emit!(MatchPreviewed {
+        match_preview: match_preview.key(),
+        trip_a: trip_a.key(),
+        trip_b: trip_b.key(),
+        date_score,
+        interest_score,
+        partial_score,
+    });
+    
+    msg!("Preview: {}% dates, interests {:?}, partial score {}", date_score, interest_score, partial_score);
+    
+    Ok(())
+}
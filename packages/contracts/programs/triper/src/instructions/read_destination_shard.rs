@@ -0,0 +1,59 @@
+// Read Destination Shard Instruction
+// Emits one page of a destination's indexed trips for discovery
+
+use anchor_lang::prelude::*;
+use crate::constants::DESTINATION_PAGE_SIZE;
+use crate::state::DestinationIndexShard;
+use crate::error::ErrorCode;
+use crate::events::DestinationShardPage;
+
+#[derive(Accounts)]
+#[instruction(destination_grid_hash: [u8; 32], shard_id: u16)]
+pub struct ReadDestinationShard<'info> {
+    #[account(
+        seeds = [b"dest", destination_grid_hash.as_ref(), &shard_id.to_le_bytes()],
+        bump = destination_shard.bump
+    )]
+    pub destination_shard: Account<'info, DestinationIndexShard>,
+}
+
+pub fn read_destination_shard_handler(
+    ctx: Context<ReadDestinationShard>,
+    destination_grid_hash: [u8; 32],
+    shard_id: u16,
+    offset: u16,
+    limit: u8,
+) -> Result<()> {
+    require!(
+        limit > 0 && limit <= DESTINATION_PAGE_SIZE,
+        ErrorCode::InvalidPageSize
+    );
+    
+    let shard = &ctx.accounts.destination_shard;
+    let trips: Vec<Pubkey> = shard
+        .trips
+        .iter()
+        .skip(offset as usize)
+        .take(limit as usize)
+        .copied()
+        .collect();
+    
+    msg!(
+        "Destination shard {}: {} of {} trips from offset {}",
+        shard_id,
+        trips.len(),
+        shard.trips.len(),
+        offset
+    );
+    
+    emit!(DestinationShardPage {
+        destination_grid_hash,
+        shard_id,
+        offset,
+        trips,
+        shard_len: shard.trips.len() as u16,
+        shard_full: shard.is_full(),
+    });
+    
+    Ok(())
+}
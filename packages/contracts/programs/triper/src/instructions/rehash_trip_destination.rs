@@ -0,0 +1,42 @@
+// Rehash Trip Destination Instruction
+// Owner migrates a trip's destination hash to the current salt epoch
+
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Trip};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct RehashTripDestination<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    /// Program config: [b"program_config"] - current destination salt epoch
+    /// CHECK: address enforced by seeds, deserialized only if owned by this program
+    #[account(
+        seeds = [b"program_config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    
+    pub user: Signer<'info>,
+}
+
+/// The client recomputes the hash with the current epoch's salt; the
+/// program can't check it, only record which epoch it claims
+pub fn rehash_trip_destination_handler(
+    ctx: Context<RehashTripDestination>,
+    destination_grid_hash: [u8; 32],
+) -> Result<()> {
+    let salt_epoch = ProgramConfig::salt_epoch_or_default(&ctx.accounts.config)?;
+    
+    let trip = &mut ctx.accounts.trip;
+    trip.destination_grid_hash = destination_grid_hash;
+    trip.salt_epoch = salt_epoch;
+    
+    msg!("Trip {} destination rehashed under salt epoch {}", trip.key(), salt_epoch);
+    
+    Ok(())
+}
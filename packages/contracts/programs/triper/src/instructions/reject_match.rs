@@ -1,12 +1,14 @@
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
-use crate::state::{MatchRecord, MatchStatus, Trip};
+use crate::instructions::{participant_profile, release_pending_slot};
+use crate::state::{MatchRecord, MatchStatus, Trip, UserProfile};
 
 #[derive(Accounts)]
 pub struct RejectMatch<'info> {
     #[account(
         mut,
-        constraint = match_account.status == MatchStatus::Pending @ ErrorCode::InvalidMatchStatus
+        constraint = (match_account.status == MatchStatus::Pending
+            || match_account.status == MatchStatus::Completed) @ ErrorCode::InvalidMatchStatus
     )]
     pub match_account: Account<'info, MatchRecord>,
     
@@ -16,12 +18,38 @@ pub struct RejectMatch<'info> {
     )]
     pub trip: Account<'info, Trip>,
     
+    /// Initiator's profile - required while the match holds a pending-match slot
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.pending_slot_owner.as_ref()],
+        bump = initiator_profile.bump
+    )]
+    pub initiator_profile: Option<Account<'info, UserProfile>>,
+    
+    /// Trip A owner's profile - required when trip A's owner is the one
+    /// rejected (omit it while initiator_profile already stands in for it)
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.trip_a_owner.as_ref()],
+        bump = owner_profile_a.bump
+    )]
+    pub owner_profile_a: Option<Account<'info, UserProfile>>,
+    
+    /// Trip B owner's profile - required when trip B's owner is the one rejected
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.trip_b_owner.as_ref()],
+        bump = owner_profile_b.bump
+    )]
+    pub owner_profile_b: Option<Account<'info, UserProfile>>,
+    
     pub user: Signer<'info>,
 }
 
 pub fn reject_match_handler(ctx: Context<RejectMatch>) -> Result<()> {
-    let match_account = &mut ctx.accounts.match_account;
-    let trip_key = ctx.accounts.trip.key();
+    let accounts = &mut *ctx.accounts;
+    let match_account = &mut accounts.match_account;
+    let trip_key = accounts.trip.key();
     
     // Check if user owns one of the trips in the match
     require!(
@@ -29,10 +57,24 @@ pub fn reject_match_handler(ctx: Context<RejectMatch>) -> Result<()> {
         ErrorCode::Unauthorized
     );
     
-    // Update status to Rejected
+    // Update status to Rejected (undoable by the same user for a short window)
     match_account.status = MatchStatus::Rejected;
+    release_pending_slot(match_account, accounts.initiator_profile.as_mut())?;
+    match_account.rejected_at = Clock::get()?.unix_timestamp;
+    match_account.rejected_by = accounts.user.key();
+    
+    // The other trip's owner takes the rejection on their reputation
+    let rejected_a = trip_key == match_account.trip_b;
+    let (rejected_owner, rejected_profile) = if rejected_a {
+        (match_account.trip_a_owner, &mut accounts.owner_profile_a)
+    } else {
+        (match_account.trip_b_owner, &mut accounts.owner_profile_b)
+    };
+    participant_profile(&mut accounts.initiator_profile, rejected_profile, rejected_owner)?
+        .ok_or(ErrorCode::RejectedProfileRequired)?
+        .record_rejection_received();
     
-    msg!("✗ Match rejected by user {}", ctx.accounts.user.key());
+    msg!("✗ Match rejected by user {}", accounts.user.key());
     
     Ok(())
 }
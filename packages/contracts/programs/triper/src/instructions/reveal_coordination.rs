@@ -0,0 +1,183 @@
+// Meeting coordination reveal for mutual matches
+// Outputs only shared cells (re-encrypted per party) plus the public overlap window
+
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::SignerAccount;
+use crate::ID;
+use crate::ID_CONST;
+use crate::COMP_DEF_OFFSET_REVEAL_COORDINATION;
+use crate::error::ErrorCode;
+use crate::utils::push_encrypted_input;
+use crate::state::{MatchRecord, MatchStatus, Trip};
+
+/// Initialize the computation definition account
+#[init_computation_definition_accounts("reveal_coordination", payer)]
+#[derive(Accounts)]
+pub struct InitRevealCoordinationCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a coordination computation for a mutual match
+#[queue_computation_accounts("reveal_coordination", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealCoordination<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COORDINATION)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+
+    /// Match must be mutually accepted, with both reveal keys submitted
+    #[account(
+        constraint = match_record.status == MatchStatus::Mutual @ ErrorCode::InvalidMatchStatus,
+        constraint = match_record.reveal_keys_exchanged() @ ErrorCode::NotMutualMatch,
+    )]
+    pub match_record: Account<'info, MatchRecord>,
+
+    #[account(address = match_record.trip_a)]
+    pub trip_a: Account<'info, Trip>,
+
+    #[account(address = match_record.trip_b)]
+    pub trip_b: Account<'info, Trip>,
+}
+
+/// Callback after the coordination payload is computed
+#[callback_accounts("reveal_coordination")]
+#[derive(Accounts)]
+pub struct RevealCoordinationCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_COORDINATION)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: computation_account for emitting events
+    pub computation_account: UncheckedAccount<'info>,
+
+    /// Mutual match being coordinated (passed via callback accounts)
+    #[account(mut)]
+    pub match_record: Account<'info, MatchRecord>,
+
+    #[account(address = match_record.trip_a)]
+    pub trip_a: Account<'info, Trip>,
+
+    #[account(address = match_record.trip_b)]
+    pub trip_b: Account<'info, Trip>,
+}
+
+pub fn reveal_coordination_handler(
+    ctx: Context<RevealCoordination>,
+    computation_offset: u64,
+    nonce_a: u128,
+    nonce_b: u128,
+) -> Result<()> {
+    let payer_key = ctx.accounts.payer.key();
+    let trip_a = &ctx.accounts.trip_a;
+    let trip_b = &ctx.accounts.trip_b;
+
+    // Only the two participants may request coordination
+    require!(
+        payer_key == trip_a.owner || payer_key == trip_b.owner,
+        ErrorCode::Unauthorized
+    );
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    // Each route is passed with its own key so each output is
+    // re-encrypted to the party that owns that key
+    let mut args = Vec::new();
+    push_encrypted_input(&mut args, trip_a.public_key, nonce_a, &trip_a.encrypted_waypoints)?;
+    push_encrypted_input(&mut args, trip_b.public_key, nonce_b, &trip_b.encrypted_waypoints)?;
+
+    let callback_accounts = [
+        CallbackAccount {
+            pubkey: ctx.accounts.match_record.key(),
+            is_writable: true,
+        },
+        CallbackAccount {
+            pubkey: trip_a.key(),
+            is_writable: false,
+        },
+        CallbackAccount {
+            pubkey: trip_b.key(),
+            is_writable: false,
+        },
+    ];
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![RevealCoordinationCallback::callback_ix(&callback_accounts)],
+    )?;
+
+    msg!("Queued coordination reveal for match: {}", ctx.accounts.match_record.key());
+
+    Ok(())
+}
@@ -0,0 +1,184 @@
+// Shared-interests reveal for mutual matches
+// Publishes only the interests both parties hold - never either full interest set
+
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::SignerAccount;
+use crate::ID;
+use crate::ID_CONST;
+use crate::COMP_DEF_OFFSET_REVEAL_SHARED_INTERESTS;
+use crate::error::ErrorCode;
+use crate::utils::push_encrypted_input;
+use crate::state::{MatchRecord, MatchStatus, Trip, UserProfile};
+
+/// Initialize the computation definition account
+#[init_computation_definition_accounts("reveal_shared_interests", payer)]
+#[derive(Accounts)]
+pub struct InitRevealSharedInterestsCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a shared-interests computation for a mutual match
+#[queue_computation_accounts("reveal_shared_interests", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct RevealSharedInterests<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SHARED_INTERESTS)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+
+    /// Match must be mutually accepted, with both reveal keys submitted
+    #[account(
+        constraint = match_record.status == MatchStatus::Mutual @ ErrorCode::InvalidMatchStatus,
+        constraint = match_record.reveal_keys_exchanged() @ ErrorCode::NotMutualMatch,
+    )]
+    pub match_record: Account<'info, MatchRecord>,
+
+    #[account(address = match_record.trip_a)]
+    pub trip_a: Account<'info, Trip>,
+
+    #[account(address = match_record.trip_b)]
+    pub trip_b: Account<'info, Trip>,
+
+    /// Trip A owner's profile - holds their encrypted interests
+    #[account(
+        seeds = [b"user_profile", trip_a.owner.as_ref()],
+        bump = user_profile_a.bump
+    )]
+    pub user_profile_a: Account<'info, UserProfile>,
+
+    /// Trip B owner's profile - holds their encrypted interests
+    #[account(
+        seeds = [b"user_profile", trip_b.owner.as_ref()],
+        bump = user_profile_b.bump
+    )]
+    pub user_profile_b: Account<'info, UserProfile>,
+}
+
+/// Callback after the shared interests are computed
+#[callback_accounts("reveal_shared_interests")]
+#[derive(Accounts)]
+pub struct RevealSharedInterestsCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_REVEAL_SHARED_INTERESTS)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: computation_account for emitting events
+    pub computation_account: UncheckedAccount<'info>,
+
+    /// Mutual match the interests belong to (passed via callback accounts)
+    #[account(mut)]
+    pub match_record: Account<'info, MatchRecord>,
+}
+
+pub fn reveal_shared_interests_handler(
+    ctx: Context<RevealSharedInterests>,
+    computation_offset: u64,
+    nonce_a: u128,
+    nonce_b: u128,
+) -> Result<()> {
+    let payer_key = ctx.accounts.payer.key();
+
+    // Only the two participants may request the reveal
+    require!(
+        payer_key == ctx.accounts.trip_a.owner || payer_key == ctx.accounts.trip_b.owner,
+        ErrorCode::Unauthorized
+    );
+
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let profile_a = &ctx.accounts.user_profile_a;
+    let profile_b = &ctx.accounts.user_profile_b;
+
+    let mut args = Vec::new();
+    for (ciphertext, public_key, nonce) in [
+        (&profile_a.encrypted_data, profile_a.public_key, nonce_a),
+        (&profile_b.encrypted_data, profile_b.public_key, nonce_b),
+    ] {
+        push_encrypted_input(&mut args, public_key, nonce, ciphertext)?;
+    }
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![RevealSharedInterestsCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: ctx.accounts.match_record.key(),
+                is_writable: true,
+            },
+        ])],
+    )?;
+
+    msg!("Queued shared-interests reveal for match: {}", ctx.accounts.match_record.key());
+
+    Ok(())
+}
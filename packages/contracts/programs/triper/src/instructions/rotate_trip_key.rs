@@ -0,0 +1,73 @@
+// Rotate Trip Key Instruction
+// Replaces a trip's x25519 key and ciphertext; marks its existing matches stale
+
+use anchor_lang::prelude::*;
+use crate::state::{MatchRecord, Trip};
+use crate::error::ErrorCode;
+use crate::events::TripKeyRotated;
+use crate::constants::{ENCRYPTED_FIELD_SIZE, MAX_WAYPOINT_FIELDS};
+use crate::utils::encrypted_fields;
+
+#[derive(Accounts)]
+pub struct RotateTripKey<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+/// Remaining accounts: writable MatchRecords that include this trip
+pub fn rotate_trip_key_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RotateTripKey<'info>>,
+    encrypted_waypoints: Vec<u8>,
+    public_key: [u8; 32],
+) -> Result<()> {
+    require!(
+        encrypted_waypoints.len() <= ENCRYPTED_FIELD_SIZE * MAX_WAYPOINT_FIELDS,
+        ErrorCode::EncryptedDataTooLarge
+    );
+    require!(!encrypted_waypoints.is_empty(), ErrorCode::InvalidEncryptedData);
+    encrypted_fields(&encrypted_waypoints)?;
+    
+    // All-zero is not a usable x25519 key, and reusing the old key defeats the rotation
+    require!(
+        public_key != [0u8; 32] && public_key != ctx.accounts.trip.public_key,
+        ErrorCode::InvalidPublicKey
+    );
+    
+    let trip_key = ctx.accounts.trip.key();
+    let mut stale_matches: u32 = 0;
+    
+    for account_info in ctx.remaining_accounts.iter() {
+        let mut match_record = Account::<MatchRecord>::try_from(account_info)?;
+        require!(
+            match_record.trip_a == trip_key || match_record.trip_b == trip_key,
+            ErrorCode::UnauthorizedAccess
+        );
+        
+        match_record.is_stale = true;
+        match_record.exit(&crate::ID)?;
+        stale_matches += 1;
+    }
+    
+    let trip = &mut ctx.accounts.trip;
+    trip.encrypted_waypoints = encrypted_waypoints;
+    trip.public_key = public_key;
+    
+    let timestamp = Clock::get()?.unix_timestamp;
+    
+    emit!(TripKeyRotated {
+        trip: trip_key,
+        owner: trip.owner,
+        public_key,
+        stale_matches,
+        timestamp,
+    });
+    
+    msg!("Trip key rotated: {} ({} matches marked stale)", trip_key, stale_matches);
+    
+    Ok(())
+}
@@ -0,0 +1,185 @@
+// Self-match MPC health check
+// Admin scores a trip against itself; the result must hit known bounds if the network is healthy
+
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::SignerAccount;
+use crate::ID;
+use crate::ID_CONST;
+use crate::COMP_DEF_OFFSET_SELF_MATCH_TEST;
+use crate::error::ErrorCode;
+use crate::utils::push_encrypted_input;
+use crate::state::{ProgramConfig, Trip, UserProfile};
+use crate::constants::SELF_MATCH_MIN_TOTAL_SCORE;
+
+/// Initialize the computation definition account
+#[init_computation_definition_accounts("self_match_test", payer)]
+#[derive(Accounts)]
+pub struct InitSelfMatchTestCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a self-match health check
+#[queue_computation_accounts("self_match_test", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct SelfMatchTest<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SELF_MATCH_TEST)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+
+    /// Only the config admin may run health checks
+    #[account(
+        seeds = [b"program_config"],
+        bump = config.bump,
+        constraint = config.admin == payer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub config: Account<'info, ProgramConfig>,
+
+    /// Trip used as both A and B
+    pub trip: Account<'info, Trip>,
+
+    /// Trip owner's profile - used as both interest sets
+    #[account(
+        seeds = [b"user_profile", trip.owner.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+}
+
+/// Callback after the self-match completes
+#[callback_accounts("self_match_test")]
+#[derive(Accounts)]
+pub struct SelfMatchTestCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_SELF_MATCH_TEST)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: computation_account for emitting events
+    pub computation_account: UncheckedAccount<'info>,
+
+    /// Trip that was scored against itself (passed via callback accounts)
+    pub trip: Account<'info, Trip>,
+}
+
+pub fn self_match_test_handler(
+    ctx: Context<SelfMatchTest>,
+    computation_offset: u64,
+    nonce_trip: u128,
+    nonce_profile: u128,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let trip = &ctx.accounts.trip;
+    let user_profile = &ctx.accounts.user_profile;
+
+    // Same layout as compute_trip_match with one trip/profile in both slots
+    let segments: [(&[u8], [u8; 32], u128); 4] = [
+        (&trip.encrypted_waypoints, trip.public_key, nonce_trip),
+        (&trip.encrypted_waypoints, trip.public_key, nonce_trip),
+        (&user_profile.encrypted_data, user_profile.public_key, nonce_profile),
+        (&user_profile.encrypted_data, user_profile.public_key, nonce_profile),
+    ];
+
+    let mut args = Vec::new();
+    for (ciphertext, public_key, nonce) in segments {
+        push_encrypted_input(&mut args, public_key, nonce, ciphertext)?;
+    }
+
+    // Balanced weights
+    let (route_weight, date_weight, interest_weight) = crate::state::ScoringProfile::Balanced.weights();
+    args.push(Argument::PlaintextU8(route_weight));
+    args.push(Argument::PlaintextU8(date_weight));
+    args.push(Argument::PlaintextU8(interest_weight));
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![SelfMatchTestCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: trip.key(),
+                is_writable: false,
+            },
+        ])],
+    )?;
+
+    msg!("Queued self-match health check for trip: {}", trip.key());
+
+    Ok(())
+}
+
+/// Compare a self-match result against the bounds a healthy network produces
+/// Identical routes always give route/coverage 100; the total may vary with
+/// dates and interest data but never falls below SELF_MATCH_MIN_TOTAL_SCORE
+pub fn self_match_is_healthy(route_score: u8, coverage_score: u8, total_score: u8) -> bool {
+    route_score == 100 && coverage_score == 100 && total_score >= SELF_MATCH_MIN_TOTAL_SCORE
+}
@@ -0,0 +1,47 @@
+// Set Destination Fee Instruction
+// Admin overrides the match fee for a popular destination
+
+use anchor_lang::prelude::*;
+use crate::state::{DestinationFee, ProgramConfig};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(destination_grid_hash: [u8; 32])]
+pub struct SetDestinationFee<'info> {
+    #[account(
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + DestinationFee::INIT_SPACE,
+        seeds = [b"destination_fee", destination_grid_hash.as_ref()],
+        bump
+    )]
+    pub destination_fee: Account<'info, DestinationFee>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_destination_fee_handler(
+    ctx: Context<SetDestinationFee>,
+    destination_grid_hash: [u8; 32],
+    fee_lamports: u64,
+) -> Result<()> {
+    let destination_fee = &mut ctx.accounts.destination_fee;
+    
+    destination_fee.destination_grid_hash = destination_grid_hash;
+    destination_fee.fee_lamports = fee_lamports;
+    destination_fee.bump = ctx.bumps.destination_fee;
+    
+    msg!("Destination fee set to {} lamports", fee_lamports);
+    
+    Ok(())
+}
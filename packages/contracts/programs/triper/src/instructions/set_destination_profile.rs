@@ -0,0 +1,52 @@
+// Set Destination Profile Instruction
+// Admin picks the default scoring profile for a destination
+
+use anchor_lang::prelude::*;
+use crate::state::{DestinationProfile, ProgramConfig, ScoringProfile};
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+#[instruction(destination_grid_hash: [u8; 32])]
+pub struct SetDestinationProfile<'info> {
+    #[account(
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + DestinationProfile::INIT_SPACE,
+        seeds = [b"destination_profile", destination_grid_hash.as_ref()],
+        bump
+    )]
+    pub destination_profile: Account<'info, DestinationProfile>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_destination_profile_handler(
+    ctx: Context<SetDestinationProfile>,
+    destination_grid_hash: [u8; 32],
+    default_profile: u8,
+) -> Result<()> {
+    require!(
+        ScoringProfile::from_index(default_profile).is_some(),
+        ErrorCode::InvalidScoringProfile
+    );
+    
+    let destination_profile = &mut ctx.accounts.destination_profile;
+    
+    destination_profile.destination_grid_hash = destination_grid_hash;
+    destination_profile.default_profile = default_profile;
+    destination_profile.bump = ctx.bumps.destination_profile;
+    
+    msg!("Destination default scoring profile set to {}", default_profile);
+    
+    Ok(())
+}
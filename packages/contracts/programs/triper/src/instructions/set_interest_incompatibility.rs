@@ -0,0 +1,61 @@
+// Set Interest Incompatibility Instruction
+// Admin flags interest pairs that lower the interest score when split across a match
+
+use anchor_lang::prelude::*;
+use crate::constants::INTEREST_COUNT;
+use crate::state::{IncompatibilityMatrix, ProgramConfig};
+use crate::error::ErrorCode;
+use crate::events::InterestIncompatibilityUpdated;
+
+#[derive(Accounts)]
+pub struct SetInterestIncompatibility<'info> {
+    #[account(
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Created on first use with no interests flagged
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + IncompatibilityMatrix::INIT_SPACE,
+        seeds = [b"incompatibility_matrix"],
+        bump
+    )]
+    pub incompatibility_matrix: Account<'info, IncompatibilityMatrix>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_interest_incompatibility_handler(
+    ctx: Context<SetInterestIncompatibility>,
+    interest: u8,
+    incompatible_mask: u32,
+) -> Result<()> {
+    require!(
+        (interest as usize) < INTEREST_COUNT,
+        ErrorCode::InvalidInterestIndex
+    );
+    
+    let incompatibility_matrix = &mut ctx.accounts.incompatibility_matrix;
+    incompatibility_matrix.set_row(interest as usize, incompatible_mask);
+    incompatibility_matrix.bump = ctx.bumps.incompatibility_matrix;
+    
+    emit!(InterestIncompatibilityUpdated {
+        interest,
+        incompatible_mask: incompatibility_matrix.incompatible[interest as usize],
+    });
+    
+    msg!(
+        "Interest {} incompatible with {:#034b}",
+        interest,
+        incompatibility_matrix.incompatible[interest as usize]
+    );
+    
+    Ok(())
+}
@@ -0,0 +1,57 @@
+// Set Interest Relatedness Instruction
+// Admin tunes the interest taxonomy used for partial interest credit
+
+use anchor_lang::prelude::*;
+use crate::constants::INTEREST_COUNT;
+use crate::state::{InterestMatrix, ProgramConfig};
+use crate::error::ErrorCode;
+use crate::events::InterestRelatednessUpdated;
+
+#[derive(Accounts)]
+pub struct SetInterestRelatedness<'info> {
+    #[account(
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = admin @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Created on first use with no interests related
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + InterestMatrix::INIT_SPACE,
+        seeds = [b"interest_matrix"],
+        bump
+    )]
+    pub interest_matrix: Account<'info, InterestMatrix>,
+    
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_interest_relatedness_handler(
+    ctx: Context<SetInterestRelatedness>,
+    interest: u8,
+    related_mask: u32,
+) -> Result<()> {
+    require!(
+        (interest as usize) < INTEREST_COUNT,
+        ErrorCode::InvalidInterestIndex
+    );
+    
+    let interest_matrix = &mut ctx.accounts.interest_matrix;
+    interest_matrix.set_row(interest as usize, related_mask);
+    interest_matrix.bump = ctx.bumps.interest_matrix;
+    
+    emit!(InterestRelatednessUpdated {
+        interest,
+        related_mask: interest_matrix.related[interest as usize],
+    });
+    
+    msg!("Interest {} related to {:#034b}", interest, interest_matrix.related[interest as usize]);
+    
+    Ok(())
+}
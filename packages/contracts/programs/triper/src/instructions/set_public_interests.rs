@@ -0,0 +1,34 @@
+// Set Public Interests Instruction
+// Owner opts in (or out) of publishing an interest bitmask for match previews
+
+use anchor_lang::prelude::*;
+use crate::state::UserProfile;
+
+#[derive(Accounts)]
+pub struct SetPublicInterests<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub user: Signer<'info>,
+}
+
+/// None withdraws the published interests
+pub fn set_public_interests_handler(
+    ctx: Context<SetPublicInterests>,
+    interests: Option<u32>,
+) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    user_profile.public_interests = interests;
+    user_profile.last_active_at = Clock::get()?.unix_timestamp;
+    
+    match interests {
+        Some(mask) => msg!("Public interests set: {:#034b}", mask),
+        None => msg!("Public interests withdrawn"),
+    }
+    
+    Ok(())
+}
@@ -0,0 +1,44 @@
+// Set Route Density Instruction
+// Owner publishes a coarse route-density hint (waypoints per bounding-box area bucket)
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::error::ErrorCode;
+use crate::constants::{MAX_ROUTE_AREA_BUCKET, MAX_WAYPOINTS};
+
+#[derive(Accounts)]
+pub struct SetRouteDensity<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+/// `area_bucket` is floor(log2(bounding-box area in waypoint cells + 1))
+pub fn set_route_density_handler(
+    ctx: Context<SetRouteDensity>,
+    waypoint_count: u8,
+    area_bucket: u8,
+) -> Result<()> {
+    require!(
+        waypoint_count > 0 && waypoint_count as usize <= MAX_WAYPOINTS,
+        ErrorCode::InvalidRouteDensity
+    );
+    require!(area_bucket <= MAX_ROUTE_AREA_BUCKET, ErrorCode::InvalidRouteDensity);
+    
+    // N distinct cells can't fit in a box smaller than N cells
+    require!(
+        area_bucket as u32 >= (waypoint_count as u32).ilog2(),
+        ErrorCode::InvalidRouteDensity
+    );
+    
+    let trip = &mut ctx.accounts.trip;
+    trip.route_density = Trip::compute_route_density(waypoint_count, area_bucket);
+    
+    msg!("Trip {} route density: {}", trip.key(), trip.route_density);
+    
+    Ok(())
+}
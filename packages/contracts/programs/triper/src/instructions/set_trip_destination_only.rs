@@ -0,0 +1,29 @@
+// Set Trip Destination-Only Instruction
+// Owner opts the trip into matching on destination + dates, ignoring route overlap
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetTripDestinationOnly<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn set_trip_destination_only_handler(
+    ctx: Context<SetTripDestinationOnly>,
+    destination_only_match: bool,
+) -> Result<()> {
+    let trip = &mut ctx.accounts.trip;
+    trip.destination_only_match = destination_only_match;
+    
+    msg!("Trip {} destination-only matching: {}", trip.key(), destination_only_match);
+    
+    Ok(())
+}
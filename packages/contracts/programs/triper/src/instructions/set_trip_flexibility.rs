@@ -0,0 +1,31 @@
+// Set Trip Flexibility Instruction
+// Owner sets how far they'd move their dates to meet someone (public, widens date scoring)
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct SetTripFlexibility<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn set_trip_flexibility_handler(
+    ctx: Context<SetTripFlexibility>,
+    flexibility: u8,
+) -> Result<()> {
+    require!(flexibility <= 100, ErrorCode::InvalidFlexibility);
+    
+    let trip = &mut ctx.accounts.trip;
+    trip.flexibility = flexibility;
+    
+    msg!("Trip {} flexibility: {}", trip.key(), flexibility);
+    
+    Ok(())
+}
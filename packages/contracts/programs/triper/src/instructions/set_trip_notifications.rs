@@ -0,0 +1,57 @@
+// Set Trip Notifications Instruction
+// Owner picks which match events the client should alert them about
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::error::ErrorCode;
+use crate::events::MatchNotification;
+use crate::constants::NOTIFY_ALL;
+
+#[derive(Accounts)]
+pub struct SetTripNotifications<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+/// `notify_on` is a NOTIFY_* bitfield (0 = never alert)
+pub fn set_trip_notifications_handler(
+    ctx: Context<SetTripNotifications>,
+    notify_on: u8,
+) -> Result<()> {
+    require!(notify_on & !NOTIFY_ALL == 0, ErrorCode::InvalidNotificationFlags);
+    
+    let trip = &mut ctx.accounts.trip;
+    trip.notify_on = notify_on;
+    
+    msg!("Trip {} notifications: {:#05b}", trip.key(), notify_on);
+    
+    Ok(())
+}
+
+/// Emit a MatchNotification of `kind` (one NOTIFY_* bit) for each trip
+/// The events always fire for indexers; `notify` carries each owner's
+/// preference so clients know whether to alert
+pub fn notify_participants(
+    match_record: Pubkey,
+    trip_a: &Account<Trip>,
+    trip_b: &Account<Trip>,
+    kind: u8,
+) -> Result<()> {
+    let timestamp = Clock::get()?.unix_timestamp;
+    for trip in [trip_a, trip_b] {
+        emit!(MatchNotification {
+            match_record,
+            trip: trip.key(),
+            owner: trip.owner,
+            kind,
+            notify: trip.notifies(kind),
+            timestamp,
+        });
+    }
+    Ok(())
+}
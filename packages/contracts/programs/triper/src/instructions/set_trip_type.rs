@@ -0,0 +1,39 @@
+// Set Trip Type Instruction
+// Owner sets who they are on this trip and who they want to meet (public pre-filter)
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::error::ErrorCode;
+use crate::constants::{SEEKING_ANY, TRAVELER_TYPE_NOMAD};
+
+#[derive(Accounts)]
+pub struct SetTripType<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn set_trip_type_handler(
+    ctx: Context<SetTripType>,
+    traveler_type: u8,
+    seeking_type: u8,
+) -> Result<()> {
+    require!(traveler_type <= TRAVELER_TYPE_NOMAD, ErrorCode::InvalidTravelerType);
+    require!(
+        seeking_type <= TRAVELER_TYPE_NOMAD || seeking_type == SEEKING_ANY,
+        ErrorCode::InvalidTravelerType
+    );
+    
+    let trip = &mut ctx.accounts.trip;
+    
+    trip.traveler_type = traveler_type;
+    trip.seeking_type = seeking_type;
+    
+    msg!("Trip {} type {} seeking {}", trip.key(), traveler_type, seeking_type);
+    
+    Ok(())
+}
@@ -0,0 +1,66 @@
+// Verification Tier Instructions
+// Verifier sets a user's tier; trip owners set the tier they require of counterparties
+
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Trip, UserProfile};
+use crate::error::ErrorCode;
+use crate::events::VerificationTierSet;
+
+#[derive(Accounts)]
+pub struct SetVerificationTier<'info> {
+    #[account(
+        seeds = [b"program_config"],
+        bump = config.bump,
+        has_one = verifier @ ErrorCode::Unauthorized
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    #[account(mut)]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub verifier: Signer<'info>,
+}
+
+pub fn set_verification_tier_handler(
+    ctx: Context<SetVerificationTier>,
+    tier: u8,
+) -> Result<()> {
+    let user_profile = &mut ctx.accounts.user_profile;
+    
+    user_profile.verification_tier = tier;
+    
+    emit!(VerificationTierSet {
+        user_profile: user_profile.key(),
+        owner: user_profile.owner,
+        tier,
+        verifier: ctx.accounts.verifier.key(),
+    });
+    
+    msg!("Verification tier for {} set to {}", user_profile.owner, tier);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMinCounterpartyTier<'info> {
+    #[account(
+        mut,
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn set_min_counterparty_tier_handler(
+    ctx: Context<SetMinCounterpartyTier>,
+    min_tier: u8,
+) -> Result<()> {
+    let trip = &mut ctx.accounts.trip;
+    
+    trip.min_counterparty_tier = min_tier;
+    
+    msg!("Trip {} now requires counterparty tier >= {}", trip.key(), min_tier);
+    
+    Ok(())
+}
@@ -0,0 +1,35 @@
+// Split Encrypted Payload Instruction
+// Read-only check of a combined payload against the canonical framing in utils
+
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::EncryptedPayloadSplit;
+use crate::constants::ENCRYPTED_FIELD_SIZE;
+use crate::utils::{join_encrypted_payload, split_encrypted_payload};
+
+#[derive(Accounts)]
+pub struct SplitEncryptedPayload<'info> {
+    pub payer: Signer<'info>,
+}
+
+pub fn split_encrypted_payload_handler(
+    _ctx: Context<SplitEncryptedPayload>,
+    payload: Vec<u8>,
+) -> Result<()> {
+    let (waypoints, interests) = split_encrypted_payload(&payload)?;
+    
+    // The framing must round-trip exactly
+    require!(
+        join_encrypted_payload(waypoints, interests)? == payload,
+        ErrorCode::MalformedEncryptedPayload
+    );
+    
+    emit!(EncryptedPayloadSplit {
+        waypoint_fields: (waypoints.len() / ENCRYPTED_FIELD_SIZE) as u8,
+        interest_fields: (interests.len() / ENCRYPTED_FIELD_SIZE) as u8,
+        waypoints: waypoints.to_vec(),
+        interests: interests.to_vec(),
+    });
+    
+    Ok(())
+}
@@ -0,0 +1,58 @@
+// Submit Reveal Key Instruction
+// Each participant of a mutual match opts in to reveals; both must before any MXE decryption
+
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+use crate::events::RevealKeySubmitted;
+use crate::state::{MatchRecord, MatchStatus, Trip};
+
+#[derive(Accounts)]
+pub struct SubmitRevealKey<'info> {
+    #[account(
+        mut,
+        constraint = match_record.status == MatchStatus::Mutual @ ErrorCode::InvalidMatchStatus
+    )]
+    pub match_record: Account<'info, MatchRecord>,
+    
+    /// Submitter's trip in this match
+    #[account(
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn submit_reveal_key_handler(
+    ctx: Context<SubmitRevealKey>,
+    reveal_key: [u8; 32],
+) -> Result<()> {
+    require!(reveal_key != [0u8; 32], ErrorCode::InvalidPublicKey);
+    
+    let match_record = &mut ctx.accounts.match_record;
+    let trip_key = ctx.accounts.trip.key();
+    
+    if trip_key == match_record.trip_a {
+        match_record.reveal_key_a = reveal_key;
+    } else if trip_key == match_record.trip_b {
+        match_record.reveal_key_b = reveal_key;
+    } else {
+        return Err(ErrorCode::Unauthorized.into());
+    }
+    
+    let both_submitted = match_record.reveal_keys_exchanged();
+    
+    emit!(RevealKeySubmitted {
+        match_record: match_record.key(),
+        participant: ctx.accounts.user.key(),
+        both_submitted,
+    });
+    
+    if both_submitted {
+        msg!("Both reveal keys submitted - reveals unblocked");
+    } else {
+        msg!("Reveal key submitted. Waiting for the other party.");
+    }
+    
+    Ok(())
+}
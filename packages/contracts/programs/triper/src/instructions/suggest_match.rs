@@ -0,0 +1,227 @@
+// Suggest Match Instructions
+// Commit-reveal randomness for picking a fair match suggestion from a destination shard
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::{hash, hashv};
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use crate::constants::{BOOST_RANK_BONUS, SUGGESTION_BASE_WEIGHT};
+use crate::state::{DestinationIndexShard, SuggestionSeed, Trip};
+use crate::error::ErrorCode;
+use crate::events::{MatchSuggested, SuggestionSeedCommitted};
+
+#[derive(Accounts)]
+pub struct CommitSuggestionSeed<'info> {
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + SuggestionSeed::INIT_SPACE,
+        seeds = [b"suggestion_seed", trip.key().as_ref()],
+        bump
+    )]
+    pub suggestion_seed: Account<'info, SuggestionSeed>,
+    
+    #[account(
+        has_one = owner @ ErrorCode::Unauthorized,
+        constraint = trip.is_active @ ErrorCode::TripNotActive
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn commit_suggestion_seed_handler(
+    ctx: Context<CommitSuggestionSeed>,
+    commitment: [u8; 32],
+    shard_id: u16,
+) -> Result<()> {
+    let suggestion_seed = &mut ctx.accounts.suggestion_seed;
+    suggestion_seed.trip = ctx.accounts.trip.key();
+    suggestion_seed.commitment = commitment;
+    suggestion_seed.commit_slot = Clock::get()?.slot;
+    suggestion_seed.shard_id = shard_id;
+    suggestion_seed.bump = ctx.bumps.suggestion_seed;
+    
+    emit!(SuggestionSeedCommitted {
+        trip: suggestion_seed.trip,
+        commitment,
+        commit_slot: suggestion_seed.commit_slot,
+        shard_id,
+    });
+    
+    msg!("Suggestion seed committed at slot {}", suggestion_seed.commit_slot);
+    
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RevealAndSuggest<'info> {
+    /// Spent on reveal; rent goes back to the owner
+    #[account(
+        mut,
+        seeds = [b"suggestion_seed", trip.key().as_ref()],
+        bump = suggestion_seed.bump,
+        close = owner
+    )]
+    pub suggestion_seed: Account<'info, SuggestionSeed>,
+    
+    #[account(
+        has_one = owner @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    /// The shard fixed at commit time, for the trip's destination
+    #[account(
+        seeds = [
+            b"dest",
+            trip.destination_grid_hash.as_ref(),
+            &suggestion_seed.shard_id.to_le_bytes(),
+        ],
+        bump = destination_shard.bump
+    )]
+    pub destination_shard: Account<'info, DestinationIndexShard>,
+    
+    /// CHECK: SlotHashes sysvar, checked by the address constraint
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+    
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+/// Hash of `slot` from raw SlotHashes data, None once it has aged out
+/// Layout: u64 entry count, then (slot: u64, hash: [u8; 32]) entries,
+/// newest first. Scanned in place - the sysvar is too large to deserialize
+fn find_slot_hash(data: &[u8], slot: u64) -> Option<[u8; 32]> {
+    const ENTRY_LEN: usize = 8 + 32;
+    let count = u64::from_le_bytes(data.get(..8)?.try_into().ok()?) as usize;
+    let entries = data.get(8..)?;
+    
+    entries
+        .chunks_exact(ENTRY_LEN)
+        .take(count)
+        .find(|entry| u64::from_le_bytes(entry[..8].try_into().unwrap()) == slot)
+        .map(|entry| entry[8..].try_into().unwrap())
+}
+
+/// Draw weight of a suggestion candidate
+/// A boost adds BOOST_RANK_BONUS, the same lift ranking_score gives a
+/// boosted counterparty; an expired boost weighs like no boost at all
+pub fn suggestion_weight(boosted: bool) -> u64 {
+    SUGGESTION_BASE_WEIGHT + if boosted { BOOST_RANK_BONUS as u64 } else { 0 }
+}
+
+/// Index of the candidate whose slice of the cumulative weights holds
+/// `draw`, None when every weight is 0
+fn weighted_index(draw: u64, weights: &[u64]) -> Option<usize> {
+    let total: u64 = weights.iter().sum();
+    if total == 0 {
+        return None;
+    }
+    let mut target = draw % total;
+    weights.iter().position(|&weight| {
+        if target < weight {
+            true
+        } else {
+            target -= weight;
+            false
+        }
+    })
+}
+
+/// Remaining accounts: every trip in destination_shard, in shard order
+/// Boosted trips are drawn with a heavier weight; a full shard needs an
+/// address lookup table to fit them all
+pub fn reveal_and_suggest_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, RevealAndSuggest<'info>>,
+    seed: [u8; 32],
+) -> Result<()> {
+    let suggestion_seed = &ctx.accounts.suggestion_seed;
+    
+    require!(
+        hash(&seed).to_bytes() == suggestion_seed.commitment,
+        ErrorCode::SeedCommitmentMismatch
+    );
+    require!(
+        Clock::get()?.slot > suggestion_seed.commit_slot,
+        ErrorCode::SeedRevealTooEarly
+    );
+    
+    let slot_hash = {
+        let data = ctx.accounts.slot_hashes.try_borrow_data()?;
+        find_slot_hash(&data, suggestion_seed.commit_slot).ok_or(ErrorCode::SeedExpired)?
+    };
+    
+    let trip_key = ctx.accounts.trip.key();
+    let randomness = hashv(&[&seed, &slot_hash, trip_key.as_ref()]).to_bytes();
+    
+    // Every candidate is read to weigh its boost; the requesting trip and
+    // trips closed since they were indexed weigh 0, so they are never drawn
+    let candidates = &ctx.accounts.destination_shard.trips;
+    require!(
+        ctx.remaining_accounts.len() == candidates.len(),
+        ErrorCode::SuggestionCandidatesMismatch
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let mut weights = Vec::with_capacity(candidates.len());
+    let mut boosted = Vec::with_capacity(candidates.len());
+    for (candidate, candidate_info) in candidates.iter().zip(ctx.remaining_accounts.iter()) {
+        require_keys_eq!(candidate_info.key(), *candidate, ErrorCode::SuggestionCandidatesMismatch);
+        let trip = Account::<Trip>::try_from(candidate_info).ok();
+        let is_boosted = trip.as_ref().is_some_and(|trip| trip.is_boosted(now));
+        let drawable = trip.is_some() && *candidate != trip_key;
+        weights.push(if drawable { suggestion_weight(is_boosted) } else { 0 });
+        boosted.push(is_boosted);
+    }
+    
+    let draw = u64::from_le_bytes(randomness[..8].try_into().unwrap());
+    let index = weighted_index(draw, &weights).ok_or(ErrorCode::NoSuggestionCandidates)?;
+    let suggested_trip = candidates[index];
+    
+    emit!(MatchSuggested {
+        trip: trip_key,
+        suggested_trip,
+        shard_id: suggestion_seed.shard_id,
+        randomness,
+        boosted: boosted[index],
+    });
+    
+    msg!("Suggested trip {} for {}", suggested_trip, trip_key);
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn active_boost_raises_the_draw_weight() {
+        assert_eq!(suggestion_weight(false), SUGGESTION_BASE_WEIGHT);
+        assert_eq!(suggestion_weight(true), SUGGESTION_BASE_WEIGHT + BOOST_RANK_BONUS as u64);
+    }
+    
+    #[test]
+    fn boosted_candidate_wins_a_larger_share_of_draws() {
+        // Over one full cycle of draws each candidate wins exactly its weight
+        let share = |weights: &[u64]| {
+            let total: u64 = weights.iter().sum();
+            (0..total).filter(|&draw| weighted_index(draw, weights) == Some(0)).count() as u64
+        };
+        let plain = [suggestion_weight(false), suggestion_weight(false)];
+        let boosted = [suggestion_weight(true), suggestion_weight(false)];
+        
+        assert_eq!(share(&plain), 100); // of 200
+        assert_eq!(share(&boosted), 125); // of 225
+    }
+    
+    #[test]
+    fn zero_weight_candidates_are_never_drawn() {
+        let weights = [0, suggestion_weight(false), 0];
+        assert!((0..1_000).all(|draw| weighted_index(draw, &weights) == Some(1)));
+        assert_eq!(weighted_index(7, &[0, 0]), None);
+        assert_eq!(weighted_index(7, &[]), None);
+    }
+}
@@ -0,0 +1,63 @@
+// Sweep Inactive User Instruction
+// Dead-man's switch: anyone can deactivate the trips of a user who stopped acting
+
+use anchor_lang::prelude::*;
+use crate::state::{ProgramConfig, Trip, UserProfile};
+use crate::error::ErrorCode;
+use crate::events::UserSwept;
+
+#[derive(Accounts)]
+pub struct SweepInactiveUser<'info> {
+    #[account(
+        seeds = [b"program_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, ProgramConfig>,
+    
+    /// Profile whose activity decides the sweep
+    pub user_profile: Account<'info, UserProfile>,
+    
+    /// Anyone can crank the sweep
+    pub cranker: Signer<'info>,
+}
+
+/// Remaining accounts: writable Trips owned by user_profile.owner
+pub fn sweep_inactive_user_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SweepInactiveUser<'info>>,
+) -> Result<()> {
+    let user_profile = &ctx.accounts.user_profile;
+    let now = Clock::get()?.unix_timestamp;
+    
+    // Recently active users are left alone
+    if now - user_profile.last_active_at <= ctx.accounts.config.inactivity_period_secs {
+        msg!("User {} is still active - nothing to sweep", user_profile.owner);
+        return Ok(());
+    }
+    
+    let mut trips_deactivated: u32 = 0;
+    
+    for account_info in ctx.remaining_accounts.iter() {
+        let mut trip = Account::<Trip>::try_from(account_info)?;
+        require!(
+            trip.owner == user_profile.owner,
+            ErrorCode::Unauthorized
+        );
+        
+        if trip.is_active {
+            trip.is_active = false;
+            trip.exit(&crate::ID)?;
+            trips_deactivated += 1;
+        }
+    }
+    
+    emit!(UserSwept {
+        owner: user_profile.owner,
+        last_active_at: user_profile.last_active_at,
+        trips_deactivated,
+        timestamp: now,
+    });
+    
+    msg!("Swept inactive user {}: {} trips deactivated", user_profile.owner, trips_deactivated);
+    
+    Ok(())
+}
@@ -0,0 +1,104 @@
+// Undo Reject Instruction
+// Lets the rejecting user restore a match rejected by mistake
+
+use anchor_lang::prelude::*;
+use crate::constants::UNDO_REJECT_WINDOW_SECS;
+use crate::error::ErrorCode;
+use crate::instructions::{acquire_pending_slot, participant_profile};
+use crate::state::{MatchRecord, MatchStatus, Trip, UserProfile};
+
+#[derive(Accounts)]
+pub struct UndoReject<'info> {
+    #[account(
+        mut,
+        constraint = match_account.status == MatchStatus::Rejected @ ErrorCode::InvalidMatchStatus,
+        constraint = match_account.rejected_by == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub match_account: Account<'info, MatchRecord>,
+    
+    /// Trip account to verify ownership
+    #[account(
+        constraint = trip.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    /// Initiator's profile - required while the match holds a pending-match slot
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.pending_slot_owner.as_ref()],
+        bump = initiator_profile.bump
+    )]
+    pub initiator_profile: Option<Account<'info, UserProfile>>,
+    
+    /// Trip A owner's profile - required when trip A's owner was the one
+    /// rejected (omit it while initiator_profile already stands in for it)
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.trip_a_owner.as_ref()],
+        bump = owner_profile_a.bump
+    )]
+    pub owner_profile_a: Option<Account<'info, UserProfile>>,
+    
+    /// Trip B owner's profile - required when trip B's owner was the one rejected
+    #[account(
+        mut,
+        seeds = [b"user_profile", match_account.trip_b_owner.as_ref()],
+        bump = owner_profile_b.bump
+    )]
+    pub owner_profile_b: Option<Account<'info, UserProfile>>,
+    
+    pub user: Signer<'info>,
+}
+
+pub fn undo_reject_handler(ctx: Context<UndoReject>) -> Result<()> {
+    let accounts = &mut *ctx.accounts;
+    let match_account = &mut accounts.match_account;
+    let trip_key = accounts.trip.key();
+    
+    require!(
+        trip_key == match_account.trip_a || trip_key == match_account.trip_b,
+        ErrorCode::Unauthorized
+    );
+    
+    // Only within the grace window after rejecting
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        now <= match_account.rejected_at + UNDO_REJECT_WINDOW_SECS,
+        ErrorCode::InvalidMatchStatus
+    );
+    
+    // Back to where it was rejected from: scored matches carry a deadline
+    match_account.status = if match_account.accept_deadline != 0 {
+        MatchStatus::Completed
+    } else {
+        MatchStatus::Pending
+    };
+    
+    // The restored match takes its initiator's pending slot back (no cap
+    // check - it only returns a slot it already had)
+    if match_account.pending_slot_owner != Pubkey::default() {
+        let profile = accounts
+            .initiator_profile
+            .as_mut()
+            .ok_or(ErrorCode::InitiatorProfileRequired)?;
+        acquire_pending_slot(match_account, profile);
+    }
+    match_account.rejected_at = 0;
+    match_account.rejected_by = Pubkey::default();
+    
+    // Give back the rejection reject_match charged to the other owner, so a
+    // reject/undo loop can't keep lowering their reputation
+    let rejected_a = trip_key == match_account.trip_b;
+    let (rejected_owner, rejected_profile) = if rejected_a {
+        (match_account.trip_a_owner, &mut accounts.owner_profile_a)
+    } else {
+        (match_account.trip_b_owner, &mut accounts.owner_profile_b)
+    };
+    participant_profile(&mut accounts.initiator_profile, rejected_profile, rejected_owner)?
+        .ok_or(ErrorCode::RejectedProfileRequired)?
+        .revoke_rejection_received();
+    
+    msg!("↺ Rejection undone by user {}", accounts.user.key());
+    
+    Ok(())
+}
@@ -0,0 +1,54 @@
+// Update Trip Instruction
+// Owner corrects a trip's dates and route in place, keeping its PDA
+
+use anchor_lang::prelude::*;
+use crate::state::Trip;
+use crate::error::ErrorCode;
+use crate::events::TripUpdated;
+use crate::instructions::validate_trip_input;
+
+#[derive(Accounts)]
+pub struct UpdateTrip<'info> {
+    /// The PDA keeps the start_date it was created with as its seed
+    #[account(
+        mut,
+        has_one = owner @ ErrorCode::Unauthorized,
+        constraint = trip.is_active @ ErrorCode::TripNotActive
+    )]
+    pub trip: Account<'info, Trip>,
+    
+    pub owner: Signer<'info>,
+}
+
+pub fn update_trip_handler(
+    ctx: Context<UpdateTrip>,
+    start_date: i64,
+    end_date: i64,
+    encrypted_waypoints: Vec<u8>,
+    public_key: [u8; 32],
+) -> Result<()> {
+    validate_trip_input(start_date, end_date, &encrypted_waypoints)?;
+    
+    let trip = &mut ctx.accounts.trip;
+    trip.start_date = start_date;
+    trip.end_date = end_date;
+    trip.encrypted_waypoints = encrypted_waypoints;
+    trip.public_key = public_key;
+    
+    // The route changed, so the trip gets a fresh match quota
+    trip.match_count = 0;
+    trip.updated_at = Clock::get()?.unix_timestamp;
+    
+    emit!(TripUpdated {
+        trip: trip.key(),
+        owner: trip.owner,
+        start_date,
+        end_date,
+        timestamp: trip.updated_at,
+    });
+    
+    msg!("Trip updated: {}", trip.key());
+    msg!("Dates: {} to {}", start_date, end_date);
+    
+    Ok(())
+}
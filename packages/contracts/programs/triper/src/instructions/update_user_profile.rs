@@ -35,6 +35,8 @@ pub fn update_user_profile_handler(
     user_profile.encrypted_data = encrypted_data;
     user_profile.public_key = public_key;
     user_profile.updated_at = clock.unix_timestamp;
+    user_profile.interests_updated_at = clock.unix_timestamp;
+    user_profile.last_active_at = clock.unix_timestamp;
     
     emit!(UserProfileUpdated {
         user_profile: user_profile.key(),
@@ -46,3 +48,22 @@ pub fn update_user_profile_handler(
     
     Ok(())
 }
+
+#[derive(Accounts)]
+pub struct RecordActivity<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_profile", user.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.owner == user.key() @ ErrorCode::Unauthorized,
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    
+    pub user: Signer<'info>,
+}
+
+/// Heartbeat - refreshes last_active_at without touching profile data
+pub fn record_activity_handler(ctx: Context<RecordActivity>) -> Result<()> {
+    ctx.accounts.user_profile.last_active_at = Clock::get()?.unix_timestamp;
+    Ok(())
+}
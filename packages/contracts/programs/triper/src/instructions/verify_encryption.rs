@@ -0,0 +1,147 @@
+// Self-verification of trip encryption
+// Owner checks their ciphertext decrypts to a valid layout - only pass/fail is revealed
+
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+
+use crate::SignerAccount;
+use crate::ID;
+use crate::ID_CONST;
+use crate::COMP_DEF_OFFSET_VERIFY_TRIP_ENCRYPTION;
+use crate::error::ErrorCode;
+use crate::utils::push_encrypted_input;
+
+/// Initialize the computation definition account
+#[init_computation_definition_accounts("verify_trip_encryption", payer)]
+#[derive(Accounts)]
+pub struct InitVerifyTripEncryptionCompDef<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Box<Account<'info, MXEAccount>>,
+    #[account(mut)]
+    /// CHECK: comp_def_account, checked by arcium program.
+    pub comp_def_account: UncheckedAccount<'info>,
+    pub arcium_program: Program<'info, Arcium>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Queue a self-verification computation
+#[queue_computation_accounts("verify_trip_encryption", payer)]
+#[derive(Accounts)]
+#[instruction(computation_offset: u64)]
+pub struct VerifyOwnEncryption<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init_if_needed,
+        space = 9,
+        payer = payer,
+        seeds = [&SIGN_PDA_SEED],
+        bump,
+        address = derive_sign_pda!(),
+    )]
+    pub sign_pda_account: Account<'info, SignerAccount>,
+    #[account(
+        address = derive_mxe_pda!()
+    )]
+    pub mxe_account: Account<'info, MXEAccount>,
+    #[account(
+        mut,
+        address = derive_mempool_pda!()
+    )]
+    /// CHECK: mempool_account, checked by the arcium program.
+    pub mempool_account: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_execpool_pda!()
+    )]
+    /// CHECK: executing_pool, checked by the arcium program.
+    pub executing_pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        address = derive_comp_pda!(computation_offset)
+    )]
+    /// CHECK: computation_account, checked by the arcium program.
+    pub computation_account: UncheckedAccount<'info>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_TRIP_ENCRYPTION)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(
+        mut,
+        address = derive_cluster_pda!(mxe_account)
+    )]
+    pub cluster_account: Account<'info, Cluster>,
+    #[account(
+        mut,
+        address = ARCIUM_FEE_POOL_ACCOUNT_ADDRESS,
+    )]
+    pub pool_account: Account<'info, FeePool>,
+    #[account(
+        address = ARCIUM_CLOCK_ACCOUNT_ADDRESS
+    )]
+    pub clock_account: Account<'info, ClockAccount>,
+    pub system_program: Program<'info, System>,
+    pub arcium_program: Program<'info, Arcium>,
+
+    /// Trip to verify - only its owner may check it
+    #[account(
+        constraint = trip.owner == payer.key() @ ErrorCode::Unauthorized,
+    )]
+    pub trip: Account<'info, crate::state::Trip>,
+}
+
+/// Callback after verification completes
+#[callback_accounts("verify_trip_encryption")]
+#[derive(Accounts)]
+pub struct VerifyTripEncryptionCallback<'info> {
+    pub arcium_program: Program<'info, Arcium>,
+    #[account(
+        address = derive_comp_def_pda!(COMP_DEF_OFFSET_VERIFY_TRIP_ENCRYPTION)
+    )]
+    pub comp_def_account: Account<'info, ComputationDefinitionAccount>,
+    #[account(address = ::anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions_sysvar, checked by the account constraint
+    pub instructions_sysvar: AccountInfo<'info>,
+    #[account(mut)]
+    /// CHECK: computation_account for emitting events
+    pub computation_account: UncheckedAccount<'info>,
+
+    /// Trip that was verified (passed via callback accounts)
+    pub trip: Account<'info, crate::state::Trip>,
+}
+
+pub fn verify_own_encryption_handler(
+    ctx: Context<VerifyOwnEncryption>,
+    computation_offset: u64,
+    nonce: u128,
+) -> Result<()> {
+    ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
+
+    let trip = &ctx.accounts.trip;
+    let trip_key = trip.key();
+
+    let mut args = Vec::new();
+    push_encrypted_input(&mut args, trip.public_key, nonce, &trip.encrypted_waypoints)?;
+
+    queue_computation(
+        ctx.accounts,
+        computation_offset,
+        args,
+        None,
+        vec![VerifyTripEncryptionCallback::callback_ix(&[
+            CallbackAccount {
+                pubkey: trip_key,
+                is_writable: false,
+            },
+        ])],
+    )?;
+
+    msg!("Queued encryption self-check for trip: {}", trip_key);
+
+    Ok(())
+}
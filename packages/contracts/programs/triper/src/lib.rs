@@ -9,18 +9,38 @@ pub mod instructions;
 pub mod state;
 pub mod error;
 pub mod events;
+pub mod prefilter;
+pub mod scoring;
+pub mod constants;
+pub mod utils;
 
 // Re-export for convenience
 pub use instructions::*;
 pub use state::*;
 pub use error::*;
 pub use events::*;
+pub use constants::*;
 
 declare_id!("Fn6rAGhjUc45tQqfgsXCdNtNC3GSfNWdjHEjpHaUJMaY");
 
 // Computation definition offset for compute_trip_match encrypted instruction
 const COMP_DEF_OFFSET_COMPUTE_TRIP_MATCH: u32 = comp_def_offset("compute_trip_match");
 
+// Computation definition offset for verify_trip_encryption encrypted instruction
+const COMP_DEF_OFFSET_VERIFY_TRIP_ENCRYPTION: u32 = comp_def_offset("verify_trip_encryption");
+
+// Computation definition offset for reveal_coordination encrypted instruction
+const COMP_DEF_OFFSET_REVEAL_COORDINATION: u32 = comp_def_offset("reveal_coordination");
+
+// Computation definition offset for reveal_shared_interests encrypted instruction
+const COMP_DEF_OFFSET_REVEAL_SHARED_INTERESTS: u32 = comp_def_offset("reveal_shared_interests");
+
+// Computation definition offset for self_match_test encrypted instruction
+const COMP_DEF_OFFSET_SELF_MATCH_TEST: u32 = comp_def_offset("self_match_test");
+
+// Computation definition offset for check_match_threshold encrypted instruction
+const COMP_DEF_OFFSET_CHECK_MATCH_THRESHOLD: u32 = comp_def_offset("check_match_threshold");
+
 #[arcium_program]
 pub mod triper {
     use super::*;
@@ -43,12 +63,22 @@ pub mod triper {
         instructions::update_user_profile_handler(ctx, encrypted_data, public_key)
     }
 
+    /// Refresh the caller's last_active_at (keeps their trips from being swept)
+    pub fn record_activity(ctx: Context<RecordActivity>) -> Result<()> {
+        instructions::record_activity_handler(ctx)
+    }
+
     // Accept a match
+    // Reaching Mutual credits both owners' profiles (when passed)
     pub fn accept_match(ctx: Context<AcceptMatch>) -> Result<()> {
         instructions::accept_match_handler(ctx)
     }
 
     /// Create a new trip with encrypted data
+    /// Indexes it in destination shard `shard_id`, the destination's open shard
+    /// Routes whose area bucket exceeds the configured maximum are rejected
+    /// With the owner's payload registry, reused waypoint ciphertext is rejected
+    #[allow(clippy::too_many_arguments)]
     pub fn create_trip(
         ctx: Context<CreateTrip>,
         destination_grid_hash: [u8; 32],
@@ -56,6 +86,8 @@ pub mod triper {
         end_date: i64,
         encrypted_data: Vec<u8>,
         public_key: [u8; 32],
+        shard_id: u16,
+        route_area_bucket: u8,
     ) -> Result<()> {
         instructions::create_trip_handler(
             ctx,
@@ -64,13 +96,52 @@ pub mod triper {
             end_date,
             encrypted_data,
             public_key,
+            shard_id,
+            route_area_bucket,
         )
     }
 
+    /// Clone a trip with a new date window, reusing its encrypted route
+    pub fn clone_trip(
+        ctx: Context<CloneTrip>,
+        start_date: i64,
+        end_date: i64,
+        shard_id: u16,
+    ) -> Result<()> {
+        instructions::clone_trip_handler(ctx, start_date, end_date, shard_id)
+    }
+
+    /// Import several already-encrypted trips to one destination at once
+    /// Trip PDAs are passed as writable remaining accounts in entry order;
+    /// malformed entries are skipped and counted rather than failing the batch
+    /// Imported trips are indexed in the destination's open shard
+    pub fn create_trips_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateTripsBatch<'info>>,
+        trips: Vec<TripImport>,
+        public_key: [u8; 32],
+        destination_grid_hash: [u8; 32],
+        shard_id: u16,
+    ) -> Result<()> {
+        instructions::create_trips_batch_handler(ctx, trips, public_key, destination_grid_hash, shard_id)
+    }
+
     /// Initiate a match computation between two trips
     /// Creates MatchRecord in Pending status
-    pub fn initiate_match(ctx: Context<InitiateMatch>) -> Result<()> {
-        instructions::initiate_match_handler(ctx)
+    /// The scoring profile fixes the weights compute_trip_match will use;
+    /// without one, trip A's destination default (else Balanced) applies
+    /// With encrypt_scores the scores are readable only by the participants
+    /// A non-zero budget_weight adds budget compatibility to the total
+    /// Matches scoring below min_score can't be accepted
+    /// Fails if either owner's blocklist names the other
+    /// A rejected pair can be re-initiated only after REMATCH_COOLDOWN_SECS
+    pub fn initiate_match(
+        ctx: Context<InitiateMatch>,
+        scoring_profile: Option<ScoringProfile>,
+        encrypt_scores: bool,
+        budget_weight: u8,
+        min_score: u8,
+    ) -> Result<()> {
+        instructions::initiate_match_handler(ctx, scoring_profile, encrypt_scores, budget_weight, min_score)
     }
 
     /// Initialize the computation definition for match computation
@@ -84,63 +155,209 @@ pub mod triper {
 
     /// Queue a confidential trip matching computation
     /// Encrypted data is sent to Arcium MPC network  
+    /// Each ciphertext is passed with its own key and encryption nonce
+    /// Clients should prepend a compute-budget limit of at least the
+    /// recommended_compute_units from estimate_match_args: full 20-waypoint
+    /// routes exceed the default per-instruction budget
+    /// Pairs the public pre-filter rules out are settled without MPC
     pub fn compute_trip_match(
         ctx: Context<ComputeTripMatch>,
         computation_offset: u64,
-        nonce: u128,
+        nonce_trip_a: u128,
+        nonce_trip_b: u128,
+        nonce_profile_a: u128,
+        nonce_profile_b: u128,
     ) -> Result<()> {
         ctx.accounts.sign_pda_account.bump = ctx.bumps.sign_pda_account;
         
+        // The record must be the canonical match PDA of the trips being scored
+        let (first, second) = state::canonical_pair(ctx.accounts.trip_a.key(), ctx.accounts.trip_b.key());
+        let expected_match_record = Pubkey::create_program_address(
+            &[b"match", first.as_ref(), second.as_ref(), &[ctx.accounts.match_record.bump]],
+            &crate::ID,
+        )
+        .map_err(|_| error::ErrorCode::UnauthorizedAccess)?;
+        require_keys_eq!(
+            ctx.accounts.match_record.key(),
+            expected_match_record,
+            error::ErrorCode::UnauthorizedAccess
+        );
+        // canonical_pair accepts the trips either way round, but the callback
+        // only accepts them in the record's order - swapped trips would lock
+        // the fee and escrow behind a callback that can never land
+        require!(
+            ctx.accounts.trip_a.key() == ctx.accounts.match_record.trip_a
+                && ctx.accounts.trip_b.key() == ctx.accounts.match_record.trip_b,
+            error::ErrorCode::TripOrderMismatch
+        );
+        
+        // Obvious non-matches (dates further apart than the pair's
+        // flexibility bridges, different destinations) are settled from
+        // public data: a zero-score Completed match, no fee, no escrow and
+        // no MPC. The match_record constraint already limits this to Pending
+        // or stale Completed records
+        let (trip_a, trip_b) = (&ctx.accounts.trip_a, &ctx.accounts.trip_b);
+        if prefilter::provably_incompatible(
+            trip_a.start_date,
+            trip_a.end_date,
+            &trip_a.destination_grid_hash,
+            trip_a.salt_epoch,
+            trip_b.start_date,
+            trip_b.end_date,
+            &trip_b.destination_grid_hash,
+            trip_b.salt_epoch,
+            trip_a.date_tolerance_with(trip_b),
+        ) {
+            let now = Clock::get()?.unix_timestamp;
+            let match_record = &mut ctx.accounts.match_record;
+            // As in the callback, only a first result counts as a new match
+            let was_pending = match_record.status == state::MatchStatus::Pending;
+            match_record.route_score = 0;
+            match_record.date_score = 0;
+            match_record.interest_score = 0;
+            match_record.age_score = 0;
+            match_record.budget_score = 0;
+            match_record.total_score = 0;
+            match_record.coverage_score = 0;
+            match_record.scoring_version = SCORING_VERSION;
+            match_record.is_stale = false;
+            match_record.status = state::MatchStatus::Completed;
+            match_record.accept_deadline = now + ACCEPT_WINDOW_SECS;
+            
+            emit!(MatchComputationSkipped {
+                match_record: match_record.key(),
+                trip_a: match_record.trip_a,
+                trip_b: match_record.trip_b,
+                timestamp: now,
+            });
+            if was_pending {
+                let user_profile_a = &mut ctx.accounts.user_profile_a;
+                user_profile_a.total_matches = user_profile_a.total_matches.saturating_add(1);
+                let user_profile_b = &mut ctx.accounts.user_profile_b;
+                user_profile_b.total_matches = user_profile_b.total_matches.saturating_add(1);
+                
+                instructions::notify_participants(match_record.key(), trip_a, trip_b, NOTIFY_NEW_MATCH)?;
+            }
+            
+            msg!("Match record {} skipped MPC: no date overlap and different destinations", match_record.key());
+            return Ok(());
+        }
+        
+        // Charge the destination-specific (or base) match fee
+        let fee_lamports = ctx.accounts.match_fee()?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.treasury.to_account_info(),
+                },
+            ),
+            fee_lamports,
+        )?;
+        
+        emit!(MatchFeeCharged {
+            match_record: ctx.accounts.match_record.key(),
+            payer: ctx.accounts.payer.key(),
+            destination_grid_hash: ctx.accounts.trip_a.destination_grid_hash,
+            fee_lamports,
+        });
+        
+        // Lock the refundable compute escrow: the callback returns it, an
+        // aborted or timed-out computation forfeits it to the treasury
+        require!(
+            ctx.accounts.match_record.escrow_lamports == 0,
+            error::ErrorCode::EscrowAlreadyLocked
+        );
+        let escrow_expires_at = Clock::get()?.unix_timestamp + ctx.accounts.compute_escrow_timeout()?;
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: ctx.accounts.match_record.to_account_info(),
+                },
+            ),
+            COMPUTE_ESCROW_LAMPORTS,
+        )?;
+        let escrow_payer = ctx.accounts.payer.key();
+        let match_record = &mut ctx.accounts.match_record;
+        match_record.escrow_lamports = COMPUTE_ESCROW_LAMPORTS;
+        match_record.escrow_payer = escrow_payer;
+        match_record.escrow_expires_at = escrow_expires_at;
+        
         // Get encrypted data from Trip and UserProfile accounts
         let trip_a = &ctx.accounts.trip_a;
         let trip_b = &ctx.accounts.trip_b;
         let user_profile_a = &ctx.accounts.user_profile_a;
         let user_profile_b = &ctx.accounts.user_profile_b;
         
-        // Use Trip A's public key (both trips should use MXE's public key in production)
-        let pub_key = trip_a.public_key;
+        // Bound the argument count so oversized payloads fail with a clear error
+        // instead of an opaque Arcium/compute-limit failure
+        require!(
+            trip_a.encrypted_waypoints.len() / ENCRYPTED_FIELD_SIZE <= MAX_WAYPOINT_FIELDS
+                && trip_b.encrypted_waypoints.len() / ENCRYPTED_FIELD_SIZE <= MAX_WAYPOINT_FIELDS,
+            error::ErrorCode::TooManyEncryptedFields
+        );
+        // Partial trailing fields fail in push_encrypted_input below
+        require!(
+            !trip_a.encrypted_waypoints.is_empty() && !trip_b.encrypted_waypoints.is_empty(),
+            error::ErrorCode::InvalidEncryptedData
+        );
         
-        // Build arguments for Arcium MPC
+        // Each Enc<Shared, _> input carries its own x25519 key and nonce, so
+        // a route and an interest profile never have to share a key and can
+        // be revealed independently of each other
         // Order: waypoints_a, waypoints_b, interests_a, interests_b
-        let mut args = vec![
-            Argument::ArcisPubkey(pub_key),
-            Argument::PlaintextU128(nonce),
+        let segments: [(&[u8], [u8; 32], u128); 4] = [
+            (&trip_a.encrypted_waypoints, trip_a.public_key, nonce_trip_a),
+            (&trip_b.encrypted_waypoints, trip_b.public_key, nonce_trip_b),
+            (&user_profile_a.encrypted_data, user_profile_a.public_key, nonce_profile_a),
+            (&user_profile_b.encrypted_data, user_profile_b.public_key, nonce_profile_b),
         ];
         
-        // Split trip_a encrypted_waypoints into 32-byte chunks for EncryptedU8 arguments
-        for chunk in trip_a.encrypted_waypoints.chunks(32) {
-            if chunk.len() == 32 {
-                let mut field = [0u8; 32];
-                field.copy_from_slice(chunk);
-                args.push(Argument::EncryptedU8(field));
-            }
+        let mut args = Vec::new();
+        for (ciphertext, public_key, nonce) in segments {
+            utils::push_encrypted_input(&mut args, public_key, nonce, ciphertext)?;
         }
         
-        // Split trip_b encrypted_waypoints into 32-byte chunks for EncryptedU8 arguments
-        for chunk in trip_b.encrypted_waypoints.chunks(32) {
-            if chunk.len() == 32 {
-                let mut field = [0u8; 32];
-                field.copy_from_slice(chunk);
-                args.push(Argument::EncryptedU8(field));
-            }
-        }
+        // Weights recorded at initiate_match, with the interest weight
+        // scaled down when either profile's interests are stale
+        let [route_weight, date_weight, interest_weight] = ctx.accounts.match_record.weights;
+        let interest_freshness = ctx.accounts.interest_freshness(Clock::get()?.unix_timestamp)?;
+        let interest_weight = (interest_weight as u16 * interest_freshness as u16 / 100) as u8;
+        ctx.accounts.match_record.interest_freshness = interest_freshness;
+        args.push(Argument::PlaintextU8(route_weight));
+        args.push(Argument::PlaintextU8(date_weight));
+        args.push(Argument::PlaintextU8(interest_weight));
         
-        // Split user_profile_a encrypted_data (interests) into 32-byte chunks
-        for chunk in user_profile_a.encrypted_data.chunks(32) {
-            if chunk.len() == 32 {
-                let mut field = [0u8; 32];
-                field.copy_from_slice(chunk);
-                args.push(Argument::EncryptedU8(field));
-            }
+        // Optional age component: weight and the gap at which it reaches 0
+        args.push(Argument::PlaintextU8(AGE_WEIGHT));
+        args.push(Argument::PlaintextU8(MAX_AGE_GAP_YEARS));
+        
+        // Optional budget component, weighted as chosen at initiate_match
+        args.push(Argument::PlaintextU8(ctx.accounts.match_record.budget_weight));
+        
+        // Destination-only pairs score without the route component
+        args.push(Argument::PlaintextBool(trip_a.destination_only_with(trip_b)));
+        
+        // Date shift the more flexible trip allows (at most 14 days, well
+        // inside u32 seconds)
+        args.push(Argument::PlaintextU32(trip_a.date_tolerance_with(trip_b) as u32));
+        
+        // Score privacy mode and the auto-reject threshold (the threshold is
+        // checked inside MPC so encrypted mode can still set the status)
+        args.push(Argument::PlaintextBool(ctx.accounts.match_record.encrypt_scores));
+        args.push(Argument::PlaintextU8(MIN_TOTAL_SCORE));
+        
+        // Interest taxonomy rows (public) for related-interest credit
+        for row in state::InterestMatrix::rows_or_default(&ctx.accounts.interest_matrix)? {
+            args.push(Argument::PlaintextU32(row));
         }
         
-        // Split user_profile_b encrypted_data (interests) into 32-byte chunks
-        for chunk in user_profile_b.encrypted_data.chunks(32) {
-            if chunk.len() == 32 {
-                let mut field = [0u8; 32];
-                field.copy_from_slice(chunk);
-                args.push(Argument::EncryptedU8(field));
-            }
+        // Interest incompatibility rows (public) for the clash penalty
+        for row in state::IncompatibilityMatrix::rows_or_default(&ctx.accounts.incompatibility_matrix)? {
+            args.push(Argument::PlaintextU32(row));
         }
         
         // TODO: Add PUBLIC date parameters once Arcium SDK supports PlaintextI64
@@ -152,7 +369,36 @@ pub mod triper {
             computation_offset,
             args,
             None,
-            vec![ComputeTripMatchCallback::callback_ix(&[])],
+            vec![ComputeTripMatchCallback::callback_ix(&[
+                CallbackAccount {
+                    pubkey: ctx.accounts.match_record.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: trip_a.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: trip_b.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: user_profile_a.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: user_profile_b.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.payer.key(),
+                    is_writable: true,
+                },
+                CallbackAccount {
+                    pubkey: ctx.accounts.treasury.key(),
+                    is_writable: true,
+                },
+            ])],
         )?;
         
         msg!("Queued MPC computation for match record: {}", ctx.accounts.match_record.key());
@@ -171,35 +417,446 @@ pub mod triper {
         ctx: Context<ComputeTripMatchCallback>,
         output: ComputationOutputs<ComputeTripMatchOutput>,
     ) -> Result<()> {
-        let scores = match output {
-            ComputationOutputs::Success(ComputeTripMatchOutput { field_0 }) => field_0,
-            _ => return Err(error::ErrorCode::ComputationFailed.into()),
+        // Circuit/callback contract: compute_trip_match returns the 10-tuple
+        // (route, date, interest, age, budget, total, coverage,
+        // passes_threshold, scores_for_a, scores_for_b). The struct pattern below is exhaustive,
+        // so adding or removing a circuit output fails to compile here
+        // instead of silently shifting scores at runtime.
+        let (
+            route_score,
+            date_score,
+            interest_score,
+            age_score,
+            budget_score,
+            total_score,
+            coverage_score,
+            passes_threshold,
+            scores_for_a,
+            scores_for_b,
+        ) = match output {
+            ComputationOutputs::Success(ComputeTripMatchOutput {
+                field_0: ComputeTripMatchOutputStruct0 {
+                    field_0: route_score,
+                    field_1: date_score,
+                    field_2: interest_score,
+                    field_3: age_score,
+                    field_4: budget_score,
+                    field_5: total_score,
+                    field_6: coverage_score,
+                    field_7: passes_threshold,
+                    field_8: scores_for_a,
+                    field_9: scores_for_b,
+                },
+            }) => (
+                route_score,
+                date_score,
+                interest_score,
+                age_score,
+                budget_score,
+                total_score,
+                coverage_score,
+                passes_threshold,
+                scores_for_a,
+                scores_for_b,
+            ),
+            _ => {
+                // Returning an error would revert the forfeit along with
+                // everything else, so the abort is settled and logged instead
+                msg!("Match computation aborted");
+                return instructions::settle_compute_escrow(
+                    &mut ctx.accounts.match_record,
+                    &ctx.accounts.treasury.to_account_info(),
+                    false,
+                );
+            }
         };
+        
+        instructions::settle_compute_escrow(
+            &mut ctx.accounts.match_record,
+            &ctx.accounts.escrow_payer.to_account_info(),
+            true,
+        )?;
 
         let match_record = &mut ctx.accounts.match_record;
+        let scores_encrypted = match_record.encrypt_scores;
+        // Only the first result for a pending match counts as a new match;
+        // a duplicate or re-score callback must not bump profile counters
+        let was_pending = match_record.status == state::MatchStatus::Pending;
+        
+        // Results land only on the states compute_trip_match queues from.
+        // A participant may reject while the computation is in flight; that
+        // rejection (and the pending slot it released) must stand, so only
+        // the escrow is settled
+        let awaiting_scores = was_pending
+            || (match_record.status == state::MatchStatus::Completed && match_record.is_stale);
+        if !awaiting_scores {
+            msg!("Match record {} settled while computing - scores discarded", match_record.key());
+            return Ok(());
+        }
         
         // Update MatchRecord with MPC computation results
-        match_record.route_score = scores.field_0;
-        match_record.date_score = scores.field_1;
-        match_record.interest_score = scores.field_2;
-        match_record.total_score = scores.field_3;
-        match_record.status = state::MatchStatus::Completed;
+        // (all zero in encrypted mode - the circuit withholds them)
+        match_record.route_score = route_score;
+        match_record.date_score = date_score;
+        match_record.interest_score = interest_score;
+        match_record.age_score = age_score;
+        match_record.budget_score = budget_score;
+        match_record.total_score = total_score;
+        match_record.coverage_score = coverage_score;
+        match_record.scoring_version = SCORING_VERSION;
+        match_record.computation_id = ctx.accounts.computation_account.key().to_bytes();
+        match_record.is_stale = false;
+        
+        if scores_encrypted {
+            match_record.encrypted_scores_a = state::EncryptedScores {
+                nonce: scores_for_a.nonce,
+                ciphertexts: scores_for_a.ciphertexts,
+            };
+            match_record.encrypted_scores_b = state::EncryptedScores {
+                nonce: scores_for_b.nonce,
+                ciphertexts: scores_for_b.ciphertexts,
+            };
+        }
+        
+        // Low-quality matches are auto-rejected and don't consume quota.
+        // The quota unit is refunded once, on the first result only
+        if !passes_threshold {
+            match_record.status = state::MatchStatus::Rejected;
+            
+            if was_pending {
+                let trip_a = &mut ctx.accounts.trip_a;
+                trip_a.match_count = trip_a.match_count.saturating_sub(1);
+                let trip_b = &mut ctx.accounts.trip_b;
+                trip_b.match_count = trip_b.match_count.saturating_sub(1);
+            }
+            
+            // Trip A's owner initiated, so any pending slot is on profile A
+            instructions::release_pending_slot(match_record, Some(&mut ctx.accounts.user_profile_a))?;
+            
+            msg!("Total score below {} - match auto-rejected", MIN_TOTAL_SCORE);
+        } else {
+            match_record.status = state::MatchStatus::Completed;
+            match_record.accept_deadline = Clock::get()?.unix_timestamp + ACCEPT_WINDOW_SECS;
+            
+            if was_pending {
+                let user_profile_a = &mut ctx.accounts.user_profile_a;
+                user_profile_a.total_matches = user_profile_a.total_matches.saturating_add(1);
+                let user_profile_b = &mut ctx.accounts.user_profile_b;
+                user_profile_b.total_matches = user_profile_b.total_matches.saturating_add(1);
+                
+                instructions::notify_participants(
+                    match_record.key(),
+                    &ctx.accounts.trip_a,
+                    &ctx.accounts.trip_b,
+                    NOTIFY_NEW_MATCH,
+                )?;
+            }
+        }
         
         // Emit event for frontend notification
         emit!(MatchComputedEvent {
             computation_account: ctx.accounts.computation_account.key(),
-            route_score: scores.field_0,
-            date_score: scores.field_1,
-            interest_score: scores.field_2,
-            total_score: scores.field_3,
+            route_score,
+            date_score,
+            interest_score,
+            age_score,
+            budget_score,
+            total_score,
+            coverage_score,
+            scores_encrypted,
+            scoring_version: SCORING_VERSION,
+        });
+        
+        // Same result with full trip context, so indexers need no join
+        // against the match record
+        emit!(MatchComputationCompleted {
+            match_record: match_record.key(),
+            trip_a: match_record.trip_a,
+            trip_b: match_record.trip_b,
+            route_score,
+            date_score,
+            interest_score,
+            age_score,
+            budget_score,
+            total_score,
+            weights: match_record.weights,
+            interest_freshness: match_record.interest_freshness,
+            computation_id: match_record.computation_id,
+            timestamp: Clock::get()?.unix_timestamp,
         });
         
         msg!("Match computation completed via Arcium MPC");
-        msg!("Match record {} updated with scores:", match_record.key());
-        msg!("  Route: {}/100", scores.field_0);
-        msg!("  Dates: {}/100", scores.field_1);
-        msg!("  Interests: {}/100", scores.field_2);
-        msg!("  Total: {}/100", scores.field_3);
+        if scores_encrypted {
+            msg!("Match record {} updated, scores stored encrypted for participants", match_record.key());
+        } else {
+            msg!("Match record {} updated with scores:", match_record.key());
+            msg!("  Route: {}/100", route_score);
+            msg!("  Dates: {}/100", date_score);
+            msg!("  Interests: {}/100", interest_score);
+            msg!("  Age: {}/100", age_score);
+            msg!("  Budget: {}/100", budget_score);
+            msg!("  Total: {}/100", total_score);
+            msg!("  Coverage: {}/100", coverage_score);
+        }
+
+        Ok(())
+    }
+
+    /// Initialize the computation definition for encryption self-checks
+    pub fn init_verify_trip_encryption_comp_def(
+        ctx: Context<InitVerifyTripEncryptionCompDef>
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Queue a self-check that the trip ciphertext decrypts correctly
+    /// Reveals only a pass/fail flag
+    pub fn verify_own_encryption(
+        ctx: Context<VerifyOwnEncryption>,
+        computation_offset: u64,
+        nonce: u128,
+    ) -> Result<()> {
+        instructions::verify_own_encryption_handler(ctx, computation_offset, nonce)
+    }
+
+    /// Callback handler - receives the self-check result from MPC network
+    #[arcium_callback(encrypted_ix = "verify_trip_encryption")]
+    pub fn verify_trip_encryption_callback(
+        ctx: Context<VerifyTripEncryptionCallback>,
+        output: ComputationOutputs<VerifyTripEncryptionOutput>,
+    ) -> Result<()> {
+        let is_valid = match output {
+            ComputationOutputs::Success(VerifyTripEncryptionOutput { field_0 }) => field_0,
+            _ => return Err(error::ErrorCode::ComputationFailed.into()),
+        };
+
+        emit!(SelfVerificationResult {
+            trip: ctx.accounts.trip.key(),
+            computation_account: ctx.accounts.computation_account.key(),
+            is_valid,
+        });
+
+        msg!("Encryption self-check for trip {}: {}", ctx.accounts.trip.key(), if is_valid { "valid" } else { "invalid" });
+
+        Ok(())
+    }
+
+    /// Initialize the computation definition for coordination reveals
+    pub fn init_reveal_coordination_comp_def(
+        ctx: Context<InitRevealCoordinationCompDef>
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Queue a coordination reveal for a mutual match
+    /// Only shared cells are output, re-encrypted to each participant
+    /// Both participants must have called submit_reveal_key first
+    pub fn reveal_coordination(
+        ctx: Context<RevealCoordination>,
+        computation_offset: u64,
+        nonce_a: u128,
+        nonce_b: u128,
+    ) -> Result<()> {
+        instructions::reveal_coordination_handler(ctx, computation_offset, nonce_a, nonce_b)
+    }
+
+    /// Callback handler - publishes the per-party encrypted coordination payloads
+    #[arcium_callback(encrypted_ix = "reveal_coordination")]
+    pub fn reveal_coordination_callback(
+        ctx: Context<RevealCoordinationCallback>,
+        output: ComputationOutputs<RevealCoordinationOutput>,
+    ) -> Result<()> {
+        let payloads = match output {
+            ComputationOutputs::Success(RevealCoordinationOutput { field_0 }) => field_0,
+            _ => return Err(error::ErrorCode::ComputationFailed.into()),
+        };
+
+        let trip_a = &ctx.accounts.trip_a;
+        let trip_b = &ctx.accounts.trip_b;
+        let (window_start, window_end) = prefilter::overlap_window(
+            trip_a.start_date,
+            trip_a.end_date,
+            trip_b.start_date,
+            trip_b.end_date,
+        ).unwrap_or((0, 0));
+
+        ctx.accounts.match_record.revealed = true;
+
+        emit!(CoordinationRevealed {
+            match_record: ctx.accounts.match_record.key(),
+            nonce_a: payloads.field_0.nonce,
+            ciphertexts_a: payloads.field_0.ciphertexts.to_vec(),
+            nonce_b: payloads.field_1.nonce,
+            ciphertexts_b: payloads.field_1.ciphertexts.to_vec(),
+            window_start,
+            window_end,
+        });
+
+        msg!("Coordination payload ready for match {}", ctx.accounts.match_record.key());
+
+        Ok(())
+    }
+
+    /// Initialize the computation definition for shared-interest reveals
+    pub fn init_reveal_shared_interests_comp_def(
+        ctx: Context<InitRevealSharedInterestsCompDef>
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Queue a shared-interests reveal for a mutual match
+    /// Only the intersection of both interest sets is revealed
+    /// Both participants must have called submit_reveal_key first
+    pub fn reveal_shared_interests(
+        ctx: Context<RevealSharedInterests>,
+        computation_offset: u64,
+        nonce_a: u128,
+        nonce_b: u128,
+    ) -> Result<()> {
+        instructions::reveal_shared_interests_handler(ctx, computation_offset, nonce_a, nonce_b)
+    }
+
+    /// Callback handler - publishes the shared-interest bitmask
+    #[arcium_callback(encrypted_ix = "reveal_shared_interests")]
+    pub fn reveal_shared_interests_callback(
+        ctx: Context<RevealSharedInterestsCallback>,
+        output: ComputationOutputs<RevealSharedInterestsOutput>,
+    ) -> Result<()> {
+        let shared_mask = match output {
+            ComputationOutputs::Success(RevealSharedInterestsOutput { field_0 }) => field_0,
+            _ => return Err(error::ErrorCode::ComputationFailed.into()),
+        };
+
+        ctx.accounts.match_record.revealed = true;
+
+        emit!(SharedInterestsRevealed {
+            match_record: ctx.accounts.match_record.key(),
+            shared_mask,
+        });
+
+        msg!("Shared interests for match {}: {:#034b}", ctx.accounts.match_record.key(), shared_mask);
+
+        Ok(())
+    }
+
+    /// Initialize the computation definition for self-match health checks
+    pub fn init_self_match_test_comp_def(
+        ctx: Context<InitSelfMatchTestCompDef>
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Queue a self-match of one trip against itself (admin only)
+    /// Monitors the MPC pipeline end-to-end; no match state is touched
+    pub fn self_match_test(
+        ctx: Context<SelfMatchTest>,
+        computation_offset: u64,
+        nonce_trip: u128,
+        nonce_profile: u128,
+    ) -> Result<()> {
+        instructions::self_match_test_handler(ctx, computation_offset, nonce_trip, nonce_profile)
+    }
+
+    /// Callback handler - checks the self-match against expected bounds
+    #[arcium_callback(encrypted_ix = "self_match_test")]
+    pub fn self_match_test_callback(
+        ctx: Context<SelfMatchTestCallback>,
+        output: ComputationOutputs<SelfMatchTestOutput>,
+    ) -> Result<()> {
+        let (route_score, date_score, interest_score, total_score, coverage_score) = match output {
+            ComputationOutputs::Success(SelfMatchTestOutput {
+                field_0: SelfMatchTestOutputStruct0 {
+                    field_0: route_score,
+                    field_1: date_score,
+                    field_2: interest_score,
+                    field_3: total_score,
+                    field_4: coverage_score,
+                },
+            }) => (route_score, date_score, interest_score, total_score, coverage_score),
+            _ => return Err(error::ErrorCode::ComputationFailed.into()),
+        };
+
+        let healthy = instructions::self_match_is_healthy(route_score, coverage_score, total_score);
+
+        emit!(SelfMatchHealth {
+            trip: ctx.accounts.trip.key(),
+            route_score,
+            date_score,
+            interest_score,
+            total_score,
+            coverage_score,
+            healthy,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Self-match for trip {}: healthy = {}", ctx.accounts.trip.key(), healthy);
+
+        Ok(())
+    }
+
+    /// Initialize the computation definition for threshold-only match checks
+    pub fn init_check_match_threshold_comp_def(
+        ctx: Context<InitCheckMatchThresholdCompDef>
+    ) -> Result<()> {
+        init_comp_def(ctx.accounts, true, 0, None, None)?;
+        Ok(())
+    }
+
+    /// Queue a check of whether a pending match scores at least `threshold`
+    /// Only the pass/fail bit is revealed - component scores stay encrypted
+    pub fn check_match_threshold(
+        ctx: Context<CheckMatchThreshold>,
+        computation_offset: u64,
+        threshold: u8,
+        nonce_trip_a: u128,
+        nonce_trip_b: u128,
+        nonce_profile_a: u128,
+        nonce_profile_b: u128,
+    ) -> Result<()> {
+        instructions::check_match_threshold_handler(
+            ctx,
+            computation_offset,
+            threshold,
+            nonce_trip_a,
+            nonce_trip_b,
+            nonce_profile_a,
+            nonce_profile_b,
+        )
+    }
+
+    /// Callback handler - stores the threshold result on the match
+    #[arcium_callback(encrypted_ix = "check_match_threshold")]
+    pub fn check_match_threshold_callback(
+        ctx: Context<CheckMatchThresholdCallback>,
+        output: ComputationOutputs<CheckMatchThresholdOutput>,
+    ) -> Result<()> {
+        let passed = match output {
+            ComputationOutputs::Success(CheckMatchThresholdOutput { field_0 }) => field_0,
+            _ => return Err(error::ErrorCode::ComputationFailed.into()),
+        };
+
+        // The threshold isn't part of the output - the queueing handler
+        // staged it on the record
+        let match_record = &mut ctx.accounts.match_record;
+        match_record.threshold_checked = true;
+        match_record.threshold_passed = passed;
+
+        emit!(MatchThresholdChecked {
+            match_record: match_record.key(),
+            threshold: match_record.score_threshold,
+            passed,
+        });
+
+        msg!(
+            "Match {} threshold {}: passed = {}",
+            match_record.key(),
+            match_record.score_threshold,
+            passed
+        );
 
         Ok(())
     }
@@ -209,8 +866,381 @@ pub mod triper {
         instructions::deactivate_trip_handler(ctx)
     }
 
+    /// Deactivate a trip, succeeding if it is already inactive
+    pub fn deactivate_trip_idempotent(ctx: Context<DeactivateTripIdempotent>) -> Result<()> {
+        instructions::deactivate_trip_idempotent_handler(ctx)
+    }
+
+    /// Rotate a trip's encryption key with client re-encrypted waypoints
+    /// Match records passed as remaining accounts are marked stale
+    pub fn rotate_trip_key<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RotateTripKey<'info>>,
+        encrypted_waypoints: Vec<u8>,
+        public_key: [u8; 32],
+    ) -> Result<()> {
+        instructions::rotate_trip_key_handler(ctx, encrypted_waypoints, public_key)
+    }
+
+    /// Pay for a time-limited ranking boost on a trip
+    pub fn boost_trip(ctx: Context<BoostTrip>, days: u16) -> Result<()> {
+        instructions::boost_trip_handler(ctx, days)
+    }
+
+    /// Publish (Some) or withdraw (None) an interest bitmask for match previews
+    pub fn set_public_interests(
+        ctx: Context<SetPublicInterests>,
+        interests: Option<u32>,
+    ) -> Result<()> {
+        instructions::set_public_interests_handler(ctx, interests)
+    }
+
+    /// Quick-look partial score from public dates and published interests
+    /// No MPC: route_score is always None and the result is stored in a
+    /// MatchPreview, never in the MatchRecord
+    pub fn preview_match(ctx: Context<PreviewMatch>) -> Result<()> {
+        instructions::preview_match_handler(ctx)
+    }
+
+    /// Emit up to `limit` trips of one destination index shard from `offset`
+    /// (read-only; clients simulate it and page through shards in order)
+    pub fn read_destination_shard(
+        ctx: Context<ReadDestinationShard>,
+        destination_grid_hash: [u8; 32],
+        shard_id: u16,
+        offset: u16,
+        limit: u8,
+    ) -> Result<()> {
+        instructions::read_destination_shard_handler(ctx, destination_grid_hash, shard_id, offset, limit)
+    }
+
+    /// Emit a trip's public metadata as a TripDescribed event
+    /// (read-only; clients simulate it instead of decoding the account)
+    pub fn describe_trip(ctx: Context<DescribeTrip>) -> Result<()> {
+        instructions::describe_trip_handler(ctx)
+    }
+
+    /// Commit SHA256(seed) for a match suggestion drawn from destination
+    /// shard `shard_id`; the seed is revealed in a later slot
+    pub fn commit_suggestion_seed(
+        ctx: Context<CommitSuggestionSeed>,
+        commitment: [u8; 32],
+        shard_id: u16,
+    ) -> Result<()> {
+        instructions::commit_suggestion_seed_handler(ctx, commitment, shard_id)
+    }
+
+    /// Reveal the committed seed and draw a suggested trip from the shard,
+    /// using the seed and the commit slot's hash as randomness
+    pub fn reveal_and_suggest<'info>(
+        ctx: Context<'_, '_, 'info, 'info, RevealAndSuggest<'info>>,
+        seed: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_and_suggest_handler(ctx, seed)
+    }
+
+    /// Estimate compatibility from public trip data only (read-only, no MPC)
+    pub fn estimate_compatibility(ctx: Context<EstimateCompatibility>) -> Result<()> {
+        instructions::estimate_compatibility_handler(ctx)
+    }
+
+    /// Estimate argument count and compute units for compute_trip_match
+    /// (read-only; clients simulate it and set a compute-budget limit)
+    pub fn estimate_match_args(ctx: Context<EstimateMatchArgs>) -> Result<()> {
+        instructions::estimate_match_args_handler(ctx)
+    }
+
+    /// Emit a user's on-chain footprint for data portability (read-only)
+    /// Trips and match records are passed as remaining accounts
+    pub fn export_user_data<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExportUserData<'info>>,
+    ) -> Result<()> {
+        instructions::export_user_data_handler(ctx)
+    }
+
+    /// Split a combined waypoints+interests payload with the canonical framing
+    /// (read-only; emits both halves)
+    pub fn split_encrypted_payload(
+        ctx: Context<SplitEncryptedPayload>,
+        payload: Vec<u8>,
+    ) -> Result<()> {
+        instructions::split_encrypted_payload_handler(ctx, payload)
+    }
+
+    /// Initialize the global program config (signer becomes admin)
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        verifier: Pubkey,
+    ) -> Result<()> {
+        instructions::initialize_config_handler(ctx, verifier)
+    }
+
+    /// Set the trip's traveler type and the counterparty type it is looking for
+    pub fn set_trip_type(
+        ctx: Context<SetTripType>,
+        traveler_type: u8,
+        seeking_type: u8,
+    ) -> Result<()> {
+        instructions::set_trip_type_handler(ctx, traveler_type, seeking_type)
+    }
+
+    /// Set how flexible the trip's dates are (0 = rigid, 100 = very flexible)
+    /// Date scoring lets the more flexible trip of a pair shift by up to
+    /// its tolerance, so near-miss dates still score
+    pub fn set_trip_flexibility(
+        ctx: Context<SetTripFlexibility>,
+        flexibility: u8,
+    ) -> Result<()> {
+        instructions::set_trip_flexibility_handler(ctx, flexibility)
+    }
+
+    /// Choose which match events the trip owner is alerted about
+    /// (NOTIFY_* bitfield; events are still emitted with notify = false)
+    pub fn set_trip_notifications(
+        ctx: Context<SetTripNotifications>,
+        notify_on: u8,
+    ) -> Result<()> {
+        instructions::set_trip_notifications_handler(ctx, notify_on)
+    }
+
+    /// Match on destination + dates alone (applies when both trips opt in
+    /// and share a destination; route_score is then reported as neutral)
+    pub fn set_trip_destination_only(
+        ctx: Context<SetTripDestinationOnly>,
+        destination_only_match: bool,
+    ) -> Result<()> {
+        instructions::set_trip_destination_only_handler(ctx, destination_only_match)
+    }
+
+    /// Stop `blocked` from starting matches with the caller's trips (and
+    /// the caller from starting matches with theirs)
+    /// Creates the caller's blocklist on first use
+    pub fn block_user(ctx: Context<BlockUser>, blocked: Pubkey) -> Result<()> {
+        instructions::block_user_handler(ctx, blocked)
+    }
+
+    /// Remove `blocked` from the caller's blocklist
+    pub fn unblock_user(ctx: Context<UnblockUser>, blocked: Pubkey) -> Result<()> {
+        instructions::unblock_user_handler(ctx, blocked)
+    }
+
+    /// Create the caller's payload registry so create_trip can reject
+    /// waypoint ciphertext reused across their trips
+    pub fn init_payload_registry(ctx: Context<InitPayloadRegistry>) -> Result<()> {
+        instructions::init_payload_registry_handler(ctx)
+    }
+
+    /// Correct an active trip's dates and route without recreating it
+    /// Resets the trip's match quota since its route changed
+    pub fn update_trip(
+        ctx: Context<UpdateTrip>,
+        start_date: i64,
+        end_date: i64,
+        encrypted_waypoints: Vec<u8>,
+        public_key: [u8; 32],
+    ) -> Result<()> {
+        instructions::update_trip_handler(ctx, start_date, end_date, encrypted_waypoints, public_key)
+    }
+
+    /// Replace the trip's destination hash with one salted under the current
+    /// epoch, so it compares against trips created after a salt rotation
+    pub fn rehash_trip_destination(
+        ctx: Context<RehashTripDestination>,
+        destination_grid_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::rehash_trip_destination_handler(ctx, destination_grid_hash)
+    }
+
+    /// Publish the trip's route density from its waypoint count and
+    /// bounding-box area bucket (computed client-side from the plaintext route)
+    pub fn set_route_density(
+        ctx: Context<SetRouteDensity>,
+        waypoint_count: u8,
+        area_bucket: u8,
+    ) -> Result<()> {
+        instructions::set_route_density_handler(ctx, waypoint_count, area_bucket)
+    }
+
+    /// Set how long a user must be inactive before their trips can be swept (admin only)
+    pub fn set_inactivity_period(
+        ctx: Context<SetInactivityPeriod>,
+        inactivity_period_secs: i64,
+    ) -> Result<()> {
+        instructions::set_inactivity_period_handler(ctx, inactivity_period_secs)
+    }
+
+    /// Set the interest half-life used to down-weight stale interests (admin only)
+    /// 0 disables decay
+    pub fn set_interest_decay(
+        ctx: Context<SetInterestDecay>,
+        interest_half_life_secs: i64,
+    ) -> Result<()> {
+        instructions::set_interest_decay_handler(ctx, interest_half_life_secs)
+    }
+
+    /// Set how long a compute escrow waits for its callback (admin only)
+    pub fn set_compute_escrow_timeout(
+        ctx: Context<SetComputeEscrowTimeout>,
+        compute_escrow_timeout_secs: i64,
+    ) -> Result<()> {
+        instructions::set_compute_escrow_timeout_handler(ctx, compute_escrow_timeout_secs)
+    }
+
+    /// Cap how many unresolved matches one user may initiate (admin only)
+    /// 0 removes the cap
+    pub fn set_max_pending_matches(
+        ctx: Context<SetMaxPendingMatches>,
+        max_pending_matches: u16,
+    ) -> Result<()> {
+        instructions::set_max_pending_matches_handler(ctx, max_pending_matches)
+    }
+
+    /// Set how long a trip must exist before it can be matched (0 = no cooling-off)
+    pub fn set_min_trip_age(
+        ctx: Context<SetMinTripAge>,
+        min_trip_age_secs: i64,
+    ) -> Result<()> {
+        instructions::set_min_trip_age_handler(ctx, min_trip_age_secs)
+    }
+
+    /// Cap the route bounding-box area create_trip accepts, as an area
+    /// bucket (admin only). 0 removes the cap
+    pub fn set_max_route_area(
+        ctx: Context<SetMaxRouteArea>,
+        max_route_area_bucket: u8,
+    ) -> Result<()> {
+        instructions::set_max_route_area_handler(ctx, max_route_area_bucket)
+    }
+
+    /// Cap the lifetime number of matches initiated between the same two
+    /// owners across all their trips (admin only). 0 removes the cap
+    pub fn set_max_owner_pair_matches(
+        ctx: Context<SetMaxOwnerPairMatches>,
+        max_owner_pair_matches: u16,
+    ) -> Result<()> {
+        instructions::set_max_owner_pair_matches_handler(ctx, max_owner_pair_matches)
+    }
+
+    /// Start a new destination salt epoch (admin only)
+    /// Only the SHA256 commitment of the new salt is stored on-chain
+    pub fn rotate_destination_salt(
+        ctx: Context<RotateDestinationSalt>,
+        salt_commitment: [u8; 32],
+    ) -> Result<()> {
+        instructions::rotate_destination_salt_handler(ctx, salt_commitment)
+    }
+
+    /// Forfeit a timed-out compute escrow to the treasury (permissionless crank)
+    pub fn forfeit_compute_escrow(ctx: Context<ForfeitComputeEscrow>) -> Result<()> {
+        instructions::forfeit_compute_escrow_handler(ctx)
+    }
+
+    /// Deactivate an inactive user's trips (permissionless crank)
+    /// Trips are passed as writable remaining accounts
+    pub fn sweep_inactive_user<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SweepInactiveUser<'info>>,
+    ) -> Result<()> {
+        instructions::sweep_inactive_user_handler(ctx)
+    }
+
+    /// Set a user's verification tier (verifier only)
+    pub fn set_verification_tier(
+        ctx: Context<SetVerificationTier>,
+        tier: u8,
+    ) -> Result<()> {
+        instructions::set_verification_tier_handler(ctx, tier)
+    }
+
+    /// Set the minimum verification tier a trip requires of counterparties
+    pub fn set_min_counterparty_tier(
+        ctx: Context<SetMinCounterpartyTier>,
+        min_tier: u8,
+    ) -> Result<()> {
+        instructions::set_min_counterparty_tier_handler(ctx, min_tier)
+    }
+
+    /// Expire a completed match past its acceptance deadline, or a pending
+    /// match past expires_at (permissionless)
+    pub fn expire_match(ctx: Context<ExpireMatch>) -> Result<()> {
+        instructions::expire_match_handler(ctx)
+    }
+
+    /// Override the match fee for a destination (admin only)
+    pub fn set_destination_fee(
+        ctx: Context<SetDestinationFee>,
+        destination_grid_hash: [u8; 32],
+        fee_lamports: u64,
+    ) -> Result<()> {
+        instructions::set_destination_fee_handler(ctx, destination_grid_hash, fee_lamports)
+    }
+
+    /// Set the default scoring profile for a destination (admin only)
+    /// `default_profile` is a ScoringProfile index
+    pub fn set_destination_profile(
+        ctx: Context<SetDestinationProfile>,
+        destination_grid_hash: [u8; 32],
+        default_profile: u8,
+    ) -> Result<()> {
+        instructions::set_destination_profile_handler(ctx, destination_grid_hash, default_profile)
+    }
+
+    /// Set which interests are related to `interest` (admin only)
+    /// Creates the InterestMatrix on first use; the matrix stays symmetric
+    pub fn set_interest_relatedness(
+        ctx: Context<SetInterestRelatedness>,
+        interest: u8,
+        related_mask: u32,
+    ) -> Result<()> {
+        instructions::set_interest_relatedness_handler(ctx, interest, related_mask)
+    }
+
+    /// Set which interests clash with `interest` (admin only)
+    /// Creates the IncompatibilityMatrix on first use; the matrix stays symmetric
+    pub fn set_interest_incompatibility(
+        ctx: Context<SetInterestIncompatibility>,
+        interest: u8,
+        incompatible_mask: u32,
+    ) -> Result<()> {
+        instructions::set_interest_incompatibility_handler(ctx, interest, incompatible_mask)
+    }
+
+    /// Cancel a match that is still Pending; refunds rent and quota
+    pub fn cancel_pending_match(ctx: Context<CancelPendingMatch>) -> Result<()> {
+        instructions::cancel_pending_match_handler(ctx)
+    }
+
+    /// Close a finished (rejected or expired) match; refunds rent
+    /// A rejection is carried into the pair history for the rematch cooldown
+    pub fn close_match(ctx: Context<CloseMatch>) -> Result<()> {
+        instructions::close_match_handler(ctx)
+    }
+
+    /// Close a mutual match once both owners sign off; rent goes to its funder
+    pub fn close_mutual_match(ctx: Context<CloseMutualMatch>) -> Result<()> {
+        instructions::close_mutual_match_handler(ctx)
+    }
+
+    /// Submit this participant's reveal key for a mutual match
+    /// Coordination and shared-interest reveals need both participants' keys
+    pub fn submit_reveal_key(
+        ctx: Context<SubmitRevealKey>,
+        reveal_key: [u8; 32],
+    ) -> Result<()> {
+        instructions::submit_reveal_key_handler(ctx, reveal_key)
+    }
+
+    /// Confirm this participant fetched the revealed data (safety audit trail)
+    pub fn acknowledge_reveal(ctx: Context<AcknowledgeReveal>) -> Result<()> {
+        instructions::acknowledge_reveal_handler(ctx)
+    }
+
     /// Reject a match
+    /// Counts against the other owner's reputation (when their profile is passed)
     pub fn reject_match(ctx: Context<RejectMatch>) -> Result<()> {
         instructions::reject_match_handler(ctx)
     }
+
+    /// Undo a rejection within the grace window (rejecting user only)
+    pub fn undo_reject(ctx: Context<UndoReject>) -> Result<()> {
+        instructions::undo_reject_handler(ctx)
+    }
 }
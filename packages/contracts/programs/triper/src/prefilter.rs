@@ -0,0 +1,240 @@
+// Public pre-filter helpers
+// Operate only on PUBLIC trip data (dates, destination hash) - never on ciphertexts
+
+use anchor_lang::prelude::*;
+use crate::constants::MAX_FLEXIBILITY_TOLERANCE_DAYS;
+use crate::error::ErrorCode;
+use crate::scoring::clamp_pct;
+
+pub const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+pub const SECONDS_PER_WEEK: i64 = 7 * SECONDS_PER_DAY;
+
+/// Week-of-year buckets per year
+/// Days 364-365 fold into the last week, so every year has exactly 52
+pub const WEEKS_PER_YEAR: u8 = 52;
+
+/// Earliest timestamp accepted for bucketing (1900-01-01T00:00:00Z)
+pub const MIN_BUCKET_TIMESTAMP: i64 = -2_208_988_800;
+
+/// Latest timestamp accepted for bucketing (2200-01-01T00:00:00Z)
+pub const MAX_BUCKET_TIMESTAMP: i64 = 7_258_118_400;
+
+/// Map a Unix timestamp to a week bucket (weeks since epoch)
+///
+/// Buckets are unsigned, so every pre-1970 timestamp (1900-1969) is clamped
+/// into bucket 0 and shares it with the first week of 1970: bucket
+/// distances are only meaningful from 1970 on. Floor division keeps the
+/// weeks just before the epoch from rounding toward zero ahead of that
+/// clamp. Timestamps outside [1900, 2200) are rejected.
+pub fn date_to_week_bucket(ts: i64) -> Result<u32> {
+    require!(
+        (MIN_BUCKET_TIMESTAMP..MAX_BUCKET_TIMESTAMP).contains(&ts),
+        ErrorCode::InvalidTimestamp
+    );
+
+    let week = ts.div_euclid(SECONDS_PER_WEEK);
+    Ok(week.max(0) as u32)
+}
+
+/// Week of the calendar year (1-52, UTC) for a timestamp
+///
+/// Seasonal bucket: unlike date_to_week_bucket it repeats every year, so
+/// week 52 and week 1 are neighbours. Compare buckets only with
+/// week_of_year_distance, never by plain subtraction.
+pub fn week_of_year(ts: i64) -> Result<u8> {
+    require!(
+        (MIN_BUCKET_TIMESTAMP..MAX_BUCKET_TIMESTAMP).contains(&ts),
+        ErrorCode::InvalidTimestamp
+    );
+
+    let days = ts.div_euclid(SECONDS_PER_DAY);
+    let day_of_year = days - days_from_civil(civil_year(days), 1, 1);
+    Ok(((day_of_year / 7) as u8).min(WEEKS_PER_YEAR - 1) + 1)
+}
+
+/// Distance between two week-of-year buckets, wrapping around the year
+/// Week 52 and week 1 are 1 apart, not 51; the result is 0-26
+pub fn week_of_year_distance(a: u8, b: u8) -> u8 {
+    let distance = a.abs_diff(b) % WEEKS_PER_YEAR;
+    distance.min(WEEKS_PER_YEAR - distance)
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_from_march = (month + 9) % 12;
+    let day_of_year = (153 * month_from_march + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Calendar year containing a day count since 1970-01-01
+fn civil_year(days: i64) -> i64 {
+    let shifted = days + 719_468;
+    let era = shifted.div_euclid(146_097);
+    let day_of_era = shifted - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_from_march = (5 * day_of_year + 2) / 153;
+    // Years above start in March; January and February belong to the next one
+    let year = year_of_era + era * 400;
+    if month_from_march >= 10 { year + 1 } else { year }
+}
+
+/// Intersection of two date ranges, if any
+/// Used as the suggested meeting window for mutual matches
+pub fn overlap_window(start_a: i64, end_a: i64, start_b: i64, end_b: i64) -> Option<(i64, i64)> {
+    let start = start_a.max(start_b);
+    let end = end_a.min(end_b);
+    if end >= start {
+        Some((start, end))
+    } else {
+        None
+    }
+}
+
+/// Seconds between two date ranges (0 when they overlap or touch)
+pub fn date_gap_secs(start_a: i64, end_a: i64, start_b: i64, end_b: i64) -> i64 {
+    (start_a.max(start_b) - end_a.min(end_b)).max(0)
+}
+
+/// Whether two destination hashes name the same area
+/// None when they were salted under different epochs: such hashes differ
+/// even for the same destination, so the comparison is unknown rather than
+/// false until the older trip is rehashed (rehash_trip_destination)
+pub fn same_destination(
+    destination_a: &[u8; 32],
+    salt_epoch_a: u32,
+    destination_b: &[u8; 32],
+    salt_epoch_b: u32,
+) -> Option<bool> {
+    (salt_epoch_a == salt_epoch_b).then(|| destination_a == destination_b)
+}
+
+/// Whether public data alone rules a pair out: the dates sit further apart
+/// than the pair's flexibility bridges (so the date score is 0) and the
+/// destinations provably differ. Trips carry no search radius, so the
+/// exact-match destination hash is the only location signal available;
+/// hashes from different salt epochs never rule a pair out
+#[allow(clippy::too_many_arguments)]
+pub fn provably_incompatible(
+    start_a: i64,
+    end_a: i64,
+    destination_a: &[u8; 32],
+    salt_epoch_a: u32,
+    start_b: i64,
+    end_b: i64,
+    destination_b: &[u8; 32],
+    salt_epoch_b: u32,
+    date_tolerance_secs: i64,
+) -> bool {
+    date_gap_secs(start_a, end_a, start_b, end_b) > date_tolerance_secs
+        && same_destination(destination_a, salt_epoch_a, destination_b, salt_epoch_b) == Some(false)
+}
+
+/// Date shift a trip of the given flexibility tolerates
+/// Scoring treats the more flexible trip of a pair as movable by up to this
+/// much (see date_overlap_percent): 0 (rigid) scores the dates as given;
+/// each point adds 1% of MAX_FLEXIBILITY_TOLERANCE_DAYS, so 100 bridges a
+/// 14-day gap. The destination hash is an exact-match bucket with no notion
+/// of distance, so flexibility only widens the date tolerance
+pub fn flexibility_tolerance_secs(flexibility: u8) -> i64 {
+    MAX_FLEXIBILITY_TOLERANCE_DAYS * SECONDS_PER_DAY * flexibility.min(100) as i64 / 100
+}
+
+/// Date overlap as a percentage of the average trip duration (0-100)
+/// Same formula as compute_date_overlap in the circuit, so the estimate
+/// equals the date_score the MPC computation would produce: the average is
+/// kept exact (overlap * 200 / duration sum), a zero-length overlap scores 0,
+/// and two zero-duration trips score 100 only on the same instant
+/// `tolerance_secs` lets one trip shift toward the other first: it is added
+/// to the signed overlap (a gap is a negative overlap), capped at the
+/// shorter trip, and instants match within it
+pub fn date_overlap_percent(start_a: i64, end_a: i64, start_b: i64, end_b: i64, tolerance_secs: i64) -> u8 {
+    let (duration_a, duration_b) = (end_a - start_a, end_b - start_b);
+    let signed_overlap = end_a.min(end_b) - start_a.max(start_b);
+    let overlap = (signed_overlap + tolerance_secs).clamp(0, duration_a.min(duration_b));
+    
+    let duration_sum = duration_a + duration_b;
+    if duration_sum == 0 {
+        return if (start_a - start_b).abs() <= tolerance_secs { 100 } else { 0 };
+    }
+    
+    clamp_pct((overlap * 200 / duration_sum).clamp(0, u32::MAX as i64) as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn week_bucket_starts_at_the_epoch() {
+        assert_eq!(date_to_week_bucket(0).unwrap(), 0);
+        assert_eq!(date_to_week_bucket(SECONDS_PER_WEEK - 1).unwrap(), 0);
+        assert_eq!(date_to_week_bucket(SECONDS_PER_WEEK).unwrap(), 1);
+    }
+    
+    #[test]
+    fn week_bucket_counts_whole_weeks_for_positive_dates() {
+        // 2024-01-01T00:00:00Z is 2817 weeks and 4 days after the epoch
+        assert_eq!(date_to_week_bucket(1_704_067_200).unwrap(), 2817);
+        assert_eq!(date_to_week_bucket(MAX_BUCKET_TIMESTAMP - 1).unwrap(), 12_000);
+    }
+    
+    #[test]
+    fn week_bucket_clamps_negative_timestamps_to_zero() {
+        assert_eq!(date_to_week_bucket(-1).unwrap(), 0);
+        assert_eq!(date_to_week_bucket(-SECONDS_PER_WEEK - 1).unwrap(), 0);
+        assert_eq!(date_to_week_bucket(MIN_BUCKET_TIMESTAMP).unwrap(), 0);
+    }
+    
+    #[test]
+    fn week_bucket_rejects_timestamps_outside_the_range() {
+        assert!(date_to_week_bucket(MIN_BUCKET_TIMESTAMP - 1).is_err());
+        assert!(date_to_week_bucket(MAX_BUCKET_TIMESTAMP).is_err());
+    }
+    
+    #[test]
+    fn date_overlap_without_tolerance_scores_the_dates_as_given() {
+        // Week-long trips sharing 3 days: 3 * 200 / 14
+        assert_eq!(date_overlap_percent(0, 7 * SECONDS_PER_DAY, 4 * SECONDS_PER_DAY, 11 * SECONDS_PER_DAY, 0), 42);
+        // Touching and gapped ranges score 0
+        assert_eq!(date_overlap_percent(0, 7 * SECONDS_PER_DAY, 7 * SECONDS_PER_DAY, 14 * SECONDS_PER_DAY, 0), 0);
+        assert_eq!(date_overlap_percent(0, 7 * SECONDS_PER_DAY, 12 * SECONDS_PER_DAY, 19 * SECONDS_PER_DAY, 0), 0);
+        assert_eq!(date_overlap_percent(5, 5, 5, 5, 0), 100);
+        assert_eq!(date_overlap_percent(5, 5, 6, 6, 0), 0);
+    }
+    
+    #[test]
+    fn flexibility_tolerance_bridges_a_gap_and_caps_at_the_shorter_trip() {
+        let week = 7 * SECONDS_PER_DAY;
+        // Flexibility 50 = 7 days: a 5-day gap becomes a 2-day overlap
+        let tolerance = flexibility_tolerance_secs(50);
+        assert_eq!(tolerance, week);
+        assert_eq!(date_overlap_percent(0, week, 12 * SECONDS_PER_DAY, 19 * SECONDS_PER_DAY, tolerance), 28);
+        // Shifting can at best line a trip up with the other entirely
+        assert_eq!(date_overlap_percent(0, week, 3 * SECONDS_PER_DAY, 10 * SECONDS_PER_DAY, tolerance), 100);
+        assert_eq!(date_overlap_percent(0, week, 0, 14 * SECONDS_PER_DAY, tolerance), 66);
+        // Instants match anywhere within the tolerance
+        assert_eq!(date_overlap_percent(0, 0, week, week, tolerance), 100);
+        assert_eq!(date_overlap_percent(0, 0, week + 1, week + 1, tolerance), 0);
+    }
+    
+    #[test]
+    fn only_gaps_beyond_the_tolerance_are_provably_incompatible() {
+        let (here, there) = ([1u8; 32], [2u8; 32]);
+        let week = 7 * SECONDS_PER_DAY;
+        let gap = |start_b: i64, tolerance: i64| {
+            provably_incompatible(0, week, &here, 0, start_b, start_b + week, &there, 0, tolerance)
+        };
+        assert!(!gap(week, 0)); // Touching
+        assert!(gap(week + 5 * SECONDS_PER_DAY, 0));
+        assert!(!gap(week + 5 * SECONDS_PER_DAY, flexibility_tolerance_secs(50)));
+        assert!(gap(week + 8 * SECONDS_PER_DAY, flexibility_tolerance_secs(50)));
+        // A shared destination never rules a pair out
+        assert!(!provably_incompatible(0, week, &here, 0, 3 * week, 4 * week, &here, 0, 0));
+    }
+}
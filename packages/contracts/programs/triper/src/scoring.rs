@@ -0,0 +1,518 @@
+// Shared scoring helpers
+// Plaintext mirror of the rounding/clamping helpers in encrypted-ixs/src/trip_matching.rs -
+// estimates computed on-chain must round exactly like the circuit. The
+// circuit's scoring functions are mirrored here too, so host tests can
+// exercise the same arithmetic the MPC runs
+
+use crate::constants::{
+    H3_PARENT_SHIFT, INTEREST_COUNT, MAX_WAYPOINTS, PARENT_MATCH_CREDIT_PCT, RELATED_INTEREST_CREDIT_PCT,
+};
+
+/// Clamp a percentage to 0-100
+pub fn clamp_pct(v: u32) -> u8 {
+    v.min(100) as u8
+}
+
+/// |A ∩ B| / |A ∪ B| as a floored percentage (0 when the union is empty)
+/// Scaled in u64 so no u32 count can overflow; the circuit's counts are
+/// bounded by MAX_WAYPOINTS / INTEREST_COUNT and never get near u32 range
+pub fn jaccard_pct(intersection: u32, union: u32) -> u8 {
+    if union == 0 {
+        return 0;
+    }
+    (intersection as u64 * 100 / union as u64).min(100) as u8
+}
+
+/// Weighted average of (route, date, interest) scores, floored
+/// Weights need not sum to 100; an all-zero weight set scores 0
+pub fn weighted_total(route: u8, date: u8, interest: u8, weights: (u8, u8, u8)) -> u8 {
+    let (route_w, date_w, interest_w) = (weights.0 as u32, weights.1 as u32, weights.2 as u32);
+    let weight_sum = route_w + date_w + interest_w;
+    if weight_sum == 0 {
+        return 0;
+    }
+    clamp_pct(
+        (route as u32 * route_w + date as u32 * date_w + interest as u32 * interest_w) / weight_sum
+    )
+}
+
+/// Interest weight multiplier (0-100) for data last written at `updated_at`
+/// Halves once per elapsed half-life; a half-life of 0 disables decay
+pub fn interest_freshness_pct(now: i64, updated_at: i64, half_life_secs: i64) -> u8 {
+    if half_life_secs <= 0 {
+        return 100;
+    }
+    let half_lives = (now - updated_at).max(0) / half_life_secs;
+    if half_lives >= 7 {
+        0
+    } else {
+        100u8 >> half_lives
+    }
+}
+
+/// The first `count` cells of a fixed-size route
+fn route_cells(waypoints: &[u64; MAX_WAYPOINTS], count: u8) -> &[u64] {
+    &waypoints[..(count as usize).min(MAX_WAYPOINTS)]
+}
+
+/// Level-6 parent key of a level-7 H3 cell
+fn h3_parent(cell: u64) -> u64 {
+    cell >> H3_PARENT_SHIFT
+}
+
+/// Cells of A and B paired one-to-one (a multiset intersection) where
+/// `key` agrees - the circuit's count_route_intersection with key = the
+/// cell, count_parent_intersection with key = its parent
+fn count_matched(a: &[u64], b: &[u64], key: impl Fn(u64) -> u64) -> u32 {
+    let mut used = [false; MAX_WAYPOINTS];
+    let mut matched = 0;
+    for &cell_b in b {
+        if let Some(j) = (0..a.len()).find(|&j| !used[j] && key(a[j]) == key(cell_b)) {
+            used[j] = true;
+            matched += 1;
+        }
+    }
+    matched
+}
+
+/// The circuit's compute_route_similarity: Jaccard over level-6 parents,
+/// where a parent match that is also an exact level-7 match counts fully
+/// and a parent-only match counts PARENT_MATCH_CREDIT_PCT. Empty routes score 0
+pub fn route_similarity_pct(
+    waypoints_a: &[u64; MAX_WAYPOINTS],
+    count_a: u8,
+    waypoints_b: &[u64; MAX_WAYPOINTS],
+    count_b: u8,
+) -> u8 {
+    let (a, b) = (route_cells(waypoints_a, count_a), route_cells(waypoints_b, count_b));
+    if a.is_empty() || b.is_empty() {
+        return 0;
+    }
+    let exact = count_matched(a, b, |cell| cell);
+    let parent = count_matched(a, b, h3_parent);
+    // |A ∪ B| counts each parent-level match once
+    let union = (a.len() + b.len()) as u32 - parent;
+    clamp_pct((exact * 100 + (parent - exact) * PARENT_MATCH_CREDIT_PCT) / union.max(1))
+}
+
+/// The circuit's compute_route_similarity_multires: the level-7 Jaccard
+/// index blended with the level-6 parent Jaccard, the parent share being
+/// `coarse_weight_pct` (clamped to 100). Empty routes score 0
+pub fn route_similarity_multires_pct(
+    waypoints_a: &[u64; MAX_WAYPOINTS],
+    count_a: u8,
+    waypoints_b: &[u64; MAX_WAYPOINTS],
+    count_b: u8,
+    coarse_weight_pct: u8,
+) -> u8 {
+    let (a, b) = (route_cells(waypoints_a, count_a), route_cells(waypoints_b, count_b));
+    if a.is_empty() || b.is_empty() {
+        return 0;
+    }
+    let cells = (a.len() + b.len()) as u32;
+    let exact = count_matched(a, b, |cell| cell);
+    let parent = count_matched(a, b, h3_parent);
+    let fine = jaccard_pct(exact, cells - exact) as u32;
+    let coarse = jaccard_pct(parent, cells - parent) as u32;
+    let coarse_w = clamp_pct(coarse_weight_pct as u32) as u32;
+    clamp_pct((fine * (100 - coarse_w) + coarse * coarse_w) / 100)
+}
+
+/// Integer square root via `iterations` Newton steps from `seed`
+/// The circuit's isqrt_newton runs ISQRT_ITERATIONS of them
+pub fn isqrt_newton(n: u32, seed: u32, iterations: usize) -> u32 {
+    let mut x = seed;
+    for _ in 0..iterations {
+        x = (x + n / x.max(1)) / 2;
+    }
+    x
+}
+
+/// Cosine interest similarity |A ∩ B| / sqrt(|A| * |B|) from the set sizes
+/// and their overlap, as the circuit's compute_interest_cosine computes it
+/// (with `iterations` = ISQRT_ITERATIONS). Counts are of at most
+/// INTEREST_COUNT flags; an empty side scores 0
+pub fn interest_cosine_pct(count_a: u32, count_b: u32, common: u32, iterations: usize) -> u8 {
+    if count_a == 0 || count_b == 0 {
+        return 0;
+    }
+    // sqrt(|A| * |B|) scaled by 100, seeded with the arithmetic mean
+    let norm = isqrt_newton(count_a * count_b * 10000, (count_a + count_b) * 50, iterations);
+    clamp_pct((common * 10000) / norm.max(1))
+}
+
+/// Interest similarity as the circuit's compute_interest_similarity scores
+/// it, over interest bitmasks (bit i = interests[i]): Jaccard, with
+/// RELATED_INTEREST_CREDIT_PCT for each interest only one party holds that
+/// `relatedness` (the InterestMatrix rows) links to one the other holds
+/// An empty side scores 0, and compute_trip_match drops the interest weight
+pub fn interest_similarity_pct(interests_a: u32, interests_b: u32, relatedness: &[u32; INTEREST_COUNT]) -> u8 {
+    if interests_a == 0 || interests_b == 0 {
+        return 0;
+    }
+    let common = (interests_a & interests_b).count_ones();
+    let total = (interests_a | interests_b).count_ones();
+    let related = (0..INTEREST_COUNT)
+        .filter(|&i| {
+            let (in_a, in_b) = ((interests_a >> i) & 1 == 1, (interests_b >> i) & 1 == 1);
+            (in_a && !in_b && interests_b & relatedness[i] != 0)
+                || (in_b && !in_a && interests_a & relatedness[i] != 0)
+        })
+        .count() as u32;
+    clamp_pct((common * 100 + related * RELATED_INTEREST_CREDIT_PCT) / total)
+}
+
+/// Interest weight compute_trip_match keeps: the full `weight` only when
+/// both parties declared at least the stricter of the two trips'
+/// min_interests (and always at least one), else 0 so the total
+/// renormalizes over the other components
+pub fn interest_weight(interests_a: u32, interests_b: u32, min_a: u8, min_b: u8, weight: u8) -> u8 {
+    let min_interests = min_a.max(min_b).max(1) as u32;
+    if interests_a.count_ones() >= min_interests && interests_b.count_ones() >= min_interests {
+        weight
+    } else {
+        0
+    }
+}
+
+/// `waypoints`' cells that also lie on `other`, in `waypoints`' own slots
+/// (0 where not shared), and how many there are
+fn shared_cells(
+    waypoints: &[u64; MAX_WAYPOINTS],
+    count: u8,
+    other: &[u64; MAX_WAYPOINTS],
+    other_count: u8,
+) -> ([u64; MAX_WAYPOINTS], u8) {
+    let other_cells = &other[..(other_count as usize).min(MAX_WAYPOINTS)];
+    let mut shared = [0u64; MAX_WAYPOINTS];
+    let mut shared_count = 0u8;
+    for (slot, &cell) in waypoints.iter().enumerate().take(count as usize) {
+        if other_cells.contains(&cell) {
+            shared[slot] = cell;
+            shared_count += 1;
+        }
+    }
+    (shared, shared_count)
+}
+
+/// The circuit's reveal_coordination payloads: each party's shared cells in
+/// their own waypoint order, with the count, as (for A, for B)
+pub fn reveal_coordination(
+    waypoints_a: &[u64; MAX_WAYPOINTS],
+    count_a: u8,
+    waypoints_b: &[u64; MAX_WAYPOINTS],
+    count_b: u8,
+) -> (([u64; MAX_WAYPOINTS], u8), ([u64; MAX_WAYPOINTS], u8)) {
+    (
+        shared_cells(waypoints_a, count_a, waypoints_b, count_b),
+        shared_cells(waypoints_b, count_b, waypoints_a, count_a),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::ISQRT_ITERATIONS;
+    
+    /// Largest (under, over) deviation from an f64 cosine, in points, over
+    /// every pair of non-empty subsets of 32 interest flags
+    fn cosine_error_bounds(iterations: usize) -> (f64, f64) {
+        let (mut under, mut over) = (0f64, 0f64);
+        for count_a in 1..=32u32 {
+            for count_b in 1..=32u32 {
+                for common in 0..=count_a.min(count_b) {
+                    if count_a + count_b - common > 32 {
+                        continue;
+                    }
+                    let exact = 100.0 * common as f64 / ((count_a * count_b) as f64).sqrt();
+                    let error = interest_cosine_pct(count_a, count_b, common, iterations) as f64 - exact;
+                    under = under.max(-error);
+                    over = over.max(error);
+                }
+            }
+        }
+        (under, over)
+    }
+    
+    #[test]
+    fn cosine_error_shrinks_with_newton_iterations() {
+        let (under_1, over_1) = cosine_error_bounds(1);
+        let (under_2, over_2) = cosine_error_bounds(2);
+        let (under_3, over_3) = cosine_error_bounds(3);
+        
+        // Seeded from above, so every iteration count errs low
+        assert!((7.67..7.69).contains(&under_1), "1 iteration: {under_1}");
+        assert!((2.67..2.69).contains(&under_2), "2 iterations: {under_2}");
+        assert!(under_3 <= 1.0, "3 iterations: {under_3}");
+        for over in [over_1, over_2, over_3] {
+            assert!(over < 0.05, "overshoot: {over}");
+        }
+    }
+    
+    #[test]
+    fn cosine_scores_identical_and_disjoint_sets_exactly() {
+        for count in 1..=32 {
+            assert_eq!(interest_cosine_pct(count, count, count, ISQRT_ITERATIONS), 100);
+        }
+        assert_eq!(interest_cosine_pct(16, 16, 0, ISQRT_ITERATIONS), 0);
+        assert_eq!(interest_cosine_pct(0, 5, 0, ISQRT_ITERATIONS), 0);
+    }
+    
+    /// Pad a route to the circuit's fixed waypoint array
+    fn route(cells: &[u64]) -> ([u64; MAX_WAYPOINTS], u8) {
+        let mut waypoints = [0u64; MAX_WAYPOINTS];
+        waypoints[..cells.len()].copy_from_slice(cells);
+        (waypoints, cells.len() as u8)
+    }
+    
+    #[test]
+    fn coordination_reveals_only_intersecting_cells() {
+        let (a, count_a) = route(&[11, 12, 13, 14]);
+        let (b, count_b) = route(&[20, 13, 21, 11, 22]);
+        let ((shared_a, n_a), (shared_b, n_b)) = reveal_coordination(&a, count_a, &b, count_b);
+        
+        // Each side sees the shared cells in its own waypoint order
+        assert_eq!(&shared_a[..4], &[11, 0, 13, 0]);
+        assert_eq!(&shared_b[..5], &[0, 13, 0, 11, 0]);
+        assert_eq!((n_a, n_b), (2, 2));
+        // Nothing leaks from the padding either
+        assert!(shared_a[4..].iter().chain(&shared_b[5..]).all(|&cell| cell == 0));
+    }
+    
+    #[test]
+    fn coordination_reports_a_repeated_cell_at_each_of_its_own_slots() {
+        let (a, count_a) = route(&[7, 7, 8]);
+        let (b, count_b) = route(&[7, 9]);
+        let ((shared_a, n_a), (shared_b, n_b)) = reveal_coordination(&a, count_a, &b, count_b);
+        
+        // A's duplicate stays at both of its positions; B gains no copies
+        assert_eq!(&shared_a[..3], &[7, 7, 0]);
+        assert_eq!(&shared_b[..2], &[7, 0]);
+        assert_eq!((n_a, n_b), (2, 1));
+    }
+    
+    #[test]
+    fn coordination_reveals_nothing_for_disjoint_routes() {
+        let (a, count_a) = route(&[1, 2, 3]);
+        let (b, count_b) = route(&[4, 5, 6]);
+        let ((shared_a, n_a), (shared_b, n_b)) = reveal_coordination(&a, count_a, &b, count_b);
+        
+        assert_eq!(shared_a, [0; MAX_WAYPOINTS]);
+        assert_eq!(shared_b, [0; MAX_WAYPOINTS]);
+        assert_eq!((n_a, n_b), (0, 0));
+        
+        // A padding slot (0) never matches the other side's padding
+        let (short, count_short) = route(&[1]);
+        let ((_, n_short), (_, n_long)) = reveal_coordination(&short, count_short, &b, count_b);
+        assert_eq!((n_short, n_long), (0, 0));
+    }
+    
+    /// A level-7 cell under level-6 parent `parent`: `digit` is its own
+    /// resolution-7 digit, and the unused finer digits are all 0b111
+    fn cell(parent: u64, digit: u64) -> u64 {
+        (parent << H3_PARENT_SHIFT) | (digit << 24) | 0xFF_FFFF
+    }
+    
+    /// Five cells under five different parents
+    fn sample_route() -> Vec<u64> {
+        (1..=5).map(|parent| cell(parent, 0)).collect()
+    }
+    
+    /// Each cell moved to a sibling under the same parent
+    fn siblings(cells: &[u64]) -> Vec<u64> {
+        cells.iter().map(|&c| cell(c >> H3_PARENT_SHIFT, 1)).collect()
+    }
+    
+    fn multires(a: &[u64], b: &[u64], coarse_weight_pct: u8) -> u8 {
+        let ((a, count_a), (b, count_b)) = (route(a), route(b));
+        route_similarity_multires_pct(&a, count_a, &b, count_b, coarse_weight_pct)
+    }
+    
+    #[test]
+    fn multires_gives_near_miss_routes_partial_credit() {
+        let cells = sample_route();
+        let near_miss = siblings(&cells);
+        
+        // No shared level-7 cell, every level-6 parent shared
+        assert_eq!(multires(&cells, &near_miss, 0), 0);
+        assert_eq!(multires(&cells, &near_miss, 30), 30);
+        assert_eq!(multires(&cells, &near_miss, 100), 100);
+        // Weights past 100 are clamped
+        assert_eq!(multires(&cells, &near_miss, 255), 100);
+    }
+    
+    #[test]
+    fn multires_matches_single_resolution_for_identical_and_unrelated_routes() {
+        let cells = sample_route();
+        let unrelated: Vec<u64> = (10..13).map(|parent| cell(parent, 0)).collect();
+        for coarse_weight_pct in [0, 30, 100] {
+            assert_eq!(multires(&cells, &cells, coarse_weight_pct), 100);
+            assert_eq!(multires(&cells, &unrelated, coarse_weight_pct), 0);
+            assert_eq!(multires(&cells, &[], coarse_weight_pct), 0);
+        }
+        
+        // Half the cells exact, the rest near misses: fine 3/7, coarse 5/5
+        let half = [&cells[..3], &siblings(&cells[3..])].concat();
+        assert_eq!(multires(&cells, &half, 0), 42);
+        // (42 * 70 + 100 * 30) / 100
+        assert_eq!(multires(&cells, &half, 30), 59);
+    }
+    
+    #[test]
+    fn multires_never_counts_a_shared_parent_twice() {
+        // Two cells of A under one parent vs B's single cell in that parent:
+        // fine 1/2, coarse 1/2 (not 2/1)
+        let first = cell(1, 0);
+        let a = [first, cell(1, 1)];
+        assert_eq!(multires(&a, &[first], 100), 50);
+        assert_eq!(multires(&a, &[first], 30), 50);
+    }
+    
+    fn similarity(a: &[u64], b: &[u64]) -> u8 {
+        let ((a, count_a), (b, count_b)) = (route(a), route(b));
+        route_similarity_pct(&a, count_a, &b, count_b)
+    }
+    
+    #[test]
+    fn route_similarity_credits_parent_only_matches_at_half() {
+        let cells = sample_route();
+        assert_eq!(similarity(&cells, &cells), 100);
+        
+        // Every waypoint moved to a sibling cell: all parents shared, no exact cell
+        assert_eq!(similarity(&cells, &siblings(&cells)), 50);
+        
+        // One waypoint nudged across a cell boundary, the rest exact: (4 + 0.5) / 5
+        let nudged = [&siblings(&cells[..1]), &cells[1..]].concat();
+        assert_eq!(similarity(&cells, &nudged), 90);
+        
+        // A parent-only match also joins the union once: (100 + 50) / 3
+        assert_eq!(similarity(&cells[..2], &[cells[0], siblings(&cells[1..2])[0], cell(9, 0)]), 50);
+    }
+    
+    #[test]
+    fn route_similarity_keeps_unrelated_routes_at_zero_and_never_exceeds_100() {
+        let cells = sample_route();
+        let unrelated: Vec<u64> = (10..13).map(|parent| cell(parent, 0)).collect();
+        assert_eq!(similarity(&cells, &unrelated), 0);
+        assert_eq!(similarity(&cells, &[]), 0);
+        assert_eq!(similarity(&[], &[]), 0);
+        
+        // Two cells of A under one parent vs that parent's single B cell:
+        // one exact match over a union of 2
+        let (first, sibling) = (cell(1, 0), cell(1, 1));
+        assert_eq!(similarity(&[first, sibling], &[first]), 50);
+        assert_eq!(similarity(&[first, sibling], &[first, sibling]), 100);
+        assert_eq!(similarity(&[first, sibling], &[sibling, first]), 100);
+        
+        // A full route of siblings under one parent still caps at 100
+        let crowded: Vec<u64> = (0..MAX_WAYPOINTS as u64).map(|digit| cell(1, digit % 7)).collect();
+        assert_eq!(similarity(&crowded, &crowded), 100);
+    }
+    
+    #[test]
+    fn route_similarity_matches_a_repeated_exact_cell_at_most_once() {
+        // The exact intersection pairs cells one-to-one, so it never exceeds
+        // the parent intersection and the score stays 1 / 2
+        let first = cell(1, 0);
+        assert_eq!(similarity(&[first, first], &[first]), 50);
+        assert_eq!(similarity(&[first], &[first, first]), 50);
+        assert_eq!(similarity(&[first, first], &[first, first]), 100);
+    }
+    
+    #[test]
+    fn interests_score_zero_and_drop_their_weight_when_both_are_empty() {
+        let everything_related = [u32::MAX; 32];
+        assert_eq!(interest_similarity_pct(0, 0, &[0; 32]), 0);
+        assert_eq!(interest_similarity_pct(0, 0, &everything_related), 0);
+        assert_eq!(interest_weight(0, 0, 0, 0, 25), 0);
+    }
+    
+    #[test]
+    fn interests_score_zero_and_drop_their_weight_when_one_side_is_empty() {
+        let everything_related = [u32::MAX; 32];
+        for (a, b) in [(0b1011, 0), (0, 0b1011)] {
+            assert_eq!(interest_similarity_pct(a, b, &[0; 32]), 0);
+            // Related-interest credit can't stand in for a missing side
+            assert_eq!(interest_similarity_pct(a, b, &everything_related), 0);
+            assert_eq!(interest_weight(a, b, 0, 0, 25), 0);
+        }
+        // Both non-empty keeps the weight and scores plain Jaccard
+        assert_eq!(interest_weight(0b1011, 0b0011, 0, 0, 25), 25);
+        assert_eq!(interest_similarity_pct(0b1011, 0b0011, &[0; 32]), 66);
+    }
+    
+    #[test]
+    fn interest_weight_applies_the_stricter_minimum() {
+        // B's 2 interests clear A's own minimum of 1 but not a minimum of 3
+        assert_eq!(interest_weight(0b111, 0b011, 1, 0, 25), 25);
+        assert_eq!(interest_weight(0b111, 0b011, 3, 0, 25), 0);
+        assert_eq!(interest_weight(0b111, 0b011, 0, 3, 25), 0);
+        assert_eq!(interest_weight(0b111, 0b111, 3, 2, 25), 25);
+    }
+    
+    #[test]
+    fn missing_interests_renormalize_the_total_over_route_and_dates() {
+        let (route_w, date_w, interest_w) = (40, 35, 25);
+        let (route, date) = (80, 60);
+        
+        let kept = interest_weight(0b1, 0, 0, 0, interest_w);
+        let interest = interest_similarity_pct(0b1, 0, &[0; 32]);
+        let total = weighted_total(route, date, interest, (route_w, date_w, kept));
+        
+        // (80 * 40 + 60 * 35) / 75, not dragged to 53 by a 0 interest score
+        assert_eq!(total, 70);
+        assert_eq!(total, weighted_total(route, date, 0, (route_w, date_w, 0)));
+        assert_eq!(weighted_total(route, date, 0, (route_w, date_w, interest_w)), 53);
+    }
+    
+    #[test]
+    fn clamp_caps_anything_over_100() {
+        assert_eq!(clamp_pct(0), 0);
+        assert_eq!(clamp_pct(100), 100);
+        assert_eq!(clamp_pct(101), 100);
+        assert_eq!(clamp_pct(250), 100);
+        assert_eq!(clamp_pct(u32::MAX), 100);
+    }
+    
+    #[test]
+    fn jaccard_scores_an_empty_union_as_zero() {
+        assert_eq!(jaccard_pct(0, 0), 0);
+        // A (nonsensical) intersection with no union still scores 0
+        assert_eq!(jaccard_pct(5, 0), 0);
+    }
+    
+    #[test]
+    fn jaccard_does_not_overflow_on_large_counts() {
+        assert_eq!(jaccard_pct(u32::MAX, u32::MAX), 100);
+        // 2147483647 / 4294967295 = 49.99...%, floored
+        assert_eq!(jaccard_pct(u32::MAX / 2, u32::MAX), 49);
+        assert_eq!(jaccard_pct(1, u32::MAX), 0);
+    }
+    
+    #[test]
+    fn jaccard_clamps_an_intersection_larger_than_the_union() {
+        assert_eq!(jaccard_pct(5, 3), 100);
+        assert_eq!(jaccard_pct(u32::MAX, 1), 100);
+    }
+    
+    #[test]
+    fn weighted_total_normalizes_weights_that_do_not_sum_to_100() {
+        // Equal weights of any size are a plain floored average
+        assert_eq!(weighted_total(90, 60, 31, (1, 1, 1)), 60);
+        assert_eq!(weighted_total(90, 60, 31, (255, 255, 255)), 60);
+        // Sums above 100: (90 * 200 + 60 * 100) / 300 = 80
+        assert_eq!(weighted_total(90, 60, 31, (200, 100, 0)), 80);
+        // Sums below 100 scale the same as their multiples
+        assert_eq!(
+            weighted_total(90, 60, 31, (4, 3, 2)),
+            weighted_total(90, 60, 31, (40, 30, 20))
+        );
+        assert_eq!(weighted_total(90, 60, 31, (0, 0, 0)), 0);
+    }
+    
+    #[test]
+    fn weighted_total_clamps_out_of_range_scores() {
+        assert_eq!(weighted_total(255, 255, 255, (1, 1, 1)), 100);
+        assert_eq!(weighted_total(255, 0, 0, (255, 0, 0)), 100);
+    }
+}
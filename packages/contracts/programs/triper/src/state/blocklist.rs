@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::constants::MAX_BLOCKED_USERS;
+
+/// Owners a user refuses to be matched with
+/// initiate_match fails with UserBlocked when either side's blocklist
+/// names the other owner
+/// 
+/// Seeds: [b"blocklist", owner]
+#[account]
+#[derive(InitSpace)]
+pub struct Blocklist {
+    /// Owner of this blocklist
+    pub owner: Pubkey,
+    
+    /// Blocked wallets, in the order they were blocked
+    #[max_len(MAX_BLOCKED_USERS)]
+    pub blocked: Vec<Pubkey>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl Blocklist {
+    /// Whether `user` is on this blocklist
+    pub fn is_blocked(&self, user: &Pubkey) -> bool {
+        self.blocked.contains(user)
+    }
+}
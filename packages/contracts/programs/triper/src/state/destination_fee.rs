@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+/// Per-destination match fee override (admin-configured)
+/// Destinations without one are charged BASE_MATCH_FEE_LAMPORTS
+/// 
+/// Seeds: [b"destination_fee", destination_grid_hash]
+#[account]
+#[derive(InitSpace)]
+pub struct DestinationFee {
+    /// Destination grid hash this fee applies to
+    pub destination_grid_hash: [u8; 32],
+    
+    /// Fee charged per match computation (lamports)
+    pub fee_lamports: u64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
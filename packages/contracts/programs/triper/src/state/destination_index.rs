@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::constants::DESTINATION_SHARD_CAPACITY;
+
+/// One page-sized slice of a destination's trip index
+/// Shards fill in shard_id order: trips are only written to shard N once
+/// shard N - 1 is full, so the open (least-full) shard is always the last
+/// one and every earlier shard holds DESTINATION_SHARD_CAPACITY trips
+/// 
+/// Seeds: [b"dest", destination_grid_hash, shard_id (u16 LE)]
+#[account]
+#[derive(InitSpace)]
+pub struct DestinationIndexShard {
+    /// Destination grid hash this shard indexes
+    pub destination_grid_hash: [u8; 32],
+    
+    /// Position of this shard in the destination's shard sequence
+    pub shard_id: u16,
+    
+    /// Trips indexed here, in creation order
+    #[max_len(DESTINATION_SHARD_CAPACITY)]
+    pub trips: Vec<Pubkey>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl DestinationIndexShard {
+    /// Whether the shard has no room for another trip
+    pub fn is_full(&self) -> bool {
+        self.trips.len() >= DESTINATION_SHARD_CAPACITY
+    }
+    
+    /// Append a trip, stamping the shard's identity (set on first use)
+    pub fn push_trip(&mut self, destination_grid_hash: [u8; 32], shard_id: u16, bump: u8, trip: Pubkey) {
+        self.destination_grid_hash = destination_grid_hash;
+        self.shard_id = shard_id;
+        self.bump = bump;
+        self.trips.push(trip);
+    }
+}
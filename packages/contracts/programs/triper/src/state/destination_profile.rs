@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// Per-destination default scoring profile (admin-configured)
+/// initiate_match uses it when the caller doesn't pick a profile;
+/// destinations without one default to Balanced
+/// 
+/// Seeds: [b"destination_profile", destination_grid_hash]
+#[account]
+#[derive(InitSpace)]
+pub struct DestinationProfile {
+    /// Destination grid hash this default applies to
+    pub destination_grid_hash: [u8; 32],
+    
+    /// ScoringProfile index (see ScoringProfile::from_index)
+    pub default_profile: u8,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::constants::INTEREST_COUNT;
+
+/// Interest incompatibility taxonomy (admin-configured singleton)
+/// Bit j of incompatible[i] marks interests i and j as a lifestyle clash
+/// (e.g. nightlife vs sunrise hikes); the matrix is kept symmetric. Each
+/// interest one party holds that clashes with one the other party holds
+/// lowers the interest score. Without this account nothing clashes
+/// 
+/// Seeds: [b"incompatibility_matrix"]
+#[account]
+#[derive(InitSpace)]
+pub struct IncompatibilityMatrix {
+    /// Incompatibility rows, one bitmask per interest
+    pub incompatible: [u32; INTEREST_COUNT],
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl IncompatibilityMatrix {
+    /// Incompatibility rows from an unchecked [b"incompatibility_matrix"]
+    /// account, all zero (nothing clashes) while the matrix has not been created
+    pub fn rows_or_default(info: &AccountInfo) -> Result<[u32; INTEREST_COUNT]> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok([0u32; INTEREST_COUNT]);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(IncompatibilityMatrix::try_deserialize(&mut &data[..])?.incompatible)
+    }
+    
+    /// Set interest's row to incompatible_mask and mirror it into the other rows
+    /// An interest never clashes with itself
+    pub fn set_row(&mut self, interest: usize, incompatible_mask: u32) {
+        let mask = incompatible_mask & !(1u32 << interest);
+        for other in 0..INTEREST_COUNT {
+            if mask & (1u32 << other) != 0 {
+                self.incompatible[other] |= 1u32 << interest;
+            } else {
+                self.incompatible[other] &= !(1u32 << interest);
+            }
+        }
+        self.incompatible[interest] = mask;
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use crate::constants::INTEREST_COUNT;
+
+/// Interest relatedness taxonomy (admin-configured singleton)
+/// Bit j of related[i] marks interests i and j as related; the matrix is
+/// kept symmetric. An interest only one party holds earns partial credit in
+/// the interest score when it is related to one the other party holds.
+/// Without this account no interests are related (plain Jaccard)
+/// 
+/// Seeds: [b"interest_matrix"]
+#[account]
+#[derive(InitSpace)]
+pub struct InterestMatrix {
+    /// Relatedness rows, one bitmask per interest
+    pub related: [u32; INTEREST_COUNT],
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl InterestMatrix {
+    /// Relatedness rows from an unchecked [b"interest_matrix"] account,
+    /// all zero (nothing related) while the matrix has not been created
+    pub fn rows_or_default(info: &AccountInfo) -> Result<[u32; INTEREST_COUNT]> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok([0u32; INTEREST_COUNT]);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(InterestMatrix::try_deserialize(&mut &data[..])?.related)
+    }
+    
+    /// Set interest's row to related_mask and mirror it into the other rows
+    /// An interest is never related to itself
+    pub fn set_row(&mut self, interest: usize, related_mask: u32) {
+        let mask = related_mask & !(1u32 << interest);
+        for other in 0..INTEREST_COUNT {
+            if mask & (1u32 << other) != 0 {
+                self.related[other] |= 1u32 << interest;
+            } else {
+                self.related[other] &= !(1u32 << interest);
+            }
+        }
+        self.related[interest] = mask;
+    }
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+/// Quick-look score from public data only - no MPC involved
+/// Kept apart from MatchRecord so a preview is never mistaken for, or
+/// overwrites, an MPC-backed result. Refreshed on every preview_match
+/// 
+/// Seeds: [b"match_preview", first, second] in canonical_pair order
+#[account]
+#[derive(InitSpace)]
+pub struct MatchPreview {
+    /// Trip the preview was requested for
+    pub trip_a: Pubkey,
+    
+    /// Candidate trip
+    pub trip_b: Pubkey,
+    
+    /// Date overlap (0-100), same formula as the MPC date_score
+    pub date_score: u8,
+    
+    /// Public-interest Jaccard (0-100), None unless both owners publish interests
+    pub interest_score: Option<u8>,
+    
+    /// Always None - routes are only ever scored inside MPC
+    pub route_score: Option<u8>,
+    
+    /// Balanced-profile total over the dimensions that are present
+    pub partial_score: u8,
+    
+    /// When the preview was last computed
+    pub previewed_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
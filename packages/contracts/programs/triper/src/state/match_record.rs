@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::constants::BOOST_RANK_BONUS;
 
 /// Match record - Stores match status and detailed scores
 /// Computation happens via Arcium MXE confidential circuit
@@ -22,6 +23,10 @@ pub struct MatchRecord {
     /// Interest similarity score (0-100)
     pub interest_score: u8,
     
+    /// Route coverage: overlap relative to the shorter route (0-100)
+    /// Informational - not part of total_score
+    pub coverage_score: u8,
+    
     /// Match status
     pub status: MatchStatus,
     
@@ -37,6 +42,107 @@ pub struct MatchRecord {
     /// Arcium computation ID (for tracking MXE execution)
     pub computation_id: [u8; 32],
     
+    /// When the match was rejected (0 if never rejected)
+    pub rejected_at: i64,
+    
+    /// Wallet that rejected the match (default if never rejected)
+    pub rejected_by: Pubkey,
+    
+    /// Set when either trip's key was rotated after scoring
+    /// Scores must be recomputed before the match can be accepted
+    pub is_stale: bool,
+    
+    /// Last moment the match can be accepted (0 until scores land)
+    pub accept_deadline: i64,
+    
+    /// After this a still-Pending match can be expired by anyone
+    pub expires_at: i64,
+    
+    /// Weighting used for total_score, chosen at initiate_match
+    pub scoring_profile: ScoringProfile,
+    
+    /// Interest weight multiplier (0-100) applied at queue time
+    /// The staler participant's interest data sets it (100 = no decay)
+    pub interest_freshness: u8,
+    
+    /// SCORING_VERSION that produced the scores (0 until scored)
+    pub scoring_version: u8,
+    
+    /// Wallet that funded this account - receives the rent on close
+    pub rent_payer: Pubkey,
+    
+    /// Scores are kept off the public fields, chosen at initiate_match
+    /// When set, the plaintext score fields stay 0 and only the
+    /// participants can read encrypted_scores_a / encrypted_scores_b
+    pub encrypt_scores: bool,
+    
+    /// Scores encrypted to trip_a owner's profile key (encrypt_scores only)
+    pub encrypted_scores_a: EncryptedScores,
+    
+    /// Scores encrypted to trip_b owner's profile key (encrypt_scores only)
+    pub encrypted_scores_b: EncryptedScores,
+    
+    /// Deposit locked by compute_trip_match (0 when none is outstanding)
+    pub escrow_lamports: u64,
+    
+    /// Wallet that locked the escrow - refunded when the callback lands
+    pub escrow_payer: Pubkey,
+    
+    /// After this the escrow can be forfeited to the treasury
+    pub escrow_expires_at: i64,
+    
+    /// Reveal keys submitted by each participant after mutual acceptance
+    /// (all zero until submitted). Nothing is decrypted until both are set,
+    /// so neither party can unilaterally reveal the other's data
+    pub reveal_key_a: [u8; 32],
+    pub reveal_key_b: [u8; 32],
+    
+    /// Result of check_match_threshold - only the pass/fail bit and the
+    /// threshold it was checked against are ever stored
+    pub threshold_checked: bool,
+    pub score_threshold: u8,
+    pub threshold_passed: bool,
+    
+    /// Owner whose pending_match_count this match counts against
+    /// (default when it was initiated without a cap in force)
+    pub pending_slot_owner: Pubkey,
+    
+    /// Whether the match currently holds that slot (false once resolved)
+    pub pending_slot_held: bool,
+    
+    /// Trip owners captured at initiate_match (trip ownership never changes)
+    /// Lets clients find a wallet's matches without loading both trips
+    pub trip_a_owner: Pubkey,
+    pub trip_b_owner: Pubkey,
+    
+    /// Set by the reveal callbacks once revealed data has been published
+    pub revealed: bool,
+    
+    /// Whether each participant confirmed they fetched the revealed data
+    pub acknowledged_by_a: bool,
+    pub acknowledged_by_b: bool,
+    
+    /// (route, date, interest) weights that produce total_score, copied
+    /// from scoring_profile at initiate_match. The interest weight is further
+    /// scaled by interest_freshness, and the circuit redistributes it when
+    /// either party lacks interest data
+    pub weights: [u8; 3],
+    
+    /// Age compatibility score (0-100), 0 unless both trips disclose an age
+    /// Counts toward total_score with AGE_WEIGHT when it applies
+    pub age_score: u8,
+    
+    /// Weight of the budget component, chosen at initiate_match (0 = off)
+    pub budget_weight: u8,
+    
+    /// Budget compatibility score (0-100), 0 unless budget_weight is set
+    /// and both trips disclose a daily budget
+    pub budget_score: u8,
+    
+    /// Lowest total_score at which accept_match lets the match become
+    /// Mutual, set at initiate_match (0 = no gate)
+    pub min_score: u8,
+    
     /// Bump seed for PDA
     pub bump: u8,
 }
@@ -49,15 +155,109 @@ impl MatchRecord {
         1 +  // route_score
         1 +  // date_score
         1 +  // interest_score
+        1 +  // coverage_score
         1 +  // status (enum)
         1 +  // trip_a_accepted
         1 +  // trip_b_accepted
         8 +  // created_at
         32 + // computation_id
+        8 +  // rejected_at
+        32 + // rejected_by
+        1 +  // is_stale
+        8 +  // accept_deadline
+        8 +  // expires_at
+        1 +  // scoring_profile (enum)
+        1 +  // interest_freshness
+        1 +  // scoring_version
+        32 + // rent_payer
+        1 +  // encrypt_scores
+        EncryptedScores::LEN + // encrypted_scores_a
+        EncryptedScores::LEN + // encrypted_scores_b
+        8 +  // escrow_lamports
+        32 + // escrow_payer
+        8 +  // escrow_expires_at
+        32 + // reveal_key_a
+        32 + // reveal_key_b
+        1 +  // threshold_checked
+        1 +  // score_threshold
+        1 +  // threshold_passed
+        32 + // pending_slot_owner
+        1 +  // pending_slot_held
+        32 + // trip_a_owner
+        32 + // trip_b_owner
+        1 +  // revealed
+        1 +  // acknowledged_by_a
+        1 +  // acknowledged_by_b
+        3 +  // weights
+        1 +  // age_score
+        1 +  // budget_weight
+        1 +  // budget_score
+        1 +  // min_score
         1;   // bump
     
     // Alias for compatibility
     pub const SIZE: usize = Self::LEN;
+    
+    /// Whether both participants have submitted reveal keys
+    pub fn reveal_keys_exchanged(&self) -> bool {
+        self.reveal_key_a != [0u8; 32] && self.reveal_key_b != [0u8; 32]
+    }
+    
+    /// Whether the match clears min_score
+    /// In encrypt_scores mode total_score is withheld, so the gate is met
+    /// only by a passed check_match_threshold at min_score or above
+    pub fn meets_min_score(&self) -> bool {
+        if self.encrypt_scores {
+            self.min_score == 0
+                || (self.threshold_checked && self.threshold_passed && self.score_threshold >= self.min_score)
+        } else {
+            self.total_score >= self.min_score
+        }
+    }
+    
+    /// Score used for ranking matches in listings
+    /// Halved when the counterparty's trip is no longer active so stale
+    /// matches sink; the stored total_score is left untouched
+    pub fn display_score(&self, counterparty_active: bool) -> u8 {
+        if counterparty_active {
+            self.total_score
+        } else {
+            self.total_score / 2
+        }
+    }
+    
+    /// Sort key for suggestion listings
+    /// A boosted counterparty trip ranks above unboosted ones with the same
+    /// display score; the stored scores are never modified
+    pub fn ranking_score(&self, counterparty_active: bool, counterparty_boosted: bool) -> u16 {
+        let bonus = if counterparty_boosted { BOOST_RANK_BONUS } else { 0 };
+        self.display_score(counterparty_active) as u16 + bonus
+    }
+}
+
+/// A trip pair in byte order, so X-Y and Y-X derive the same match and
+/// pair-history PDAs. Only the seeds are ordered - trip_a on the record
+/// stays the initiator's trip
+pub fn canonical_pair(a: Pubkey, b: Pubkey) -> (Pubkey, Pubkey) {
+    if a.to_bytes() <= b.to_bytes() {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Match scores re-encrypted by MPC to one participant
+/// Decrypts to (route, date, interest, age, budget, total, coverage) with the
+/// participant's profile key and this nonce
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, PartialEq, Eq)]
+pub struct EncryptedScores {
+    pub nonce: u128,
+    pub ciphertexts: [[u8; 32]; 7],
+}
+
+impl EncryptedScores {
+    pub const LEN: usize = 16 + // nonce
+        7 * 32;                 // ciphertexts
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -66,4 +266,100 @@ pub enum MatchStatus {
     Completed,    // MPC computation finished, scores available
     Mutual,       // Both users accepted the match
     Rejected,     // One or both users rejected
+    Expired,      // Not accepted before accept_deadline, or never scored before expires_at
+}
+
+/// Score weighting presets, as (route, date, interest) percentages
+/// Each triple sums to 100; interest weight is redistributed by the
+/// circuit when either party lacks interest data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum ScoringProfile {
+    Balanced,         // 40 / 35 / 25
+    DateFocused,      // 25 / 55 / 20
+    RouteFocused,     // 60 / 25 / 15
+    InterestFocused,  // 25 / 25 / 50
+}
+
+impl ScoringProfile {
+    /// Profile by declaration order (0 = Balanced .. 3 = InterestFocused)
+    pub fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(ScoringProfile::Balanced),
+            1 => Some(ScoringProfile::DateFocused),
+            2 => Some(ScoringProfile::RouteFocused),
+            3 => Some(ScoringProfile::InterestFocused),
+            _ => None,
+        }
+    }
+    
+    /// (route_weight, date_weight, interest_weight)
+    pub fn weights(&self) -> (u8, u8, u8) {
+        match self {
+            ScoringProfile::Balanced => (40, 35, 25),
+            ScoringProfile::DateFocused => (25, 55, 20),
+            ScoringProfile::RouteFocused => (60, 25, 15),
+            ScoringProfile::InterestFocused => (25, 25, 50),
+        }
+    }
+    
+    /// Weights as stored on the match record
+    pub fn weight_array(&self) -> [u8; 3] {
+        let (route, date, interest) = self.weights();
+        [route, date, interest]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::Trip;
+    
+    /// A zeroed record carrying only the given total score
+    fn scored(total_score: u8) -> MatchRecord {
+        let zeroed = [0u8; MatchRecord::LEN];
+        let mut record = MatchRecord::try_deserialize_unchecked(&mut &zeroed[..]).unwrap();
+        record.total_score = total_score;
+        record
+    }
+    
+    #[test]
+    fn display_score_keeps_the_total_for_an_active_counterparty() {
+        for total in [0, 1, 57, 100] {
+            assert_eq!(scored(total).display_score(true), total);
+        }
+    }
+    
+    #[test]
+    fn display_score_halves_the_total_for_an_inactive_counterparty() {
+        let record = scored(81);
+        assert_eq!(record.display_score(false), 40);
+        assert!(record.display_score(false) < record.display_score(true));
+        // The stored score is untouched
+        assert_eq!(record.total_score, 81);
+        assert_eq!(scored(100).display_score(false), 50);
+        assert_eq!(scored(0).display_score(false), 0);
+    }
+    
+    #[test]
+    fn active_boost_raises_ranking_and_an_expired_one_does_not() {
+        let now = 1_700_000_000;
+        let zeroed = [0u8; Trip::LEN];
+        let mut counterparty = Trip::try_deserialize_unchecked(&mut &zeroed[..]).unwrap();
+        let record = scored(60);
+        let unboosted = record.ranking_score(true, false);
+        assert_eq!(unboosted, 60);
+        
+        counterparty.boost_until = now + 60;
+        assert_eq!(record.ranking_score(true, counterparty.is_boosted(now)), unboosted + BOOST_RANK_BONUS);
+        // A boosted match outranks an unboosted one up to BOOST_RANK_BONUS points higher
+        assert!(record.ranking_score(true, true) > scored(80).ranking_score(true, false));
+        
+        // Expired (including at exactly boost_until) ranks like no boost
+        for expired in [now, now - 1] {
+            counterparty.boost_until = expired;
+            assert_eq!(record.ranking_score(true, counterparty.is_boosted(now)), unboosted);
+        }
+        // The stored score never moves
+        assert_eq!(record.total_score, 60);
+    }
 }
@@ -1,7 +1,29 @@
 pub mod match_record;
 pub mod trip;
 pub mod user_profile;
+pub mod pair_history;
+pub mod program_config;
+pub mod destination_fee;
+pub mod interest_matrix;
+pub mod match_preview;
+pub mod incompatibility_matrix;
+pub mod destination_index;
+pub mod suggestion_seed;
+pub mod blocklist;
+pub mod payload_registry;
+pub mod destination_profile;
 
 pub use match_record::*;
 pub use trip::*;
 pub use user_profile::*;
+pub use pair_history::*;
+pub use program_config::*;
+pub use destination_fee::*;
+pub use interest_matrix::*;
+pub use match_preview::*;
+pub use incompatibility_matrix::*;
+pub use destination_index::*;
+pub use suggestion_seed::*;
+pub use blocklist::*;
+pub use payload_registry::*;
+pub use destination_profile::*;
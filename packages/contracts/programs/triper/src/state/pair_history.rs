@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+/// Per trip-pair history - survives closing and re-creating the match record
+/// Caps how often the same pair can start a fresh match (anti-harassment)
+/// 
+/// Seeds: [b"pair_history", first, second] in canonical_pair order
+#[account]
+#[derive(InitSpace)]
+pub struct PairHistory {
+    /// First trip public key
+    pub trip_a: Pubkey,
+    
+    /// Second trip public key
+    pub trip_b: Pubkey,
+    
+    /// Number of match records created for this pair
+    pub rematch_count: u8,
+    
+    /// When the pair's last closed match had been rejected (0 = never)
+    /// Keeps the rematch cooldown in force after the record is closed
+    pub last_rejected_at: i64,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+/// Per owner-pair history - keyed on the two wallets rather than their trips,
+/// so deleting and recreating trips doesn't reset it (anti-stalking)
+/// 
+/// Seeds: [b"owner_pair_history", first, second] in canonical_pair order
+#[account]
+#[derive(InitSpace)]
+pub struct OwnerPairHistory {
+    /// First owner public key
+    pub owner_a: Pubkey,
+    
+    /// Second owner public key
+    pub owner_b: Pubkey,
+    
+    /// Match records ever initiated between the two owners
+    pub match_count: u32,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PairHistory {
+    /// Whether a rejection at `rejected_at` (0 = none) still blocks a rematch
+    pub fn in_cooldown(rejected_at: i64, now: i64) -> bool {
+        rejected_at != 0 && now < rejected_at + crate::constants::REMATCH_COOLDOWN_SECS
+    }
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::constants::MAX_PAYLOAD_CHECKSUMS;
+
+/// Checksums of the owner's recent encrypted_waypoints payloads (opt-in)
+/// The same ciphertext on two trips would reveal they share a route, so
+/// create_trip refuses a payload whose checksum is already registered
+/// 
+/// Seeds: [b"payload_registry", owner]
+#[account]
+#[derive(InitSpace)]
+pub struct PayloadRegistry {
+    /// Owner whose trips this registry covers
+    pub owner: Pubkey,
+    
+    /// sha256 of each registered payload, oldest first
+    #[max_len(MAX_PAYLOAD_CHECKSUMS)]
+    pub checksums: Vec<[u8; 32]>,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl PayloadRegistry {
+    /// Checksum identifying an encrypted payload
+    pub fn checksum(payload: &[u8]) -> [u8; 32] {
+        hash(payload).to_bytes()
+    }
+    
+    /// Whether `checksum` is already registered
+    pub fn contains(&self, checksum: &[u8; 32]) -> bool {
+        self.checksums.contains(checksum)
+    }
+    
+    /// Register `checksum`, evicting the oldest entry when full
+    pub fn record(&mut self, checksum: [u8; 32]) {
+        if self.checksums.len() >= MAX_PAYLOAD_CHECKSUMS {
+            self.checksums.remove(0);
+        }
+        self.checksums.push(checksum);
+    }
+}
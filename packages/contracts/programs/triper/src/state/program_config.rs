@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+/// Global program configuration (singleton)
+/// Holds the privileged keys for admin-gated instructions
+/// 
+/// Seeds: [b"program_config"]
+#[account]
+#[derive(InitSpace)]
+pub struct ProgramConfig {
+    /// Admin allowed to change configuration
+    pub admin: Pubkey,
+    
+    /// Key allowed to set user verification tiers
+    pub verifier: Pubkey,
+    
+    /// Seconds without owner activity before a user's trips can be swept
+    pub inactivity_period_secs: i64,
+    
+    /// Interest data older than this loses half its weight per period (0 = no decay)
+    pub interest_half_life_secs: i64,
+    
+    /// Seconds a compute escrow waits for its callback before it can be forfeited
+    pub compute_escrow_timeout_secs: i64,
+    
+    /// Most unresolved (Pending/Completed) matches one user may initiate (0 = no cap)
+    pub max_pending_matches: u16,
+    
+    /// Destination-hash salt generation, bumped by rotate_destination_salt
+    /// Trips record the epoch their destination_grid_hash was salted under
+    pub destination_salt_epoch: u32,
+    
+    /// SHA256 of the current salt, so clients can check they hash with the
+    /// right one. The salt itself is distributed off-chain and never stored
+    pub destination_salt_commitment: [u8; 32],
+    
+    /// Seconds a trip must exist before initiate_match accepts it (0 = none)
+    pub min_trip_age_secs: i64,
+    
+    /// Largest route area bucket create_trip accepts (0 = no limit)
+    /// Same scale as set_route_density: floor(log2(bbox area in cells + 1))
+    pub max_route_area_bucket: u8,
+    
+    /// Lifetime cap on matches initiated between the same two owners,
+    /// whatever trips they use (0 = no limit)
+    pub max_owner_pair_matches: u16,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
+
+impl ProgramConfig {
+    /// Current destination salt epoch from an unchecked [b"program_config"]
+    /// account, 0 while the config has not been initialized
+    pub fn salt_epoch_or_default(info: &AccountInfo) -> Result<u32> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(0);
+        }
+        let data = info.try_borrow_data()?;
+        Ok(ProgramConfig::try_deserialize(&mut &data[..])?.destination_salt_epoch)
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// A trip owner's committed suggestion seed, pending reveal
+/// commit_suggestion_seed stores SHA256(seed) and the commit slot; the seed
+/// is revealed in a later slot and mixed with that slot's hash, so neither
+/// the requester (who cannot know the slot hash) nor a validator (who
+/// cannot know the seed) can grind the suggestion. Closed on reveal
+/// 
+/// Seeds: [b"suggestion_seed", trip]
+#[account]
+#[derive(InitSpace)]
+pub struct SuggestionSeed {
+    /// Trip the suggestion is for
+    pub trip: Pubkey,
+    
+    /// SHA256 of the secret seed
+    pub commitment: [u8; 32],
+    
+    /// Slot the commitment landed in - its slot hash feeds the randomness
+    pub commit_slot: u64,
+    
+    /// Destination index shard the suggestion is drawn from, fixed up front
+    /// so the requester cannot pick a shard after seeing the randomness
+    pub shard_id: u16,
+    
+    /// Bump seed for PDA derivation
+    pub bump: u8,
+}
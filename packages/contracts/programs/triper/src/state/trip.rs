@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::{ROUTE_DENSITY_SCALE, SEEKING_ANY};
+use crate::prefilter::{flexibility_tolerance_secs, same_destination};
 
 /// Trip account with destination-based matching
 /// Two-stage architecture:
@@ -25,9 +27,9 @@ pub struct Trip {
     pub end_date: i64,
     
     /// Encrypted waypoints ONLY (no interests, no duplicate dates)
-    /// Contains: waypoints[20] (H3 cells), waypoint_count
+    /// Contains: waypoints[20] (H3 cells), waypoint_count, min_interests
     /// Format: x25519 + RescueCipher encrypted WaypointData struct
-    /// ~640 bytes actual
+    /// ~640 bytes actual; create_trip enforces MAX_ENCRYPTED_WAYPOINTS_LEN
     #[max_len(800)]
     pub encrypted_waypoints: Vec<u8>,
     
@@ -40,9 +42,46 @@ pub struct Trip {
     /// Number of match computations performed
     pub match_count: u32,
     
+    /// Minimum verification tier required of counterparties (0 = no requirement)
+    pub min_counterparty_tier: u8,
+    
+    /// What kind of traveler the owner is on this trip (TRAVELER_TYPE_*)
+    pub traveler_type: u8,
+    
+    /// Counterparty traveler type this trip wants to meet (SEEKING_ANY = no filter)
+    pub seeking_type: u8,
+    
+    /// Waypoints per bounding-box area bucket (0 = not set)
+    /// Public hint for choosing a matching grid size - dense urban routes
+    /// score high, sparse cross-country routes low
+    pub route_density: u8,
+    
+    /// Paid ranking boost expiry (0 = never boosted)
+    /// Only affects display ranking, never match scores
+    pub boost_until: i64,
+    
+    /// How far the owner will move their dates, 0 (rigid) to 100 (very
+    /// flexible). Widens the date overlap scoring credits - see
+    /// prefilter::flexibility_tolerance_secs
+    pub flexibility: u8,
+    
+    /// ProgramConfig salt epoch destination_grid_hash was computed under
+    /// Hashes from different epochs are not comparable
+    pub salt_epoch: u32,
+    
     /// Creation timestamp
     pub created_at: i64,
     
+    /// Last update_trip (created_at until the trip is edited)
+    pub updated_at: i64,
+    
+    /// NOTIFY_* bits the owner wants match alerts for (NOTIFY_ALL by default)
+    pub notify_on: u8,
+    
+    /// Match on destination + dates alone, ignoring route overlap
+    /// Takes effect only when both trips opt in and share a destination
+    pub destination_only_match: bool,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
 }
@@ -57,11 +96,60 @@ impl Trip {
         32 + // public_key
         1 +  // is_active
         4 +  // match_count
+        1 +  // min_counterparty_tier
+        1 +  // traveler_type
+        1 +  // seeking_type
+        1 +  // route_density
+        8 +  // boost_until
+        1 +  // flexibility
+        4 +  // salt_epoch
         8 +  // created_at
+        8 +  // updated_at
+        1 +  // notify_on
+        1 +  // destination_only_match
         1;   // bump
-    // Total: ~937 bytes
+    // Total: ~963 bytes
 
     // Alias for compatibility
     pub const SIZE: usize = Self::LEN;
+    
+    /// Whether the owner wants alerts for `kind` (a NOTIFY_* bit)
+    pub fn notifies(&self, kind: u8) -> bool {
+        self.notify_on & kind != 0
+    }
+    
+    /// Whether this trip's seeking filter admits a counterparty of `traveler_type`
+    pub fn accepts_traveler(&self, traveler_type: u8) -> bool {
+        self.seeking_type == SEEKING_ANY || self.seeking_type == traveler_type
+    }
+    
+    /// Route density from a waypoint count and bounding-box area bucket
+    pub fn compute_route_density(waypoint_count: u8, area_bucket: u8) -> u8 {
+        (waypoint_count as u32 * ROUTE_DENSITY_SCALE / (area_bucket as u32 + 1)).min(u8::MAX as u32) as u8
+    }
+    
+    /// Whether a match with `other` should ignore route overlap: both
+    /// owners opted in and the destinations provably match
+    pub fn destination_only_with(&self, other: &Trip) -> bool {
+        self.destination_only_match
+            && other.destination_only_match
+            && same_destination(
+                &self.destination_grid_hash,
+                self.salt_epoch,
+                &other.destination_grid_hash,
+                other.salt_epoch,
+            ) == Some(true)
+    }
+    
+    /// Date shift scoring allows for a match with `other`: the tolerance of
+    /// the more flexible of the two trips
+    pub fn date_tolerance_with(&self, other: &Trip) -> i64 {
+        flexibility_tolerance_secs(self.flexibility.max(other.flexibility))
+    }
+    
+    /// Whether a paid boost is active at `now`
+    pub fn is_boosted(&self, now: i64) -> bool {
+        self.boost_until > now
+    }
 }
 
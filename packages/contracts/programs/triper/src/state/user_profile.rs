@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
 
 /// User Profile - Stores encrypted user preferences and interests
 /// Privacy-first: All personal data is encrypted
@@ -10,6 +11,8 @@ use anchor_lang::prelude::*;
 /// - travel_style: u8 - Travel style preference
 /// 
 /// Seeds: [b"user_profile", user.key()]
+/// The owner's trips are [b"trip", user.key(), index] for index in
+/// 0..next_trip_index
 #[account]
 #[derive(InitSpace)]
 pub struct UserProfile {
@@ -34,12 +37,45 @@ pub struct UserProfile {
     /// Total number of trips created
     pub trip_count: u32,
     
+    /// Index the next trip PDA is derived from (never reused)
+    pub next_trip_index: u32,
+    
     /// Total number of matches found
     pub total_matches: u32,
     
     /// Whether profile is active
     pub is_active: bool,
     
+    /// When the encrypted interests were last written
+    /// Older interest data can be down-weighted in matching (see interest decay)
+    pub interests_updated_at: i64,
+    
+    /// Last owner action (profile writes and record_activity heartbeats)
+    /// Used by sweep_inactive_user to retire abandoned trips
+    pub last_active_at: i64,
+    
+    /// Identity verification tier (PUBLIC)
+    /// 0 = unverified, higher = more verified; set only by the config verifier
+    pub verification_tier: u8,
+    
+    /// Matches this user initiated that are still Pending or Completed
+    /// Bounded by ProgramConfig::max_pending_matches when a cap is set
+    pub pending_match_count: u16,
+    
+    /// Interest bitmask the owner chose to publish (None = kept private)
+    /// Only preview_match reads it; MPC matching always uses encrypted_data
+    pub public_interests: Option<u32>,
+    
+    /// Matches that reached Mutual with this user as a participant
+    pub mutual_matches: u32,
+    
+    /// Matches the counterparty rejected
+    pub rejections_received: u32,
+    
+    /// 0-100 share of decided outcomes that went Mutual (PUBLIC)
+    /// Derived from the counters above by recompute_reputation
+    pub reputation: u8,
+    
     /// Bump seed for PDA derivation
     pub bump: u8,
 }
@@ -52,7 +88,52 @@ impl UserProfile {
         8 +  // created_at
         8 +  // updated_at
         4 +  // trip_count
+        4 +  // next_trip_index
         4 +  // total_matches
         1 +  // is_active
+        8 +  // interests_updated_at
+        8 +  // last_active_at
+        1 +  // verification_tier
+        2 +  // pending_match_count
+        1 + 4 + // public_interests (Option tag + mask)
+        4 +  // mutual_matches
+        4 +  // rejections_received
+        1 +  // reputation
         1;   // bump
+    
+    /// Reserve `count` consecutive trip indices, returning the first
+    /// trip_count only advances as trips are actually created
+    pub fn reserve_trip_indices(&mut self, count: u32) -> Result<u32> {
+        let first = self.next_trip_index;
+        self.next_trip_index = first
+            .checked_add(count)
+            .ok_or(ErrorCode::TripIndexExhausted)?;
+        Ok(first)
+    }
+    
+    /// Count a match that went Mutual
+    pub fn record_mutual_match(&mut self) {
+        self.mutual_matches = self.mutual_matches.saturating_add(1);
+        self.recompute_reputation();
+    }
+    
+    /// Count a rejection by the counterparty
+    pub fn record_rejection_received(&mut self) {
+        self.rejections_received = self.rejections_received.saturating_add(1);
+        self.recompute_reputation();
+    }
+    
+    /// Take back a rejection that was undone within its grace window
+    pub fn revoke_rejection_received(&mut self) {
+        self.rejections_received = self.rejections_received.saturating_sub(1);
+        self.recompute_reputation();
+    }
+    
+    /// Map mutual / (mutual + rejections received) to 0-100
+    /// A user with no decided matches yet keeps the full 100
+    pub fn recompute_reputation(&mut self) {
+        let mutual = self.mutual_matches as u64;
+        let decided = mutual + self.rejections_received as u64;
+        self.reputation = if decided == 0 { 100 } else { (mutual * 100 / decided) as u8 };
+    }
 }
@@ -0,0 +1,75 @@
+// Encrypted payload framing
+// The one definition of how ciphertext bytes map onto circuit arguments.
+// Every encrypted circuit input is a sequence of whole ENCRYPTED_FIELD_SIZE
+// fields; a combined payload carries a route and an interest profile as:
+//
+//   [waypoint_field_count: u8][waypoint fields ...][interest fields ...]
+
+use anchor_lang::prelude::*;
+use arcium_anchor::prelude::*;
+use crate::constants::ENCRYPTED_FIELD_SIZE;
+use crate::error::ErrorCode;
+
+/// Split a ciphertext into its 32-byte encrypted fields
+/// Fails instead of silently dropping a trailing partial field
+pub fn encrypted_fields(ciphertext: &[u8]) -> Result<Vec<[u8; ENCRYPTED_FIELD_SIZE]>> {
+    require!(
+        ciphertext.len() % ENCRYPTED_FIELD_SIZE == 0,
+        ErrorCode::MalformedEncryptedPayload
+    );
+
+    Ok(ciphertext
+        .chunks_exact(ENCRYPTED_FIELD_SIZE)
+        .map(|chunk| {
+            let mut field = [0u8; ENCRYPTED_FIELD_SIZE];
+            field.copy_from_slice(chunk);
+            field
+        })
+        .collect())
+}
+
+/// Append one Enc<Shared, _> circuit input: its key, its nonce, then its fields
+pub fn push_encrypted_input(
+    args: &mut Vec<Argument>,
+    public_key: [u8; 32],
+    nonce: u128,
+    ciphertext: &[u8],
+) -> Result<()> {
+    args.push(Argument::ArcisPubkey(public_key));
+    args.push(Argument::PlaintextU128(nonce));
+    for field in encrypted_fields(ciphertext)? {
+        args.push(Argument::EncryptedU8(field));
+    }
+    Ok(())
+}
+
+/// Split a combined payload into (waypoint ciphertext, interest ciphertext)
+pub fn split_encrypted_payload(payload: &[u8]) -> Result<(&[u8], &[u8])> {
+    let (&waypoint_fields, body) = payload
+        .split_first()
+        .ok_or(ErrorCode::MalformedEncryptedPayload)?;
+
+    let waypoint_len = waypoint_fields as usize * ENCRYPTED_FIELD_SIZE;
+    require!(
+        body.len() >= waypoint_len && body.len() % ENCRYPTED_FIELD_SIZE == 0,
+        ErrorCode::MalformedEncryptedPayload
+    );
+
+    Ok(body.split_at(waypoint_len))
+}
+
+/// Inverse of split_encrypted_payload
+pub fn join_encrypted_payload(waypoints: &[u8], interests: &[u8]) -> Result<Vec<u8>> {
+    require!(
+        waypoints.len() % ENCRYPTED_FIELD_SIZE == 0 && interests.len() % ENCRYPTED_FIELD_SIZE == 0,
+        ErrorCode::MalformedEncryptedPayload
+    );
+    let waypoint_fields = u8::try_from(waypoints.len() / ENCRYPTED_FIELD_SIZE)
+        .map_err(|_| ErrorCode::MalformedEncryptedPayload)?;
+
+    let mut payload = Vec::with_capacity(1 + waypoints.len() + interests.len());
+    payload.push(waypoint_fields);
+    payload.extend_from_slice(waypoints);
+    payload.extend_from_slice(interests);
+    Ok(payload)
+}
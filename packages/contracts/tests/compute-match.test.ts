@@ -1,16 +1,12 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
-import { PublicKey } from "@solana/web3.js";
+import { ComputeBudgetProgram, PublicKey } from "@solana/web3.js";
 import { Triper } from "../target/types/triper";
 import { randomBytes } from "crypto";
 import {
   awaitComputationFinalization,
   getArciumEnv,
   getCompDefAccOffset,
-  getArciumAccountBaseSeed,
-  getArciumProgAddress,
-  uploadCircuit,
-  buildFinalizeCompDefTx,
   deserializeLE,
   getMXEAccAddress,
   getMempoolAccAddress,
@@ -20,17 +16,30 @@ import {
   x25519,
   RescueCipher,
 } from "@arcium-hq/client";
-import * as fs from "fs";
 import * as os from "os";
 import { expect } from "chai";
 import {
   getMXEPublicKeyWithRetry,
+  initCompDef,
+  readKpJson,
+  createFundedUser,
+  createRawTrip,
+  createRawUserProfile,
+  initiateRawMatch,
+  deriveBlocklistPDA,
+  deriveMatchPDA,
+  derivePairHistoryPDA,
+  deriveOwnerPairHistoryPDA,
   createSampleTripData,
   createVariantTripData,
-  createSampleUserData
+  createSampleUserData,
+  getOrInitProgramConfig,
+  ScoringProfile
 } from "./utils";
 import { createTrip } from "../../../apps/web/src/lib/solana/create-trip";
 import { createOrUpdateUserProfile } from "../../../apps/web/src/lib/solana/user-actions";
+import { decryptMatchScores } from "../../../apps/web/src/lib/arcium/user-encryption";
+import { InterestTag } from "../../../apps/web/src/types";
 
 describe("Arcium Trip Matching", () => {
   // Configure the client to use the local cluster
@@ -55,6 +64,11 @@ describe("Arcium Trip Matching", () => {
 
   const arciumEnv = getArciumEnv();
 
+  // Auto-reject threshold, exported from constants.rs through the IDL
+  const MIN_TOTAL_SCORE = Number(
+    program.idl.constants.find((c) => c.name === "minTotalScore").value
+  );
+
   console.log("Program ID:", program.programId.toBase58());
   console.log("Arcium Cluster:", arciumEnv.arciumClusterPubkey.toBase58());
 
@@ -62,92 +76,13 @@ describe("Arcium Trip Matching", () => {
     const owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
 
     console.log("\n🔧 Initializing compute_trip_match computation definition...");
-    
-    // Check if comp def exists first
-    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
-      "ComputationDefinitionAccount"
+    await initCompDef(
+      program,
+      provider as anchor.AnchorProvider,
+      owner,
+      "compute_trip_match",
+      () => program.methods.initComputeTripMatchCompDef()
     );
-    const offset = getCompDefAccOffset("compute_trip_match");
-    const compDefPDA = PublicKey.findProgramAddressSync(
-      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
-      getArciumProgAddress()
-    )[0];
-
-    const compDefAccount = await provider.connection.getAccountInfo(compDefPDA);
-    
-    if (compDefAccount) {
-      console.log("⚠️  Computation definition already exists");
-      console.log("   Ensuring circuit is uploaded and finalized...");
-      
-      // Upload circuit even if comp def exists
-      let rawCircuit: Buffer | undefined;
-      const possiblePaths = [
-        "build/compute_trip_match_localnet.arcis",
-        "build/compute_trip_match_testnet.arcis",
-        "build/compute_trip_match.arcis",
-      ];
-      
-      for (const path of possiblePaths) {
-        if (fs.existsSync(path)) {
-          console.log(`   Found circuit at ${path}`);
-          rawCircuit = fs.readFileSync(path);
-          break;
-        }
-      }
-      
-      if (rawCircuit) {
-        try {
-          await uploadCircuit(
-            provider as anchor.AnchorProvider,
-            "compute_trip_match",
-            program.programId,
-            rawCircuit,
-            true
-          );
-          console.log("✅ Circuit uploaded successfully!");
-        } catch (e: any) {
-          console.log("⚠️  Circuit already uploaded (this is okay):", e.message?.split('\n')[0]);
-        }
-      }
-      
-      // Finalize the comp def
-      console.log("   Finalizing computation definition...");
-      try {
-        const finalizeTx = await buildFinalizeCompDefTx(
-          provider as anchor.AnchorProvider,
-          Buffer.from(offset).readUInt32LE(),
-          program.programId
-        );
-
-        const latestBlockhash = await provider.connection.getLatestBlockhash();
-        finalizeTx.recentBlockhash = latestBlockhash.blockhash;
-        finalizeTx.lastValidBlockHeight = latestBlockhash.lastValidBlockHeight;
-
-        finalizeTx.sign(owner);
-
-        await provider.sendAndConfirm!(finalizeTx);
-        console.log("✅ Computation definition finalized!");
-      } catch (finalizeError: any) {
-        console.log("⚠️  Finalization info:", finalizeError.message?.split('\n')[0]);
-      }
-    } else {
-      try {
-        const initSig = await initComputeTripMatchCompDef(
-          program,
-          owner,
-          true, // uploadRawCircuit - upload the .arcis file
-          false  // offchainSource
-        );
-        console.log("✅ Computation definition initialized!");
-        console.log("   Transaction:", initSig);
-      } catch (error: any) {
-        if (error.message?.includes("already in use") || error.logs?.some((log: string) => log.includes("already in use"))) {
-          console.log("⚠️  Computation definition already initialized (this is okay)");
-        } else {
-          throw error;
-        }
-      }
-    }
   });
 
   it("Computes trip match with encrypted data", async () => {
@@ -272,23 +207,27 @@ describe("Arcium Trip Matching", () => {
     // Restore original wallet
     (provider as any).wallet = originalWallet;
 
-    // Generate nonce for MPC computation
-    const nonce = randomBytes(16);
+    // Route and interests are encrypted under independent keys, so either
+    // can later be revealed without exposing the other
+    const tripAAccountBefore = await program.account.trip.fetch(tripAResult.tripPDA);
+    const profileAAccount = await program.account.userProfile.fetch(userProfileAResult.userProfilePDA);
+    expect(Buffer.from(tripAAccountBefore.publicKey).equals(Buffer.from(profileAAccount.publicKey))).to.be.false;
 
     // Step 3: Initiate Match (creates MatchRecord)
     console.log("\n🤝 Initiating match between trips...");
-    const [matchRecordPda] = PublicKey.findProgramAddressSync(
-      [Buffer.from("match"), tripAResult.tripPDA.toBuffer(), tripBResult.tripPDA.toBuffer()],
-      program.programId
-    );
+    const matchRecordPda = deriveMatchPDA(program.programId, tripAResult.tripPDA, tripBResult.tripPDA);
 
     await program.methods
-      .initiateMatch()
+      .initiateMatch({ balanced: {} }, false, 0, 0)
       .accountsPartial({
         payer: owner.publicKey,
         tripA: tripAResult.tripPDA,
         tripB: tripBResult.tripPDA,
         matchRecord: matchRecordPda,
+        pairHistory: derivePairHistoryPDA(program.programId, tripAResult.tripPDA, tripBResult.tripPDA),
+        ownerPairHistory: deriveOwnerPairHistoryPDA(program.programId, owner.publicKey, tripOwnerB.publicKey),
+        blocklistA: deriveBlocklistPDA(program.programId, owner.publicKey),
+        blocklistB: deriveBlocklistPDA(program.programId, tripOwnerB.publicKey),
         systemProgram: anchor.web3.SystemProgram.programId,
       })
       .signers([owner])
@@ -312,7 +251,10 @@ describe("Arcium Trip Matching", () => {
     const queueSig = await program.methods
       .computeTripMatch(
         computationOffset,
-        new anchor.BN(deserializeLE(nonce).toString())
+        new anchor.BN(deserializeLE(tripAResult.nonce).toString()),
+        new anchor.BN(deserializeLE(tripBResult.nonce).toString()),
+        new anchor.BN(deserializeLE(userProfileAResult.nonce).toString()),
+        new anchor.BN(deserializeLE(userProfileBResult.nonce).toString())
       )
       .accountsPartial({
         computationAccount: getComputationAccAddress(
@@ -330,6 +272,8 @@ describe("Arcium Trip Matching", () => {
         matchRecord: matchRecordPda,
         tripA: tripAResult.tripPDA,
         tripB: tripBResult.tripPDA,
+        userProfileA: userProfileAResult.userProfilePDA,
+        userProfileB: userProfileBResult.userProfilePDA,
       })
       .signers([])
       .rpc();    console.log("✅ Computation queued!");
@@ -362,137 +306,2063 @@ describe("Arcium Trip Matching", () => {
     console.log("   Date Score:", matchEvent.dateScore);
     console.log("   Interest Score:", matchEvent.interestScore);
     console.log("   Total Score:", matchEvent.totalScore);
+    console.log("   Coverage Score:", matchEvent.coverageScore);
 
     // Verify scores are in valid range (0-100)
     expect(matchEvent.routeScore).to.be.at.least(0).and.at.most(100);
     expect(matchEvent.dateScore).to.be.at.least(0).and.at.most(100);
     expect(matchEvent.interestScore).to.be.at.least(0).and.at.most(100);
     expect(matchEvent.totalScore).to.be.at.least(0).and.at.most(100);
+    expect(matchEvent.coverageScore).to.be.at.least(0).and.at.most(100);
+
+    // Coverage divides the same intersection by the smaller route instead
+    // of the union, so it can never be below the Jaccard route score
+    expect(matchEvent.coverageScore).to.be.at.least(matchEvent.routeScore);
+
+    // Fixture scores: SF, San Jose and Santa Barbara are on both routes,
+    // while Monterey/Big Sur and Fresno/Bakersfield share no cell or parent
+    // - Route: 3 shared of 7 distinct cells = 42
+    // - Date: two-week trips starting 1000 s apart = 99
+    expect(matchEvent.routeScore).to.equal(42);
+    expect(matchEvent.dateScore).to.equal(99);
+
+    // Even a zero interest score leaves the balanced total at
+    // (42 * 40 + 99 * 35) / 100 = 51, well clear of the auto-reject threshold
+    expect(matchEvent.totalScore).to.be.at.least(51);
+    expect(matchEvent.totalScore).to.be.at.least(MIN_TOTAL_SCORE);
 
-    // Verify expected scores based on test data:
-    // - Route overlap: 4/8 cells match (SF, San Jose, Santa Barbara, LA) = ~50%
-    // - Date overlap: 100% (same dates)
-    // - Interest overlap: 2/3 match (hiking, photography) = ~67%
-    console.log("\n📊 Expected vs Actual:");
-    console.log("   Route: ~50% (4 overlapping waypoints)");
-    console.log("   Date: 100% (same dates)");
-    console.log("   Interest: ~67% (2/3 match)");
+    const matchRecord = await program.account.matchRecord.fetch(matchRecordPda);
+
+    // Callback maps circuit outputs field_0..field_4 onto the record in order
+    expect(matchRecord.routeScore).to.equal(matchEvent.routeScore);
+    expect(matchRecord.dateScore).to.equal(matchEvent.dateScore);
+    expect(matchRecord.interestScore).to.equal(matchEvent.interestScore);
+    expect(matchRecord.totalScore).to.equal(matchEvent.totalScore);
+    expect(matchRecord.coverageScore).to.equal(matchEvent.coverageScore);
+
+    // Quality match: kept and the quota unit consumed on both trips
+    const tripAAccount = await program.account.trip.fetch(tripAResult.tripPDA);
+    const tripBAccount = await program.account.trip.fetch(tripBResult.tripPDA);
+    expect(matchRecord.status).to.have.property("completed");
+    expect(tripAAccount.matchCount).to.equal(1);
+    expect(tripBAccount.matchCount).to.equal(1);
 
     console.log("\n✨ MPC computation completed successfully!");
     console.log("   The encrypted trip data was processed without revealing sensitive information!");
   });
 
-  async function initComputeTripMatchCompDef(
-    program: Program<Triper>,
-    owner: anchor.web3.Keypair,
-    uploadRawCircuit: boolean,
-    offchainSource: boolean
-  ): Promise<string> {
-    const baseSeedCompDefAcc = getArciumAccountBaseSeed(
-      "ComputationDefinitionAccount"
-    );
-    const offset = getCompDefAccOffset("compute_trip_match");
+  /**
+   * Run `fn` with the provider wallet swapped to `user`
+   * (the web client helpers sign with the provider wallet)
+   */
+  const withWallet = async <T>(user: anchor.web3.Keypair, fn: () => Promise<T>): Promise<T> => {
+    const originalWallet = (provider as any).wallet;
+    (provider as any).wallet = {
+      publicKey: user.publicKey,
+      signTransaction: async (tx: any) => {
+        tx.partialSign(user);
+        return tx;
+      },
+      signAllTransactions: async (txs: any[]) => {
+        txs.forEach(tx => tx.partialSign(user));
+        return txs;
+      },
+    };
+    try {
+      return await fn();
+    } finally {
+      (provider as any).wallet = originalWallet;
+    }
+  };
+
+  /**
+   * Encrypted profile + trip for `user`, created through the web client
+   */
+  const setupEncryptedUser = async (
+    user: anchor.web3.Keypair,
+    userData: ReturnType<typeof createSampleUserData>,
+    tripData: ReturnType<typeof createSampleTripData>,
+    minInterests: number,
+    age: number = 0,
+    dailyBudget: number = 0,
+    seeking: InterestTag[] = []
+  ) =>
+    withWallet(user, async () => {
+      const mxePublicKey = await getMXEPublicKeyWithRetry(
+        provider as anchor.AnchorProvider,
+        program.programId
+      );
+      const privateKey = x25519.utils.randomPrivateKey();
+      const publicKey = x25519.getPublicKey(privateKey);
+      const cipher = new RescueCipher(x25519.getSharedSecret(privateKey, mxePublicKey));
+      const profile = await createOrUpdateUserProfile(
+        program,
+        provider as anchor.AnchorProvider,
+        cipher,
+        publicKey,
+        userData.interests,
+        userData.displayName,
+        userData.bio
+      );
+      const trip = await createTrip(
+        program,
+        provider as anchor.AnchorProvider,
+        tripData.waypoints,
+        tripData.destination,
+        tripData.startDate,
+        tripData.endDate,
+        minInterests,
+        age,
+        dailyBudget,
+        seeking
+      );
+      return {
+        userProfilePDA: profile.userProfilePDA,
+        profileNonce: profile.nonce,
+        profileCipher: cipher,
+        tripPDA: trip.tripPDA,
+        tripNonce: trip.nonce,
+      };
+    });
 
-    const compDefPDA = PublicKey.findProgramAddressSync(
-      [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
-      getArciumProgAddress()
-    )[0];
+  /**
+   * Full encrypted match between two fresh users:
+   * user A declares 3 interests, user B declares 2 (see createSampleUserData)
+   */
+  const runEncryptedMatch = async (
+    minInterestsA: number,
+    minInterestsB: number,
+    scoringProfile: ScoringProfile = { balanced: {} },
+    encryptScores: boolean = false,
+    ages: [number, number] = [0, 0],
+    budgets: [number, number] = [0, 0],
+    budgetWeight: number = 0,
+    seeking: [InterestTag[], InterestTag[]] = [[], []],
+    destinationOnly: [boolean, boolean] = [false, false],
+    tripDataB: ReturnType<typeof createVariantTripData> = createVariantTripData(),
+    tripDataA: ReturnType<typeof createSampleTripData> = createSampleTripData()
+  ) => {
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+
+    const a = await setupEncryptedUser(
+      userA, createSampleUserData("userA"), tripDataA, minInterestsA, ages[0], budgets[0], seeking[0]
+    );
+    const b = await setupEncryptedUser(
+      userB, createSampleUserData("userB"), tripDataB, minInterestsB, ages[1], budgets[1], seeking[1]
+    );
+    for (const [user, trip, enabled] of [[userA, a.tripPDA, destinationOnly[0]], [userB, b.tripPDA, destinationOnly[1]]] as const) {
+      if (!enabled) continue;
+      await program.methods
+        .setTripDestinationOnly(true)
+        .accountsPartial({ trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+    const matchRecord = await initiateRawMatch(program, userA, a.tripPDA, b.tripPDA, {
+      scoringProfile,
+      encryptScores,
+      budgetWeight,
+    });
 
-    console.log("Comp def PDA:", compDefPDA.toBase58());
+    // Size the compute budget from the on-chain estimate
+    const sizing = await program.methods
+      .estimateMatchArgs()
+      .accountsPartial({ tripA: a.tripPDA, tripB: b.tripPDA })
+      .simulate();
+    const { recommendedComputeUnits } = sizing.events.find(
+      (e) => e.name === "matchArgsEstimate"
+    ).data;
 
-    const sig = await program.methods
-      .initComputeTripMatchCompDef()
-      .accounts({
-        compDefAccount: compDefPDA,
-        payer: owner.publicKey,
+    const matchEventPromise = awaitEvent("matchComputedEvent");
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    await program.methods
+      .computeTripMatch(
+        computationOffset,
+        new anchor.BN(deserializeLE(a.tripNonce).toString()),
+        new anchor.BN(deserializeLE(b.tripNonce).toString()),
+        new anchor.BN(deserializeLE(a.profileNonce).toString()),
+        new anchor.BN(deserializeLE(b.profileNonce).toString())
+      )
+      .accountsPartial({
+        payer: userA.publicKey,
+        computationAccount: getComputationAccAddress(program.programId, computationOffset),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
         mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("compute_trip_match")).readUInt32LE()
+        ),
+        matchRecord,
+        tripA: a.tripPDA,
+        tripB: b.tripPDA,
+        userProfileA: a.userProfilePDA,
+        userProfileB: b.userProfilePDA,
       })
-      .signers([owner])
-      .rpc({
-        commitment: "confirmed",
-      });
-    
-    console.log("Init computation definition transaction:", sig);
-
-    if (uploadRawCircuit) {
-      console.log("Uploading raw circuit file...");
-      
-      // Try different possible circuit file names
-      let rawCircuit: Buffer | undefined;
-      const possiblePaths = [
-        "build/compute_trip_match_localnet.arcis",
-        "build/compute_trip_match_testnet.arcis",
-        "build/compute_trip_match.arcis",
-      ];
-      
-      for (const path of possiblePaths) {
-        if (fs.existsSync(path)) {
-          console.log(`Found circuit at ${path}`);
-          rawCircuit = fs.readFileSync(path);
-          break;
-        }
-      }
-      
-      if (!rawCircuit) {
-        throw new Error("Circuit file not found. Please build with 'arcium build' first.");
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: recommendedComputeUnits }),
+      ])
+      .signers([userA])
+      .rpc();
+
+    await awaitComputationFinalization(
+      provider as anchor.AnchorProvider,
+      computationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const event = await matchEventPromise;
+    return {
+      event,
+      computationOffset,
+      matchRecord,
+      userA,
+      userB,
+      tripA: a.tripPDA,
+      tripB: b.tripPDA,
+      profileNonceA: a.profileNonce,
+      profileNonceB: b.profileNonce,
+      profileCipherA: a.profileCipher,
+      profileCipherB: b.profileCipher,
+      userProfileA: a.userProfilePDA,
+      userProfileB: b.userProfilePDA,
+    };
+  };
+
+  /**
+   * Trip to the same destination as createSampleTripData (Los Angeles) that
+   * stays inside the city, sharing no cell - or level-6 parent - with the
+   * sample's coastal route
+   */
+  const cityTripData = (startDateOffset?: number) => ({
+    ...createVariantTripData(startDateOffset),
+    waypoints: [
+      { lat: 34.1478, lng: -118.1445, name: "Pasadena" },
+      { lat: 34.0407, lng: -118.2468, name: "Downtown LA" },
+      { lat: 33.7701, lng: -118.1937, name: "Long Beach" },
+    ],
+  });
+
+  it("Excludes interests from the total when a party is below the minimum count", async () => {
+    // Trip A requires 3 interests; user B only declares 2
+    const { event } = await runEncryptedMatch(3, 0);
+
+    const expectedTotal = Math.floor((event.routeScore * 40 + event.dateScore * 35) / 75);
+    expect(event.totalScore).to.equal(expectedTotal);
+    console.log("✅ Below-threshold interests dropped from the total");
+  });
+
+  it("Matches destination-only trips on destination and dates despite zero route overlap", async () => {
+    const cityTrip = cityTripData();
+    const NEUTRAL_ROUTE_SCORE = 50;
+
+    const { event } = await runEncryptedMatch(
+      0, 0, { balanced: {} }, false, [0, 0], [0, 0], 0, [[], []], [true, true], cityTrip
+    );
+    expect(event.coverageScore).to.equal(0); // Routes really are disjoint
+    expect(event.routeScore).to.equal(NEUTRAL_ROUTE_SCORE);
+
+    // Balanced weights with the route weight dropped: 35% dates, 25% interests
+    const expectedTotal = Math.floor((event.dateScore * 35 + event.interestScore * 25) / 60);
+    expect(event.totalScore).to.equal(expectedTotal);
+
+    // Opting in on one side only keeps route scoring
+    const oneSided = await runEncryptedMatch(
+      0, 0, { balanced: {} }, false, [0, 0], [0, 0], 0, [[], []], [true, false], cityTrip
+    );
+    expect(oneSided.event.routeScore).to.equal(0);
+    expect(oneSided.event.totalScore).to.equal(
+      Math.floor((oneSided.event.dateScore * 35 + oneSided.event.interestScore * 25) / 100)
+    );
+    console.log("✅ Destination-only pair scored on destination and dates alone");
+  });
+
+  it("Scores a short route inside a long one as full coverage but low similarity", async () => {
+    // Ten stops ~11 km apart down the Salinas Valley: every stop lands in its
+    // own level-7 cell under its own level-6 parent
+    const longRoute = Array.from({ length: 10 }, (_, i) => ({
+      lat: 36.9 - i * 0.1,
+      lng: -121.6 + i * 0.05,
+      name: `Stop ${i + 1}`,
+    }));
+    const shortTrip = { ...createSampleTripData(), waypoints: longRoute.slice(3, 5) };
+    const longTrip = { ...createVariantTripData(), waypoints: longRoute };
+
+    const { event } = await runEncryptedMatch(
+      0, 0, { balanced: {} }, false, [0, 0], [0, 0], 0, [[], []], [false, false], longTrip, shortTrip
+    );
+
+    // Both short-route cells are on the long route: 2 / min(2, 10)
+    expect(event.coverageScore).to.equal(100);
+    // 2 exact matches over a union of 10 cells, no parent-only credit
+    expect(event.routeScore).to.equal(20);
+    console.log("✅ Contained route scored coverage 100, route 20");
+  });
+
+  it("Counts interests in the total when both parties meet the minimum count", async () => {
+    // Both users declare at least 2 interests
+    const { event } = await runEncryptedMatch(2, 2);
+
+    const expectedTotal = Math.floor(
+      (event.routeScore * 40 + event.dateScore * 35 + event.interestScore * 25) / 100
+    );
+    expect(event.totalScore).to.equal(expectedTotal);
+    console.log("✅ At-threshold interests included in the total");
+  });
+
+  it("Emits the canonical MatchComputedEvent from the callback", async () => {
+    const { event, computationOffset } = await runEncryptedMatch(0, 0);
+
+    // Exactly the fields of the one definition in events.rs
+    expect(Object.keys(event).sort()).to.deep.equal([
+      "ageScore",
+      "budgetScore",
+      "computationAccount",
+      "coverageScore",
+      "dateScore",
+      "interestScore",
+      "routeScore",
+      "scoresEncrypted",
+      "scoringVersion",
+      "totalScore",
+    ]);
+    expect(event.computationAccount.toBase58()).to.equal(
+      getComputationAccAddress(program.programId, computationOffset).toBase58()
+    );
+    expect(event.scoresEncrypted).to.be.false;
+    console.log("✅ MatchComputedEvent carries the expected fields");
+  });
+
+  it("Emits MatchComputationCompleted with the match's trips and computation", async () => {
+    const completedPromise = awaitEvent("matchComputationCompleted");
+    const { event, computationOffset, matchRecord, tripA, tripB } = await runEncryptedMatch(0, 0);
+    const completed = await completedPromise;
+
+    expect(completed.matchRecord.toBase58()).to.equal(matchRecord.toBase58());
+    expect(completed.tripA.toBase58()).to.equal(tripA.toBase58());
+    expect(completed.tripB.toBase58()).to.equal(tripB.toBase58());
+
+    // The computation ID is the computation account, also stored on the record
+    const computationAccount = getComputationAccAddress(program.programId, computationOffset);
+    expect(Buffer.from(completed.computationId).equals(computationAccount.toBuffer())).to.be.true;
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(Buffer.from(record.computationId).equals(computationAccount.toBuffer())).to.be.true;
+
+    expect(completed.routeScore).to.equal(event.routeScore);
+    expect(completed.dateScore).to.equal(event.dateScore);
+    expect(completed.interestScore).to.equal(event.interestScore);
+    expect(completed.totalScore).to.equal(event.totalScore);
+    expect(completed.weights).to.deep.equal(record.weights);
+    expect(completed.interestFreshness).to.equal(record.interestFreshness);
+    expect(completed.timestamp.toNumber()).to.be.greaterThan(0);
+    console.log("✅ Completed event carries trip keys and computation ID");
+  });
+
+  it("Stamps the current scoring version on computed matches", async () => {
+    const SCORING_VERSION = 10;
+    const { event, matchRecord } = await runEncryptedMatch(0, 0);
+
+    expect(event.scoringVersion).to.equal(SCORING_VERSION);
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.scoringVersion).to.equal(SCORING_VERSION);
+    console.log(`✅ Scored with algorithm version ${record.scoringVersion}`);
+  });
+
+  it("Keeps the admin-set interest matrix symmetric", async () => {
+    const config = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+    const [interestMatrix] = PublicKey.findProgramAddressSync(
+      [Buffer.from("interest_matrix")],
+      program.programId
+    );
+    const setRelated = (interest: number, mask: number) =>
+      program.methods
+        .setInterestRelatedness(interest, mask)
+        .accountsPartial({ config, interestMatrix, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+    try {
+      // Hiking (0) and camping (5) related to food (2); food is never related to itself
+      await setRelated(2, (1 << 0) | (1 << 2) | (1 << 5));
+      const matrix = await program.account.interestMatrix.fetch(interestMatrix);
+      expect(matrix.related[2]).to.equal((1 << 0) | (1 << 5));
+      expect(matrix.related[0] & (1 << 2)).to.not.equal(0);
+      expect(matrix.related[5] & (1 << 2)).to.not.equal(0);
+
+      // Shrinking the row clears the mirrored bits too
+      await setRelated(2, 1 << 0);
+      const shrunk = await program.account.interestMatrix.fetch(interestMatrix);
+      expect(shrunk.related[5] & (1 << 2)).to.equal(0);
+      expect(shrunk.related[0] & (1 << 2)).to.not.equal(0);
+
+      try {
+        await setRelated(32, 1);
+        expect.fail("interest index 32 should be rejected");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("InvalidInterestIndex");
       }
 
+      const outsider = await createFundedUser(provider as anchor.AnchorProvider);
       try {
-        await uploadCircuit(
-          provider as anchor.AnchorProvider,
-          "compute_trip_match",
-          program.programId,
-          rawCircuit,
-          true
-        );
-        console.log("✅ Circuit uploaded successfully!");
-      } catch (uploadError: any) {
-        console.log("⚠️  Circuit upload completed (may already exist):", uploadError.message);
+        await program.methods
+          .setInterestRelatedness(2, 0)
+          .accountsPartial({ config, interestMatrix, admin: outsider.publicKey })
+          .signers([outsider])
+          .rpc();
+        expect.fail("only the config admin may edit the matrix");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("Unauthorized");
       }
-      
-      // Always finalize after upload attempt
-      console.log("Finalizing computation definition...");
-      
-      const finalizeTx = await buildFinalizeCompDefTx(
-        provider as anchor.AnchorProvider,
-        Buffer.from(offset).readUInt32LE(),
-        program.programId
+      console.log("✅ Interest matrix initialized and kept symmetric");
+    } finally {
+      await setRelated(2, 0);
+    }
+  });
+
+  it("Credits interests the matrix relates to the other party's", async () => {
+    const config = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+    const [interestMatrix] = PublicKey.findProgramAddressSync(
+      [Buffer.from("interest_matrix")],
+      program.programId
+    );
+    const setRelated = (interest: number, mask: number) =>
+      program.methods
+        .setInterestRelatedness(interest, mask)
+        .accountsPartial({ config, interestMatrix, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+    // A: hiking, photography, food; B: hiking, photography
+    await setRelated(2, 0);
+    const plain = await runEncryptedMatch(0, 0);
+    expect(plain.event.interestScore).to.equal(66); // 2 shared / 3 total
+
+    try {
+      // Food (A only) related to hiking (held by B) earns half credit
+      await setRelated(2, 1 << 0);
+      const related = await runEncryptedMatch(0, 0);
+      expect(related.event.interestScore).to.equal(83); // (2 + 0.5) / 3
+      console.log(
+        `✅ Interest score ${plain.event.interestScore} -> ${related.event.interestScore} with food ~ hiking`
       );
+    } finally {
+      await setRelated(2, 0);
+    }
+  });
+
+  it("Penalizes interests the incompatibility matrix flags as clashing", async () => {
+    const config = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+    const [incompatibilityMatrix] = PublicKey.findProgramAddressSync(
+      [Buffer.from("incompatibility_matrix")],
+      program.programId
+    );
+    const setIncompatible = (interest: number, mask: number) =>
+      program.methods
+        .setInterestIncompatibility(interest, mask)
+        .accountsPartial({ config, incompatibilityMatrix, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
 
-      const latestBlockhash = await provider.connection.getLatestBlockhash();
-      finalizeTx.recentBlockhash = latestBlockhash.blockhash;
-      finalizeTx.lastValidBlockHeight = latestBlockhash.lastValidBlockHeight;
+    // A: hiking, photography, food; B: hiking, photography
+    await setIncompatible(2, 0);
+    await setIncompatible(10, 0);
+    const plain = await runEncryptedMatch(0, 0);
+    expect(plain.event.interestScore).to.equal(66); // 2 shared / 3 total
 
-      finalizeTx.sign(owner);
+    try {
+      // A clash neither party holds leaves the score alone
+      await setIncompatible(10, 1 << 11);
+      const matrix = await program.account.incompatibilityMatrix.fetch(incompatibilityMatrix);
+      expect(matrix.incompatible[11] & (1 << 10)).to.not.equal(0);
+      const compatible = await runEncryptedMatch(0, 0);
+      expect(compatible.event.interestScore).to.equal(66);
 
-      await provider.sendAndConfirm!(finalizeTx);
-      console.log("✅ Computation definition finalized!");
-    } else if (!offchainSource) {
-      console.log("Finalizing computation definition...");
-      
-      const finalizeTx = await buildFinalizeCompDefTx(
-        provider as anchor.AnchorProvider,
-        Buffer.from(offset).readUInt32LE(),
-        program.programId
+      // Food (A only) clashes with hiking (held by B): one clash, -25 points
+      await setIncompatible(2, 1 << 0);
+      const clashing = await runEncryptedMatch(0, 0);
+      expect(clashing.event.interestScore).to.equal(41);
+      console.log(
+        `✅ Interest score ${plain.event.interestScore} -> ${clashing.event.interestScore} with food x hiking`
       );
+    } finally {
+      await setIncompatible(2, 0);
+      await setIncompatible(10, 0);
+    }
+  });
+
+  it("Applies each scoring profile's documented weights", async () => {
+    // (route, date, interest) weights; both sample users declare interests,
+    // so no weight is redistributed
+    const profiles: [ScoringProfile, number, number, number][] = [
+      [{ balanced: {} }, 40, 35, 25],
+      [{ dateFocused: {} }, 25, 55, 20],
+      [{ routeFocused: {} }, 60, 25, 15],
+      [{ interestFocused: {} }, 25, 25, 50],
+    ];
 
-      const latestBlockhash = await provider.connection.getLatestBlockhash();
-      finalizeTx.recentBlockhash = latestBlockhash.blockhash;
-      finalizeTx.lastValidBlockHeight = latestBlockhash.lastValidBlockHeight;
+    for (const [profile, routeW, dateW, interestW] of profiles) {
+      const { event, matchRecord } = await runEncryptedMatch(0, 0, profile);
 
-      finalizeTx.sign(owner);
+      const expectedTotal = Math.floor(
+        (event.routeScore * routeW + event.dateScore * dateW + event.interestScore * interestW) / 100
+      );
+      expect(event.totalScore).to.equal(expectedTotal);
 
-      await provider.sendAndConfirm!(finalizeTx);
-      console.log("✅ Computation definition finalized!");
+      const record = await program.account.matchRecord.fetch(matchRecord);
+      expect(record.scoringProfile).to.have.property(Object.keys(profile)[0]);
+      // The record carries the weights its total was computed with
+      expect(record.weights).to.deep.equal([routeW, dateW, interestW]);
+      console.log(`✅ ${Object.keys(profile)[0]}: total ${event.totalScore}`);
     }
-    
-    return sig;
-  }
-});
+  });
 
-function readKpJson(path: string): anchor.web3.Keypair {
-  const file = fs.readFileSync(path);
-  return anchor.web3.Keypair.fromSecretKey(
-    new Uint8Array(JSON.parse(file.toString()))
-  );
-}
+  it("Scores age compatibility only when both trips disclose an age", async () => {
+    const AGE_WEIGHT = 15;
+
+    // Undisclosed ages leave the total as the profile's three-way average
+    const undisclosed = await runEncryptedMatch(0, 0);
+    expect(undisclosed.event.ageScore).to.equal(0);
+
+    // 10 years apart with a 20-year falloff: half credit
+    const close = await runEncryptedMatch(0, 0, { balanced: {} }, false, [30, 40]);
+    expect(close.event.ageScore).to.equal(50);
+    const { routeScore, dateScore, interestScore, ageScore } = close.event;
+    expect(close.event.totalScore).to.equal(
+      Math.floor(
+        (routeScore * 40 + dateScore * 35 + interestScore * 25 + ageScore * AGE_WEIGHT) / (100 + AGE_WEIGHT)
+      )
+    );
+    const record = await program.account.matchRecord.fetch(close.matchRecord);
+    expect(record.ageScore).to.equal(50);
+
+    // Equal ages score 100; a gap past the falloff scores 0
+    const same = await runEncryptedMatch(0, 0, { balanced: {} }, false, [35, 35]);
+    expect(same.event.ageScore).to.equal(100);
+    const far = await runEncryptedMatch(0, 0, { balanced: {} }, false, [20, 60]);
+    expect(far.event.ageScore).to.equal(0);
+    console.log(`✅ Age scores: 30/40 -> ${ageScore}, 35/35 -> 100, 20/60 -> 0`);
+  });
+
+  it("Scores budget compatibility only when the match weights it", async () => {
+    const BUDGET_WEIGHT = 20;
+
+    // Budgets alone don't count: the default budget weight is 0
+    const unweighted = await runEncryptedMatch(0, 0, { balanced: {} }, false, [0, 0], [10000, 10500]);
+    expect(unweighted.event.budgetScore).to.equal(0);
+    const unweightedRecord = await program.account.matchRecord.fetch(unweighted.matchRecord);
+    expect(unweightedRecord.budgetWeight).to.equal(0);
+
+    // Within 10% of each other: full credit
+    const close = await runEncryptedMatch(
+      0, 0, { balanced: {} }, false, [0, 0], [10000, 10500], BUDGET_WEIGHT
+    );
+    expect(close.event.budgetScore).to.equal(100);
+    const { routeScore, dateScore, interestScore, budgetScore } = close.event;
+    expect(close.event.totalScore).to.equal(
+      Math.floor(
+        (routeScore * 40 + dateScore * 35 + interestScore * 25 + budgetScore * BUDGET_WEIGHT) /
+          (100 + BUDGET_WEIGHT)
+      )
+    );
+    const record = await program.account.matchRecord.fetch(close.matchRecord);
+    expect(record.budgetWeight).to.equal(BUDGET_WEIGHT);
+    expect(record.budgetScore).to.equal(100);
+
+    // Half the other's budget: ratio 50% scaled by 100/90
+    const apart = await runEncryptedMatch(
+      0, 0, { balanced: {} }, false, [0, 0], [5000, 10000], BUDGET_WEIGHT
+    );
+    expect(apart.event.budgetScore).to.equal(55);
+
+    // An undisclosed budget drops the component even with a weight
+    const undisclosed = await runEncryptedMatch(
+      0, 0, { balanced: {} }, false, [0, 0], [0, 10000], BUDGET_WEIGHT
+    );
+    expect(undisclosed.event.budgetScore).to.equal(0);
+    expect(undisclosed.event.totalScore).to.equal(
+      Math.floor(
+        (undisclosed.event.routeScore * 40 +
+          undisclosed.event.dateScore * 35 +
+          undisclosed.event.interestScore * 25) /
+          100
+      )
+    );
+    console.log("✅ Budget scores: 10000/10500 -> 100, 5000/10000 -> 55, undisclosed -> 0");
+  });
+
+  it("Scores interests lower when only one side has what the other seeks", async () => {
+    const match = (seeking: [InterestTag[], InterestTag[]]) =>
+      runEncryptedMatch(0, 0, { balanced: {} }, false, [0, 0], [0, 0], 0, seeking);
+
+    // No seeking preferences: the symmetric interest score alone
+    const { event: baseline } = await match([[], []]);
+
+    // A seeks food, which B lacks; B seeks nothing. One-sided, so the
+    // mutual score is 0 and halves the interest score
+    const { event: oneSided } = await match([[InterestTag.FOOD], []]);
+    expect(oneSided.interestScore).to.equal(Math.floor(baseline.interestScore / 2));
+
+    // A seeks hiking (B has it), B seeks food (A has it): mutual 100
+    const { event: mutual } = await match([[InterestTag.HIKING], [InterestTag.FOOD]]);
+    expect(mutual.interestScore).to.equal(Math.floor((baseline.interestScore + 100) / 2));
+    expect(mutual.totalScore).to.be.greaterThan(oneSided.totalScore);
+    console.log(
+      `✅ Interest scores: no preference ${baseline.interestScore}, ` +
+        `one-sided ${oneSided.interestScore}, mutual ${mutual.interestScore}`
+    );
+  });
+
+  it("Rejects budget weights above MAX_BUDGET_WEIGHT", async () => {
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+
+    try {
+      await initiateRawMatch(program, userA, tripA, tripB, { budgetWeight: 101 });
+      expect.fail("Should have rejected the budget weight");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidBudgetWeight");
+      console.log("✅ Budget weight above 100 rejected");
+    }
+  });
+
+  it("Down-weights stale interests once interest decay is configured", async () => {
+    const config = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+    const setHalfLife = (secs: number) =>
+      program.methods
+        .setInterestDecay(new anchor.BN(secs))
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc();
+
+    try {
+      // A year-long half-life leaves freshly written interests at full weight
+      await setHalfLife(365 * 24 * 60 * 60);
+      const fresh = await runEncryptedMatch(0, 0);
+      const freshRecord = await program.account.matchRecord.fetch(fresh.matchRecord);
+      expect(freshRecord.interestFreshness).to.equal(100);
+      expect(fresh.event.totalScore).to.equal(
+        Math.floor(
+          (fresh.event.routeScore * 40 + fresh.event.dateScore * 35 + fresh.event.interestScore * 25) / 100
+        )
+      );
+
+      // The localnet clock cannot be warped, so a 1s half-life stands in for
+      // year-old interests: by queue time several half-lives have elapsed
+      await setHalfLife(1);
+      const stale = await runEncryptedMatch(0, 0);
+      const staleRecord = await program.account.matchRecord.fetch(stale.matchRecord);
+      expect(staleRecord.interestFreshness).to.be.at.most(50);
+
+      const interestW = Math.floor((25 * staleRecord.interestFreshness) / 100);
+      expect(stale.event.totalScore).to.equal(
+        Math.floor(
+          (stale.event.routeScore * 40 + stale.event.dateScore * 35 + stale.event.interestScore * interestW) /
+            (75 + interestW)
+        )
+      );
+      console.log(`✅ Stale interests weighted at ${staleRecord.interestFreshness}%`);
+    } finally {
+      await setHalfLife(0);
+    }
+  });
+
+  it("Stores encrypted scores only the participants can decrypt", async () => {
+    // Same inputs in plaintext mode give the reference scores
+    const { event: plainEvent } = await runEncryptedMatch(0, 0);
+
+    const { event, matchRecord, profileCipherA, profileCipherB } =
+      await runEncryptedMatch(0, 0, { balanced: {} }, true);
+    expect(event.scoresEncrypted).to.be.true;
+    expect(event.totalScore).to.equal(0);
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.encryptScores).to.be.true;
+    expect(record.routeScore).to.equal(0);
+    expect(record.dateScore).to.equal(0);
+    expect(record.interestScore).to.equal(0);
+    expect(record.ageScore).to.equal(0);
+    expect(record.budgetScore).to.equal(0);
+    expect(record.totalScore).to.equal(0);
+    expect(record.coverageScore).to.equal(0);
+    // Status still follows the threshold, checked inside MPC
+    expect(record.status).to.have.property(plainEvent.totalScore >= 20 ? "completed" : "rejected");
+    console.log("✅ Public score fields withheld in encrypted mode");
+
+    const expected = {
+      routeScore: plainEvent.routeScore,
+      dateScore: plainEvent.dateScore,
+      interestScore: plainEvent.interestScore,
+      ageScore: plainEvent.ageScore,
+      budgetScore: plainEvent.budgetScore,
+      totalScore: plainEvent.totalScore,
+      coverageScore: plainEvent.coverageScore,
+    };
+    expect(decryptMatchScores(record.encryptedScoresA, profileCipherA)).to.deep.equal(expected);
+    expect(decryptMatchScores(record.encryptedScoresB, profileCipherB)).to.deep.equal(expected);
+    console.log("✅ Both participants decrypt their copy with their own key");
+
+    // An outsider's key (or the other participant's copy) yields garbage
+    const mxePublicKey = await getMXEPublicKeyWithRetry(
+      provider as anchor.AnchorProvider,
+      program.programId
+    );
+    const outsiderCipher = new RescueCipher(
+      x25519.getSharedSecret(x25519.utils.randomPrivateKey(), mxePublicKey)
+    );
+    expect(decryptMatchScores(record.encryptedScoresA, outsiderCipher)).to.not.deep.equal(expected);
+    expect(decryptMatchScores(record.encryptedScoresA, profileCipherB)).to.not.deep.equal(expected);
+    console.log("✅ Scores unreadable without the participant key");
+  });
+
+  it("Keeps plaintext scores and no encrypted copies by default", async () => {
+    const { event, matchRecord } = await runEncryptedMatch(0, 0);
+    expect(event.scoresEncrypted).to.be.false;
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.encryptScores).to.be.false;
+    expect(record.totalScore).to.equal(event.totalScore);
+    expect(record.encryptedScoresA.nonce.toString()).to.equal("0");
+    expect(record.encryptedScoresB.nonce.toString()).to.equal("0");
+    console.log("✅ Plaintext mode unchanged");
+  });
+
+  it("Increments both participants' total_matches once when a match completes", async () => {
+    const { event, matchRecord, userProfileA, userProfileB } = await runEncryptedMatch(0, 0);
+    expect(event.totalScore).to.be.at.least(MIN_TOTAL_SCORE); // Completed, not auto-rejected
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("completed");
+
+    // Fresh profiles start at 0, so exactly one increment each
+    const profileA = await program.account.userProfile.fetch(userProfileA);
+    const profileB = await program.account.userProfile.fetch(userProfileB);
+    expect(profileA.totalMatches).to.equal(1);
+    expect(profileB.totalMatches).to.equal(1);
+    console.log("✅ Both profiles counted the completed match once");
+  });
+
+  it("Auto-rejects a low-scoring match and refunds both trips' quota once", async () => {
+    // Disjoint routes and dates overlapping by under two hours score route 0
+    // and date 0; route-focused weights cap interests at 15% of the total,
+    // so even a perfect interest score stays below MIN_TOTAL_SCORE
+    const { event, matchRecord, userA, tripA, tripB, userProfileA, userProfileB } = await runEncryptedMatch(
+      0, 0, { routeFocused: {} }, false, [0, 0], [0, 0], 0, [[], []], [false, false],
+      cityTripData(14 * 24 * 60 * 60 - 6400)
+    );
+    expect(event.totalScore).to.be.below(20);
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("rejected");
+    expect((await program.account.trip.fetch(tripA)).matchCount).to.equal(0);
+    expect((await program.account.trip.fetch(tripB)).matchCount).to.equal(0);
+
+    // A settled record can't be queued again, so the refund can't repeat
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const nonce = () => new anchor.BN(deserializeLE(randomBytes(16)).toString());
+    try {
+      await program.methods
+        .computeTripMatch(computationOffset, nonce(), nonce(), nonce(), nonce())
+        .accountsPartial({
+          payer: userA.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("compute_trip_match")).readUInt32LE()
+          ),
+          matchRecord,
+          tripA,
+          tripB,
+          userProfileA,
+          userProfileB,
+        })
+        .signers([userA])
+        .rpc();
+      expect.fail("re-queueing a rejected match should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidMatchStatus");
+    }
+    expect((await program.account.trip.fetch(tripA)).matchCount).to.equal(0);
+    console.log("✅ Low score auto-rejected, quota refunded exactly once");
+  });
+
+  it("Keeps a high-scoring match Completed and its quota consumed", async () => {
+    const { event, matchRecord, tripA, tripB } = await runEncryptedMatch(0, 0);
+    expect(event.totalScore).to.be.at.least(MIN_TOTAL_SCORE);
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("completed");
+    expect((await program.account.trip.fetch(tripA)).matchCount).to.equal(1);
+    expect((await program.account.trip.fetch(tripB)).matchCount).to.equal(1);
+    console.log("✅ Quality match kept, quota consumed");
+  });
+
+  it("Sets an acceptance deadline when scores land and accepts before it", async () => {
+    const { event, matchRecord, userA, userB, tripA, tripB } = await runEncryptedMatch(0, 0);
+    expect(event.totalScore).to.be.at.least(MIN_TOTAL_SCORE); // Completed, not auto-rejected
+
+    const ACCEPT_WINDOW_SECS = 7 * 24 * 60 * 60;
+    let record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("completed");
+    const now = Math.floor(Date.now() / 1000);
+    expect(record.acceptDeadline.toNumber()).to.be.closeTo(now + ACCEPT_WINDOW_SECS, 120);
+
+    // Cannot be expired while the window is open
+    try {
+      await program.methods
+        .expireMatch()
+        .accountsPartial({ matchAccount: matchRecord, cranker: userA.publicKey, initiatorProfile: null })
+        .signers([userA])
+        .rpc({ commitment: "confirmed" });
+      throw new Error("Should have failed: deadline not reached");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("MatchNotExpired");
+    }
+
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("mutual");
+    console.log("✅ Accepted within the deadline");
+  });
+
+  it("Restores a rejected scored match to Completed and lets it go Mutual", async () => {
+    const { matchRecord, userA, userB, tripA, tripB, userProfileA } = await runEncryptedMatch(0, 0);
+    let record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("completed");
+    const rejectionsBefore = (await program.account.userProfile.fetch(userProfileA)).rejectionsReceived;
+
+    // A scored match can be declined and, within the grace window, restored
+    await program.methods
+      .rejectMatch()
+      .accountsPartial({
+        matchAccount: matchRecord,
+        trip: tripB,
+        user: userB.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: userProfileA,
+        ownerProfileB: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+    expect((await program.account.userProfile.fetch(userProfileA)).rejectionsReceived).to.equal(rejectionsBefore + 1);
+
+    await program.methods
+      .undoReject()
+      .accountsPartial({
+        matchAccount: matchRecord,
+        trip: tripB,
+        user: userB.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: userProfileA,
+        ownerProfileB: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+    // The undone rejection no longer counts against trip A's owner
+    expect((await program.account.userProfile.fetch(userProfileA)).rejectionsReceived).to.equal(rejectionsBefore);
+    record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("completed");
+
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+    record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("mutual");
+    console.log("✅ Completed → Rejected → Completed → Mutual");
+  });
+
+  it("Closes a mutual match only with both owners' signatures", async () => {
+    const { matchRecord, userA, userB, tripA, tripB } = await runEncryptedMatch(0, 0);
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    // One owner alone can't close it
+    try {
+      await program.methods
+        .closeMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip: tripB,
+          pairHistory: derivePairHistoryPDA(program.programId, tripA, tripB),
+          rentPayer: userA.publicKey,
+          user: userB.publicKey,
+        })
+        .signers([userB])
+        .rpc();
+      expect.fail("a single owner should not close a mutual match");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidMatchStatus");
+    }
+
+    const rent = await provider.connection.getBalance(matchRecord);
+    const funderBefore = await provider.connection.getBalance(userA.publicKey);
+    const closedEventPromise = awaitEvent("matchClosed");
+    await program.methods
+      .closeMutualMatch()
+      .accountsPartial({
+        matchAccount: matchRecord,
+        ownerA: userA.publicKey,
+        ownerB: userB.publicKey,
+        rentPayer: userA.publicKey,
+      })
+      .signers([userA, userB])
+      .rpc({ commitment: "confirmed" });
+    const closedEvent = await closedEventPromise;
+
+    expect(closedEvent.lamports.toNumber()).to.equal(rent);
+    expect((await provider.connection.getBalance(userA.publicKey)) - funderBefore).to.equal(rent);
+    expect(await provider.connection.getAccountInfo(matchRecord)).to.be.null;
+    console.log("✅ Mutual match closed by both owners, rent refunded");
+  });
+
+  it("Reveals only the interests both parties of a mutual match share", async () => {
+    const owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
+    await initCompDef(
+      program,
+      provider as anchor.AnchorProvider,
+      owner,
+      "reveal_shared_interests",
+      () => program.methods.initRevealSharedInterestsCompDef()
+    );
+
+    // User A: hiking, photography, food; user B: hiking, photography
+    const { matchRecord, userA, userB, tripA, tripB, profileNonceA, profileNonceB } =
+      await runEncryptedMatch(0, 0);
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    const submitRevealKey = (user: anchor.web3.Keypair, trip: PublicKey) =>
+      program.methods
+        .submitRevealKey(Array.from(randomBytes(32)))
+        .accountsPartial({ matchRecord, trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+
+    const queueReveal = (computationOffset: anchor.BN) =>
+      program.methods
+        .revealSharedInterests(
+          computationOffset,
+          new anchor.BN(deserializeLE(profileNonceA).toString()),
+          new anchor.BN(deserializeLE(profileNonceB).toString())
+        )
+        .accountsPartial({
+          payer: userB.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("reveal_shared_interests")).readUInt32LE()
+          ),
+          matchRecord,
+          tripA,
+          tripB,
+        })
+        .signers([userB])
+        .rpc({ commitment: "confirmed" });
+
+    // Mutual acceptance alone is not enough, and neither is one reveal key
+    await submitRevealKey(userB, tripB);
+    try {
+      await queueReveal(new anchor.BN(randomBytes(8), "hex"));
+      expect.fail("reveal with a single reveal key should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("NotMutualMatch");
+    }
+    console.log("✅ One-sided reveal key blocks the reveal");
+
+    await submitRevealKey(userA, tripA);
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.revealKeyA.some((b) => b !== 0)).to.be.true;
+    expect(record.revealKeyB.some((b) => b !== 0)).to.be.true;
+
+    const revealEventPromise = awaitEvent("sharedInterestsRevealed");
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    await queueReveal(computationOffset);
+
+    await awaitComputationFinalization(
+      provider as anchor.AnchorProvider,
+      computationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const revealEvent = await revealEventPromise;
+
+    // Exactly hiking (bit 0) and photography (bit 1); food (bit 2) is A's alone
+    expect(revealEvent.sharedMask).to.equal(0b011);
+    expect(revealEvent.sharedMask & 0b100).to.equal(0);
+    console.log("✅ Only shared interests revealed:", revealEvent.sharedMask.toString(2));
+  });
+
+  it("Records each participant's acknowledgment of revealed data", async () => {
+    const { matchRecord, userA, userB, tripA, tripB, profileNonceA, profileNonceB } =
+      await runEncryptedMatch(0, 0);
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      await program.methods
+        .submitRevealKey(Array.from(randomBytes(32)))
+        .accountsPartial({ matchRecord, trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+
+    const acknowledge = (user: anchor.web3.Keypair, trip: PublicKey) =>
+      program.methods
+        .acknowledgeReveal()
+        .accountsPartial({ matchRecord, trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+
+    // Mutual with both keys in, but nothing has been revealed yet
+    try {
+      await acknowledge(userA, tripA);
+      expect.fail("acknowledging before a reveal should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("RevealNotComplete");
+    }
+
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    await program.methods
+      .revealSharedInterests(
+        computationOffset,
+        new anchor.BN(deserializeLE(profileNonceA).toString()),
+        new anchor.BN(deserializeLE(profileNonceB).toString())
+      )
+      .accountsPartial({
+        payer: userA.publicKey,
+        computationAccount: getComputationAccAddress(program.programId, computationOffset),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("reveal_shared_interests")).readUInt32LE()
+        ),
+        matchRecord,
+        tripA,
+        tripB,
+      })
+      .signers([userA])
+      .rpc({ commitment: "confirmed" });
+    await awaitComputationFinalization(
+      provider as anchor.AnchorProvider,
+      computationOffset,
+      program.programId,
+      "confirmed"
+    );
+    expect((await program.account.matchRecord.fetch(matchRecord)).revealed).to.be.true;
+
+    const ackEventPromise = awaitEvent("revealAcknowledged");
+    await acknowledge(userA, tripA);
+    const firstAck = await ackEventPromise;
+    expect(firstAck.participant.toBase58()).to.equal(userA.publicKey.toBase58());
+    expect(firstAck.bothAcknowledged).to.be.false;
+
+    let record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.acknowledgedByA).to.be.true;
+    expect(record.acknowledgedByB).to.be.false;
+
+    await acknowledge(userB, tripB);
+    record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.acknowledgedByB).to.be.true;
+    console.log("✅ Both participants acknowledged the reveal");
+  });
+
+  it("Scores a trip against itself as a healthy self-match", async () => {
+    const owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
+    await initCompDef(
+      program,
+      provider as anchor.AnchorProvider,
+      owner,
+      "self_match_test",
+      () => program.methods.initSelfMatchTestCompDef()
+    );
+    const configPDA = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+
+    const user = await createFundedUser(provider as anchor.AnchorProvider);
+    const { userProfilePDA, profileNonce, tripPDA, tripNonce } = await setupEncryptedUser(
+      user,
+      createSampleUserData("userA"),
+      createSampleTripData(),
+      0
+    );
+
+    const healthEventPromise = awaitEvent("selfMatchHealth");
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    await program.methods
+      .selfMatchTest(
+        computationOffset,
+        new anchor.BN(deserializeLE(tripNonce).toString()),
+        new anchor.BN(deserializeLE(profileNonce).toString())
+      )
+      .accountsPartial({
+        payer: owner.publicKey,
+        computationAccount: getComputationAccAddress(program.programId, computationOffset),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("self_match_test")).readUInt32LE()
+        ),
+        config: configPDA,
+        trip: tripPDA,
+        userProfile: userProfilePDA,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    await awaitComputationFinalization(
+      provider as anchor.AnchorProvider,
+      computationOffset,
+      program.programId,
+      "confirmed"
+    );
+    const health = await healthEventPromise;
+
+    expect(health.trip.toBase58()).to.equal(tripPDA.toBase58());
+    expect(health.routeScore).to.equal(100);
+    expect(health.coverageScore).to.equal(100);
+    expect(health.totalScore).to.be.at.least(50);
+    expect(health.healthy).to.be.true;
+    console.log(`✅ Self-match healthy: route ${health.routeScore}, total ${health.totalScore}`);
+  });
+
+  it("Restricts self-match health checks to the config admin", async () => {
+    const configPDA = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+    const outsider = await createFundedUser(provider as anchor.AnchorProvider);
+    const userProfile = await createRawUserProfile(program, outsider);
+    const trip = await createRawTrip(program, outsider);
+
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const nonce = () => new anchor.BN(deserializeLE(randomBytes(16)).toString());
+    try {
+      await program.methods
+        .selfMatchTest(computationOffset, nonce(), nonce())
+        .accountsPartial({
+          payer: outsider.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("self_match_test")).readUInt32LE()
+          ),
+          config: configPDA,
+          trip,
+          userProfile,
+        })
+        .signers([outsider])
+        .rpc();
+      throw new Error("Should have failed: not the admin");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+    console.log("✅ Non-admin self-match rejected");
+  });
+
+  it("Reveals only whether a pending match clears a score threshold", async () => {
+    const owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
+    await initCompDef(
+      program,
+      provider as anchor.AnchorProvider,
+      owner,
+      "check_match_threshold",
+      () => program.methods.initCheckMatchThresholdCompDef()
+    );
+
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+    const a = await setupEncryptedUser(userA, createSampleUserData("userA"), createSampleTripData(), 0);
+    const b = await setupEncryptedUser(userB, createSampleUserData("userB"), createVariantTripData(), 0);
+    const matchRecord = await initiateRawMatch(program, userA, a.tripPDA, b.tripPDA);
+
+    const checkThreshold = async (threshold: number) => {
+      const thresholdEventPromise = awaitEvent("matchThresholdChecked");
+      const computationOffset = new anchor.BN(randomBytes(8), "hex");
+      await program.methods
+        .checkMatchThreshold(
+          computationOffset,
+          threshold,
+          new anchor.BN(deserializeLE(a.tripNonce).toString()),
+          new anchor.BN(deserializeLE(b.tripNonce).toString()),
+          new anchor.BN(deserializeLE(a.profileNonce).toString()),
+          new anchor.BN(deserializeLE(b.profileNonce).toString())
+        )
+        .accountsPartial({
+          payer: userB.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("check_match_threshold")).readUInt32LE()
+          ),
+          matchRecord,
+          tripA: a.tripPDA,
+          tripB: b.tripPDA,
+          userProfileA: a.userProfilePDA,
+          userProfileB: b.userProfilePDA,
+        })
+        .signers([userB])
+        .rpc({ commitment: "confirmed" });
+
+      await awaitComputationFinalization(
+        provider as anchor.AnchorProvider,
+        computationOffset,
+        program.programId,
+        "confirmed"
+      );
+      return thresholdEventPromise;
+    };
+
+    // Every total clears 0; the sample and variant trips can't score a perfect 100
+    const above = await checkThreshold(0);
+    expect(above.threshold).to.equal(0);
+    expect(above.passed).to.be.true;
+    expect(above).to.not.have.any.keys("totalScore", "routeScore", "dateScore", "interestScore");
+
+    let record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.thresholdChecked).to.be.true;
+    expect(record.scoreThreshold).to.equal(0);
+    expect(record.thresholdPassed).to.be.true;
+    console.log("✅ Threshold 0 passed");
+
+    const below = await checkThreshold(100);
+    expect(below.threshold).to.equal(100);
+    expect(below.passed).to.be.false;
+
+    // Only the bit and the threshold are stored - components stay hidden
+    record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.scoreThreshold).to.equal(100);
+    expect(record.thresholdPassed).to.be.false;
+    expect(record.totalScore).to.equal(0);
+    expect(record.routeScore).to.equal(0);
+    expect(record.dateScore).to.equal(0);
+    expect(record.interestScore).to.equal(0);
+    expect(record.coverageScore).to.equal(0);
+    expect(record.status).to.have.property("pending");
+    console.log("✅ Threshold 100 failed without revealing any score");
+  });
+
+  it("Restricts threshold checks to the match participants", async () => {
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+    const outsider = await createFundedUser(provider as anchor.AnchorProvider);
+    const a = await setupEncryptedUser(userA, createSampleUserData("userA"), createSampleTripData(), 0);
+    const b = await setupEncryptedUser(userB, createSampleUserData("userB"), createVariantTripData(), 0);
+    const matchRecord = await initiateRawMatch(program, userA, a.tripPDA, b.tripPDA);
+
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    try {
+      await program.methods
+        .checkMatchThreshold(
+          computationOffset,
+          50,
+          new anchor.BN(deserializeLE(a.tripNonce).toString()),
+          new anchor.BN(deserializeLE(b.tripNonce).toString()),
+          new anchor.BN(deserializeLE(a.profileNonce).toString()),
+          new anchor.BN(deserializeLE(b.profileNonce).toString())
+        )
+        .accountsPartial({
+          payer: outsider.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("check_match_threshold")).readUInt32LE()
+          ),
+          matchRecord,
+          tripA: a.tripPDA,
+          tripB: b.tripPDA,
+          userProfileA: a.userProfilePDA,
+          userProfileB: b.userProfilePDA,
+        })
+        .signers([outsider])
+        .rpc();
+      expect.fail("threshold check by a non-participant should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+    console.log("✅ Outsiders cannot probe a match's score");
+  });
+
+  it("Rejects trips with more encrypted fields than the circuit expects", async () => {
+    const user = await createFundedUser(provider as anchor.AnchorProvider);
+    const startDate = Math.floor(Date.now() / 1000) + 24 * 60 * 60;
+
+    // WaypointData is 25 fields (20 waypoints + count + min_interests + age + daily_budget
+    // + seeking), which fills the Trip account exactly; 26 is one too many, so an
+    // oversized trip can't even be created, let alone queued.
+    await createRawTrip(program, user, { startDate, encryptedWaypoints: randomBytes(25 * 32) });
+    try {
+      await createRawTrip(program, user, {
+        startDate: startDate + 1,
+        encryptedWaypoints: randomBytes(26 * 32),
+      });
+      throw new Error("Should have failed with too many encrypted fields");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("EncryptedDataTooLarge");
+    }
+    console.log("✅ Oversized payload rejected at create_trip");
+  });
+
+  it("Rejects queueing a computation with the same trip in both slots", async () => {
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+    const userProfileA = await createRawUserProfile(program, userA);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+    const matchRecord = await initiateRawMatch(program, userA, tripA, tripB);
+
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const nonce = () => new anchor.BN(deserializeLE(randomBytes(16)).toString());
+    try {
+      await program.methods
+        .computeTripMatch(computationOffset, nonce(), nonce(), nonce(), nonce())
+        .accountsPartial({
+          payer: userA.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("compute_trip_match")).readUInt32LE()
+          ),
+          matchRecord,
+          tripA,
+          tripB: tripA,
+          userProfileA,
+          userProfileB: userProfileA,
+        })
+        .signers([userA])
+        .rpc();
+      throw new Error("Should have failed: same trip in both slots");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("SameTripMatch");
+    }
+    console.log("✅ Identical trip accounts rejected before queueing MPC");
+  });
+
+  it("Rejects queueing a computation with the trips swapped", async () => {
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+    const userProfileA = await createRawUserProfile(program, userA);
+    const userProfileB = await createRawUserProfile(program, userB);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+    const matchRecord = await initiateRawMatch(program, userA, tripA, tripB);
+
+    // The swapped pair derives the same canonical match PDA, but its
+    // callback could never land
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const nonce = () => new anchor.BN(deserializeLE(randomBytes(16)).toString());
+    try {
+      await program.methods
+        .computeTripMatch(computationOffset, nonce(), nonce(), nonce(), nonce())
+        .accountsPartial({
+          payer: userB.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("compute_trip_match")).readUInt32LE()
+          ),
+          matchRecord,
+          tripA: tripB,
+          tripB: tripA,
+          userProfileA: userProfileB,
+          userProfileB: userProfileA,
+        })
+        .signers([userB])
+        .rpc();
+      throw new Error("Should have failed: trips swapped");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("TripOrderMismatch");
+    }
+
+    // Nothing was charged or locked
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.escrowLamports.toNumber()).to.equal(0);
+    expect(record.status).to.have.property("pending");
+    console.log("✅ Swapped trips rejected before any fee or escrow");
+  });
+
+  /**
+   * Queue a compute_trip_match over placeholder trips at `destination`
+   * and return how much the treasury received
+   */
+  const [treasury] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], program.programId);
+
+  /**
+   * Queue compute_trip_match for two raw (random-ciphertext) trips at a destination
+   * The MPC result is irrelevant here - only queue-time effects are inspected
+   */
+  const queueRawComputation = async (
+    destination: Buffer,
+    postInstructions: (matchRecord: PublicKey) => Promise<anchor.web3.TransactionInstruction[]> = async () => [],
+    options: {
+      tripA?: Parameters<typeof createRawTrip>[2];
+      tripB?: Parameters<typeof createRawTrip>[2];
+      flexibilityA?: number;
+      notifyOnA?: number;
+      match?: Parameters<typeof initiateRawMatch>[4];
+    } = {}
+  ) => {
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+    const userProfileA = await createRawUserProfile(program, userA);
+    const userProfileB = await createRawUserProfile(program, userB);
+    const tripA = await createRawTrip(program, userA, { destinationGridHash: destination, ...options.tripA });
+    const tripB = await createRawTrip(program, userB, { destinationGridHash: destination, ...options.tripB });
+    if (options.flexibilityA !== undefined) {
+      await program.methods
+        .setTripFlexibility(options.flexibilityA)
+        .accountsPartial({ trip: tripA, user: userA.publicKey })
+        .signers([userA])
+        .rpc({ commitment: "confirmed" });
+    }
+    if (options.notifyOnA !== undefined) {
+      await program.methods
+        .setTripNotifications(options.notifyOnA)
+        .accountsPartial({ trip: tripA, user: userA.publicKey })
+        .signers([userA])
+        .rpc({ commitment: "confirmed" });
+    }
+    const matchRecord = await initiateRawMatch(program, userA, tripA, tripB, options.match);
+
+    const [destinationFee] = PublicKey.findProgramAddressSync(
+      [Buffer.from("destination_fee"), destination],
+      program.programId
+    );
+
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const nonce = () => new anchor.BN(deserializeLE(randomBytes(16)).toString());
+    const signature = await program.methods
+      .computeTripMatch(computationOffset, nonce(), nonce(), nonce(), nonce())
+      .accountsPartial({
+        payer: userA.publicKey,
+        computationAccount: getComputationAccAddress(program.programId, computationOffset),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("compute_trip_match")).readUInt32LE()
+        ),
+        matchRecord,
+        tripA,
+        tripB,
+        userProfileA,
+        userProfileB,
+        treasury,
+        destinationFee,
+      })
+      .postInstructions(await postInstructions(matchRecord))
+      .signers([userA])
+      .rpc({ commitment: "confirmed" });
+
+    return { userA, userB, userProfileA, userProfileB, tripA, tripB, matchRecord, signature };
+  };
+
+  /**
+   * Treasury balance change within one transaction
+   * (aborted callbacks from earlier tests also credit the treasury)
+   */
+  const treasuryDelta = async (signature: string): Promise<number> => {
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const index = tx.transaction.message.staticAccountKeys.findIndex((key) => key.equals(treasury));
+    return tx.meta.postBalances[index] - tx.meta.preBalances[index];
+  };
+
+  const queueAndMeasureFee = async (destination: Buffer): Promise<number> => {
+    const { signature } = await queueRawComputation(destination);
+    return treasuryDelta(signature);
+  };
+
+  it("Charges the base fee at destinations without an override", async () => {
+    const BASE_MATCH_FEE_LAMPORTS = 1_000_000;
+    const charged = await queueAndMeasureFee(randomBytes(32));
+    expect(charged).to.equal(BASE_MATCH_FEE_LAMPORTS);
+    console.log("✅ Base fee charged");
+  });
+
+  it("Charges the overridden fee at a configured destination", async () => {
+    const config = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+    const popular = randomBytes(32);
+    const overrideFee = 5_000_000;
+
+    await program.methods
+      .setDestinationFee(Array.from(popular), new anchor.BN(overrideFee))
+      .accountsPartial({
+        config,
+        admin: provider.wallet.publicKey,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const charged = await queueAndMeasureFee(popular);
+    expect(charged).to.equal(overrideFee);
+    console.log("✅ Destination override fee charged");
+  });
+
+  it("Settles an obvious non-match without queueing MPC", async () => {
+    const DAY = 24 * 60 * 60;
+    const start = Math.floor(Date.now() / 1000) + 120 * DAY;
+
+    // Rigid trips 2 days apart at different destinations
+    const skippedPromise = awaitEvent("matchComputationSkipped");
+    const { matchRecord, signature } = await queueRawComputation(randomBytes(32), undefined, {
+      tripA: { startDate: start, endDate: start + 3 * DAY },
+      tripB: { startDate: start + 5 * DAY, endDate: start + 8 * DAY, destinationGridHash: randomBytes(32) },
+    });
+    const skipped = await skippedPromise;
+    expect(skipped.matchRecord.toBase58()).to.equal(matchRecord.toBase58());
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("completed");
+    expect(record.totalScore).to.equal(0);
+    expect(record.acceptDeadline.toNumber()).to.be.greaterThan(0);
+    expect(record.escrowLamports.toNumber()).to.equal(0);
+    expect(await treasuryDelta(signature)).to.equal(0);
+    console.log("✅ Obvious non-match settled without MPC or fees");
+  });
+
+  it("Queues MPC for a date gap the more flexible trip bridges", async () => {
+    const DAY = 24 * 60 * 60;
+    const start = Math.floor(Date.now() / 1000) + 122 * DAY;
+
+    // Same pair as above, but flexibility 100 shifts trip A by up to 14 days,
+    // so the dates can still score and the prefilter must not settle it
+    const { matchRecord } = await queueRawComputation(randomBytes(32), undefined, {
+      tripA: { startDate: start, endDate: start + 3 * DAY },
+      tripB: { startDate: start + 5 * DAY, endDate: start + 8 * DAY, destinationGridHash: randomBytes(32) },
+      flexibilityA: 100,
+    });
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("pending");
+    expect(record.escrowLamports.toNumber()).to.be.greaterThan(0);
+    console.log("✅ Flexible date-adjacent pair queued for scoring");
+  });
+
+  it("Counts a prefiltered match once and refuses to settle it again", async () => {
+    const DAY = 24 * 60 * 60;
+    const start = Math.floor(Date.now() / 1000) + 125 * DAY;
+
+    const { userA, userProfileA, userProfileB, tripA, tripB, matchRecord } = await queueRawComputation(
+      randomBytes(32),
+      undefined,
+      {
+        tripA: { startDate: start, endDate: start + 3 * DAY },
+        tripB: { startDate: start + 5 * DAY, endDate: start + 8 * DAY, destinationGridHash: randomBytes(32) },
+      }
+    );
+    const totals = async () =>
+      Promise.all(
+        [userProfileA, userProfileB].map(async (profile) => (await program.account.userProfile.fetch(profile)).totalMatches)
+      );
+    expect(await totals()).to.deep.equal([1, 1]);
+    const { acceptDeadline } = await program.account.matchRecord.fetch(matchRecord);
+
+    // The settled record is Completed and fresh, so the prefilter can't
+    // re-stamp it or count it a second time
+    const { destinationGridHash } = await program.account.trip.fetch(tripA);
+    const [destinationFee] = PublicKey.findProgramAddressSync(
+      [Buffer.from("destination_fee"), Buffer.from(destinationGridHash)],
+      program.programId
+    );
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+    const nonce = () => new anchor.BN(deserializeLE(randomBytes(16)).toString());
+    try {
+      await program.methods
+        .computeTripMatch(computationOffset, nonce(), nonce(), nonce(), nonce())
+        .accountsPartial({
+          payer: userA.publicKey,
+          computationAccount: getComputationAccAddress(program.programId, computationOffset),
+          clusterAccount: arciumEnv.arciumClusterPubkey,
+          mxeAccount: getMXEAccAddress(program.programId),
+          mempoolAccount: getMempoolAccAddress(program.programId),
+          executingPool: getExecutingPoolAccAddress(program.programId),
+          compDefAccount: getCompDefAccAddress(
+            program.programId,
+            Buffer.from(getCompDefAccOffset("compute_trip_match")).readUInt32LE()
+          ),
+          matchRecord,
+          tripA,
+          tripB,
+          userProfileA,
+          userProfileB,
+          treasury,
+          destinationFee,
+        })
+        .signers([userA])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("re-settling a completed match should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidMatchStatus");
+    }
+
+    expect(await totals()).to.deep.equal([1, 1]);
+    const after = await program.account.matchRecord.fetch(matchRecord);
+    expect(after.acceptDeadline.toNumber()).to.equal(acceptDeadline.toNumber());
+    console.log("✅ Prefiltered match counted once, deadline untouched");
+  });
+
+  it("Tracks reputation from mutual matches and rejections received", async () => {
+    const DAY = 24 * 60 * 60;
+    const start = Math.floor(Date.now() / 1000) + 150 * DAY;
+
+    // A skipped (date-adjacent) pair lands Completed without MPC; both accept
+    const { userA, userB, userProfileA, userProfileB, tripA, tripB, matchRecord } = await queueRawComputation(
+      randomBytes(32),
+      undefined,
+      {
+        tripA: { startDate: start, endDate: start + 3 * DAY },
+        tripB: { startDate: start + 5 * DAY, endDate: start + 8 * DAY, destinationGridHash: randomBytes(32) },
+      }
+    );
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: userProfileA,
+          ownerProfileB: userProfileB,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+    for (const profile of [userProfileA, userProfileB]) {
+      const { mutualMatches, rejectionsReceived, reputation } = await program.account.userProfile.fetch(profile);
+      expect([mutualMatches, rejectionsReceived, reputation]).to.deep.equal([1, 0, 100]);
+    }
+
+    // A new counterparty turns down A's next trip: only A takes the rejection
+    const userC = await createFundedUser(provider as anchor.AnchorProvider);
+    const userProfileC = await createRawUserProfile(program, userC);
+    const tripA2 = await createRawTrip(program, userA, { startDate: start + 20 * DAY, endDate: start + 23 * DAY });
+    const tripC = await createRawTrip(program, userC, { startDate: start + 20 * DAY, endDate: start + 23 * DAY });
+    const rejected = await initiateRawMatch(program, userA, tripA2, tripC);
+    await program.methods
+      .rejectMatch()
+      .accountsPartial({
+        matchAccount: rejected,
+        trip: tripC,
+        user: userC.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: userProfileA,
+        ownerProfileB: userProfileC,
+      })
+      .signers([userC])
+      .rpc({ commitment: "confirmed" });
+
+    const profileA = await program.account.userProfile.fetch(userProfileA);
+    expect([profileA.mutualMatches, profileA.rejectionsReceived, profileA.reputation]).to.deep.equal([1, 1, 50]);
+    const profileC = await program.account.userProfile.fetch(userProfileC);
+    expect([profileC.rejectionsReceived, profileC.reputation]).to.deep.equal([0, 100]);
+    console.log("✅ Mutual matches and rejections received feed reputation");
+  });
+
+  it("Flags notifications off for a trip that disabled them", async () => {
+    const DAY = 24 * 60 * 60;
+    const NOTIFY_NEW_MATCH = 1;
+    const NOTIFY_MUTUAL = 2;
+    const start = Math.floor(Date.now() / 1000) + 140 * DAY;
+    const notificationsIn = async (signature: string) => {
+      const tx = await provider.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+      return [...parser.parseLogs(tx.meta.logMessages)]
+        .filter((e) => e.name === "matchNotification")
+        .map((e) => e.data);
+    };
+    const notifyFor = (notifications: any[], trip: PublicKey) =>
+      notifications.find((n) => n.trip.equals(trip)).notify;
+
+    // Trip A opts out of everything; the skipped pair lands as a new match
+    const { userA, userB, tripA, tripB, matchRecord, signature } = await queueRawComputation(
+      randomBytes(32),
+      undefined,
+      {
+        tripA: { startDate: start, endDate: start + 3 * DAY },
+        tripB: { startDate: start + 5 * DAY, endDate: start + 8 * DAY, destinationGridHash: randomBytes(32) },
+        notifyOnA: 0,
+      }
+    );
+    const landed = await notificationsIn(signature);
+    expect(landed).to.have.length(2);
+    expect(landed.every((n) => n.kind === NOTIFY_NEW_MATCH)).to.be.true;
+    expect(notifyFor(landed, tripA)).to.be.false;
+    expect(notifyFor(landed, tripB)).to.be.true;
+
+    // The accept that makes the match Mutual notifies both sides the same way
+    let mutualSignature: string;
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      mutualSignature = await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    }
+    const mutual = await notificationsIn(mutualSignature);
+    expect(mutual.every((n) => n.kind === NOTIFY_MUTUAL)).to.be.true;
+    expect(notifyFor(mutual, tripA)).to.be.false;
+    expect(notifyFor(mutual, tripB)).to.be.true;
+
+    try {
+      await program.methods
+        .setTripNotifications(0b1000)
+        .accountsPartial({ trip: tripA, user: userA.publicKey })
+        .signers([userA])
+        .rpc();
+      expect.fail("unknown notification bits should be rejected");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidNotificationFlags");
+    }
+    console.log("✅ Notification events fire with notify = false for the opted-out trip");
+  });
+
+  it("Refuses to make a match Mutual below its minimum score", async () => {
+    const DAY = 24 * 60 * 60;
+    const start = Math.floor(Date.now() / 1000) + 130 * DAY;
+
+    // The skipped (non-overlapping) pair settles as Completed with a 0 total
+    const { userA, userB, tripA, tripB, matchRecord } = await queueRawComputation(randomBytes(32), undefined, {
+      tripA: { startDate: start, endDate: start + 3 * DAY },
+      tripB: { startDate: start + 5 * DAY, endDate: start + 8 * DAY, destinationGridHash: randomBytes(32) },
+      match: { minScore: 50 },
+    });
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.status).to.have.property("completed");
+    expect(record.totalScore).to.equal(0);
+    expect(record.minScore).to.equal(50);
+
+    const accept = (user: anchor.web3.Keypair, trip: PublicKey) =>
+      program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchRecord,
+          trip,
+          counterpartyTrip: trip.equals(tripA) ? tripB : tripA,
+          user: user.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([user])
+        .rpc();
+    for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+      try {
+        await accept(user, trip);
+        expect.fail("a 0-score match should not be accepted with min_score 50");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("ScoreBelowThreshold");
+      }
+    }
+    expect((await program.account.matchRecord.fetch(matchRecord)).status).to.have.property("completed");
+
+    // Minimum scores are percentages
+    try {
+      await queueRawComputation(randomBytes(32), undefined, { match: { minScore: 101 } });
+      expect.fail("a min_score above 100 should be rejected");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidScore");
+    }
+    console.log("✅ 0-score match held below min_score 50");
+  });
+
+  it("Queues MPC for a pair that could plausibly match", async () => {
+    const { signature } = await queueRawComputation(randomBytes(32));
+
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    expect(tx.meta.logMessages.some((log) => log.includes("Queued MPC computation"))).to.be.true;
+    expect(tx.meta.logMessages.some((log) => log.includes("skipped MPC"))).to.be.false;
+
+    const BASE_MATCH_FEE_LAMPORTS = 1_000_000;
+    expect(await treasuryDelta(signature)).to.equal(BASE_MATCH_FEE_LAMPORTS);
+    console.log("✅ Plausible match queued for MPC");
+  });
+
+  const COMPUTE_ESCROW_LAMPORTS = 5_000_000;
+  const DEFAULT_COMPUTE_ESCROW_TIMEOUT_SECS = 60 * 60;
+
+  it("Refunds the compute escrow when the match computation completes", async () => {
+    const escrowEventPromise = awaitEvent("computeEscrowSettled");
+    const { matchRecord, userA } = await runEncryptedMatch(0, 0);
+    const escrowEvent = await escrowEventPromise;
+
+    expect(escrowEvent.matchRecord.toBase58()).to.equal(matchRecord.toBase58());
+    expect(escrowEvent.payer.toBase58()).to.equal(userA.publicKey.toBase58());
+    expect(escrowEvent.lamports.toNumber()).to.equal(COMPUTE_ESCROW_LAMPORTS);
+    expect(escrowEvent.refunded).to.be.true;
+
+    const record = await program.account.matchRecord.fetch(matchRecord);
+    expect(record.escrowLamports.toNumber()).to.equal(0);
+    console.log("✅ Escrow refunded on completion");
+  });
+
+  it("Forfeits a timed-out compute escrow to the treasury", async () => {
+    const config = await getOrInitProgramConfig(program, provider as anchor.AnchorProvider);
+    const setEscrowTimeout = (secs: number) =>
+      program.methods
+        .setComputeEscrowTimeout(new anchor.BN(secs))
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+    const forfeitIx = (matchRecord: PublicKey) =>
+      program.methods
+        .forfeitComputeEscrow()
+        .accountsPartial({ matchRecord, treasury, cranker: provider.wallet.publicKey })
+        .instruction();
+
+    // Nothing locked yet
+    const userA = await createFundedUser(provider as anchor.AnchorProvider);
+    const userB = await createFundedUser(provider as anchor.AnchorProvider);
+    await createRawUserProfile(program, userA);
+    await createRawUserProfile(program, userB);
+    const idle = await initiateRawMatch(
+      program,
+      userA,
+      await createRawTrip(program, userA),
+      await createRawTrip(program, userB)
+    );
+    try {
+      await program.methods
+        .forfeitComputeEscrow()
+        .accountsPartial({ matchRecord: idle, treasury, cranker: provider.wallet.publicKey })
+        .rpc();
+      expect.fail("forfeit without an escrow should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("NoEscrow");
+    }
+
+    // Within the timeout the escrow waits for its callback
+    const pending = await queueRawComputation(randomBytes(32));
+    try {
+      await program.methods
+        .forfeitComputeEscrow()
+        .accountsPartial({ matchRecord: pending.matchRecord, treasury, cranker: provider.wallet.publicKey })
+        .rpc();
+      expect.fail("forfeit before the timeout should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("EscrowNotExpired");
+    }
+
+    // The localnet clock cannot be warped: a zero timeout makes the escrow
+    // forfeitable at once, so the crank rides in the queueing transaction
+    // and always beats the callback
+    try {
+      await setEscrowTimeout(0);
+      const { matchRecord, signature } = await queueRawComputation(randomBytes(32), async (matchRecord) => [
+        await forfeitIx(matchRecord),
+      ]);
+
+      const BASE_MATCH_FEE_LAMPORTS = 1_000_000;
+      expect(await treasuryDelta(signature)).to.equal(BASE_MATCH_FEE_LAMPORTS + COMPUTE_ESCROW_LAMPORTS);
+      const record = await program.account.matchRecord.fetch(matchRecord);
+      expect(record.escrowLamports.toNumber()).to.equal(0);
+      console.log("✅ Timed-out escrow forfeited to the treasury");
+    } finally {
+      await setEscrowTimeout(DEFAULT_COMPUTE_ESCROW_TIMEOUT_SECS);
+    }
+  });
+
+  it("Refuses to cancel or close a match while its compute escrow is locked", async () => {
+    // Riding in the queueing transaction, the cancel always sees the escrow
+    try {
+      await queueRawComputation(randomBytes(32), async (matchRecord) => {
+        const { tripA, tripB, rentPayer } = await program.account.matchRecord.fetch(matchRecord);
+        return [
+          await program.methods
+            .cancelPendingMatch()
+            .accountsPartial({ matchAccount: matchRecord, tripA, tripB, rentPayer, user: rentPayer, initiatorProfile: null })
+            .instruction(),
+        ];
+      });
+      expect.fail("cancelling with a locked escrow should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("EscrowAlreadyLocked");
+    }
+
+    // Likewise rejecting and closing before the callback lands
+    try {
+      await queueRawComputation(randomBytes(32), async (matchRecord) => {
+        const { tripA, tripB, tripBOwner, rentPayer } = await program.account.matchRecord.fetch(matchRecord);
+        const [ownerProfileB] = PublicKey.findProgramAddressSync(
+          [Buffer.from("user_profile"), tripBOwner.toBuffer()],
+          program.programId
+        );
+        return [
+          await program.methods
+            .rejectMatch()
+            .accountsPartial({
+              matchAccount: matchRecord,
+              trip: tripA,
+              user: rentPayer,
+              initiatorProfile: null,
+              ownerProfileA: null,
+              ownerProfileB,
+            })
+            .instruction(),
+          await program.methods
+            .closeMatch()
+            .accountsPartial({
+              matchAccount: matchRecord,
+              trip: tripA,
+              pairHistory: derivePairHistoryPDA(program.programId, tripA, tripB),
+              rentPayer,
+              user: rentPayer,
+            })
+            .instruction(),
+        ];
+      });
+      expect.fail("closing with a locked escrow should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("EscrowAlreadyLocked");
+    }
+    console.log("✅ Locked escrow blocks cancel and close");
+  });
+
+  it("Keeps a rejection made while the computation is in flight", async () => {
+    // Riding in the queueing transaction, the rejection always beats the callback
+    const { matchRecord, userA } = await queueRawComputation(randomBytes(32), async (matchRecord) => {
+      const { tripA, tripBOwner, rentPayer } = await program.account.matchRecord.fetch(matchRecord);
+      const [ownerProfileB] = PublicKey.findProgramAddressSync(
+        [Buffer.from("user_profile"), tripBOwner.toBuffer()],
+        program.programId
+      );
+      return [
+        await program.methods
+          .rejectMatch()
+          .accountsPartial({
+            matchAccount: matchRecord,
+            trip: tripA,
+            user: rentPayer,
+            initiatorProfile: null,
+            ownerProfileA: null,
+            ownerProfileB,
+          })
+          .instruction(),
+      ];
+    });
+
+    // The callback (success or abort) always settles the escrow
+    let record = await program.account.matchRecord.fetch(matchRecord);
+    for (let i = 0; i < 120 && record.escrowLamports.toNumber() > 0; i++) {
+      await new Promise((resolve) => setTimeout(resolve, 1000));
+      record = await program.account.matchRecord.fetch(matchRecord);
+    }
+    expect(record.escrowLamports.toNumber()).to.equal(0);
+
+    expect(record.status).to.have.property("rejected");
+    expect(record.rejectedBy.toBase58()).to.equal(userA.publicKey.toBase58());
+    expect(record.scoringVersion).to.equal(0);
+    expect(record.totalScore).to.equal(0);
+    console.log("✅ In-flight result discarded, rejection stands");
+  });
+});
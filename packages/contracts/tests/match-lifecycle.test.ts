@@ -0,0 +1,967 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { Triper } from "../target/types/triper";
+import { expect } from "chai";
+import { randomBytes } from "crypto";
+import {
+  createFundedUser,
+  createRawTrip,
+  createRawUserProfile,
+  deriveBlocklistPDA,
+  deriveMatchPDA,
+  derivePairHistoryPDA,
+  deriveOwnerPairHistoryPDA,
+  getOrInitProgramConfig,
+  initiateRawMatch,
+} from "./utils";
+
+describe("Match Lifecycle", () => {
+  // Configure the client to use the local cluster
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.Triper as Program<Triper>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  /**
+   * Two users with one trip each and a Pending match between them
+   */
+  const setupMatch = async (): Promise<{
+    userA: Keypair;
+    userB: Keypair;
+    tripA: PublicKey;
+    tripB: PublicKey;
+    profileA: PublicKey;
+    matchPDA: PublicKey;
+  }> => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+    const profileA = await createRawUserProfile(program, userA);
+    const matchPDA = await initiateRawMatch(program, userA, tripA, tripB);
+    return { userA, userB, tripA, tripB, profileA, matchPDA };
+  };
+
+  it("Undoes a rejection within the grace window", async () => {
+    const { userB, tripB, profileA, matchPDA } = await setupMatch();
+
+    await program.methods
+      .rejectMatch()
+      .accountsPartial({
+        matchAccount: matchPDA,
+        trip: tripB,
+        user: userB.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: profileA,
+        ownerProfileB: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+
+    let record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.status).to.have.property("rejected");
+    expect(record.rejectedBy.toBase58()).to.equal(userB.publicKey.toBase58());
+
+    await program.methods
+      .undoReject()
+      .accountsPartial({
+        matchAccount: matchPDA,
+        trip: tripB,
+        user: userB.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: profileA,
+        ownerProfileB: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+
+    record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.status).to.have.property("pending");
+    expect(record.rejectedAt.toNumber()).to.equal(0);
+    console.log("✅ Rejection undone");
+  });
+
+  it("Charges a rejection to the rejected owner once across reject and undo", async () => {
+    const { userB, tripB, profileA, matchPDA } = await setupMatch();
+    const reject = (ownerProfileA: PublicKey | null) =>
+      program.methods
+        .rejectMatch()
+        .accountsPartial({
+          matchAccount: matchPDA,
+          trip: tripB,
+          user: userB.publicKey,
+          initiatorProfile: null,
+          ownerProfileA,
+          ownerProfileB: null,
+        })
+        .signers([userB])
+        .rpc({ commitment: "confirmed" });
+    const undo = () =>
+      program.methods
+        .undoReject()
+        .accountsPartial({
+          matchAccount: matchPDA,
+          trip: tripB,
+          user: userB.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: profileA,
+          ownerProfileB: null,
+        })
+        .signers([userB])
+        .rpc({ commitment: "confirmed" });
+    const rejections = async () => (await program.account.userProfile.fetch(profileA)).rejectionsReceived;
+
+    // The rejected owner's profile can't be left out
+    try {
+      await reject(null);
+      expect.fail("rejecting without the rejected owner's profile should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("RejectedProfileRequired");
+    }
+
+    // Looping reject → undo leaves at most the one live rejection on record
+    for (let round = 0; round < 3; round++) {
+      await reject(profileA);
+      expect(await rejections()).to.equal(1);
+      await undo();
+      expect(await rejections()).to.equal(0);
+    }
+    await reject(profileA);
+    expect(await rejections()).to.equal(1);
+    const profile = await program.account.userProfile.fetch(profileA);
+    expect(profile.reputation).to.equal(0);
+    console.log("✅ Reject/undo loops can't farm rejections");
+  });
+
+  it("Refuses to accept a match before its scores land", async () => {
+    const { userB, tripA, tripB, matchPDA } = await setupMatch();
+
+    try {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchPDA,
+          trip: tripB,
+          counterpartyTrip: tripA,
+          user: userB.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([userB])
+        .rpc();
+      expect.fail("accepting a Pending match should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidMatchStatus");
+    }
+
+    const record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.status).to.have.property("pending");
+    expect(record.tripBAccepted).to.be.false;
+    console.log("✅ Pending matches cannot be accepted");
+  });
+
+  it("Stamps a pending expiry and refuses to expire the match before it", async () => {
+    const { userB, matchPDA } = await setupMatch();
+
+    const MATCH_EXPIRY_SECS = 14 * 24 * 60 * 60;
+    const record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.expiresAt.toNumber()).to.equal(record.createdAt.toNumber() + MATCH_EXPIRY_SECS);
+
+    // Anyone may crank expiry, but only once expires_at has passed
+    try {
+      await program.methods
+        .expireMatch()
+        .accountsPartial({ matchAccount: matchPDA, cranker: userB.publicKey, initiatorProfile: null })
+        .signers([userB])
+        .rpc();
+      expect.fail("expiring a fresh Pending match should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("MatchNotExpired");
+    }
+
+    const after = await program.account.matchRecord.fetch(matchPDA);
+    expect(after.status).to.have.property("pending");
+    console.log("✅ Pending match carries an expiry and stays open until it");
+  });
+
+  it("Records both trip owners on the match", async () => {
+    const { userA, userB, matchPDA } = await setupMatch();
+
+    const record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.tripAOwner.toBase58()).to.equal(userA.publicKey.toBase58());
+    expect(record.tripBOwner.toBase58()).to.equal(userB.publicKey.toBase58());
+    console.log("✅ Trip owners recorded");
+  });
+
+  it("Keeps one match record per unordered trip pair", async () => {
+    const { userB, tripA, tripB, matchPDA } = await setupMatch();
+
+    // B initiating against A derives the same PDA A already created
+    try {
+      await initiateRawMatch(program, userB, tripB, tripA);
+      expect.fail("initiating the reverse pair should fail");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("MatchAlreadyExists");
+    }
+
+    const record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.tripA.toBase58()).to.equal(tripA.toBase58());
+    console.log("✅ Reverse initiation hits the existing record");
+  });
+
+  it("Only lets the rejecting user undo a rejection", async () => {
+    const { userA, userB, tripA, tripB, profileA, matchPDA } = await setupMatch();
+
+    await program.methods
+      .rejectMatch()
+      .accountsPartial({
+        matchAccount: matchPDA,
+        trip: tripB,
+        user: userB.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: profileA,
+        ownerProfileB: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+
+    try {
+      await program.methods
+        .undoReject()
+        .accountsPartial({
+          matchAccount: matchPDA,
+          trip: tripA,
+          user: userA.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: profileA,
+          ownerProfileB: null,
+        })
+        .signers([userA])
+        .rpc({ commitment: "confirmed" });
+      throw new Error("Should have failed: only the rejecter can undo");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("Unauthorized");
+    }
+    console.log("✅ Counterparty cannot undo the rejection");
+  });
+
+  /**
+   * Trip A requires tier 2; trip B's owner is verified at the given tier
+   */
+  const setupTierGatedPair = async (counterpartyTier: number) => {
+    const config = await getOrInitProgramConfig(program, provider);
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+    const profileB = await createRawUserProfile(program, userB);
+
+    await program.methods
+      .setMinCounterpartyTier(2)
+      .accountsPartial({ trip: tripA, user: userA.publicKey })
+      .signers([userA])
+      .rpc({ commitment: "confirmed" });
+
+    await program.methods
+      .setVerificationTier(counterpartyTier)
+      .accountsPartial({
+        config,
+        userProfile: profileB,
+        verifier: provider.wallet.publicKey,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    return { userA, tripA, tripB, profileB };
+  };
+
+  it("Allows a tier-gated match when the counterparty is verified enough", async () => {
+    const { userA, tripA, tripB, profileB } = await setupTierGatedPair(2);
+
+    const matchPDA = await initiateRawMatch(program, userA, tripA, tripB, { profileB });
+
+    const record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.status).to.have.property("pending");
+    console.log("✅ Tier 2 counterparty accepted");
+  });
+
+  it("Rejects a tier-gated match when the counterparty is under-verified", async () => {
+    const { userA, tripA, tripB, profileB } = await setupTierGatedPair(1);
+
+    try {
+      await initiateRawMatch(program, userA, tripA, tripB, { profileB });
+      throw new Error("Should have failed: counterparty tier is too low");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InsufficientVerification");
+    }
+
+    // Omitting the profile counts as unverified
+    try {
+      await initiateRawMatch(program, userA, tripA, tripB);
+      throw new Error("Should have failed: missing profile counts as tier 0");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InsufficientVerification");
+    }
+    console.log("✅ Under-verified counterparty rejected");
+  });
+
+  it("Refuses to initiate a match with a user on either blocklist", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+    const blocklistB = deriveBlocklistPDA(program.programId, userB.publicKey);
+    const setBlocked = (user: Keypair, blocked: PublicKey, block: boolean) =>
+      (block ? program.methods.blockUser(blocked) : program.methods.unblockUser(blocked))
+        .accountsPartial({ blocklist: deriveBlocklistPDA(program.programId, user.publicKey), user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+    const expectBlocked = async () => {
+      try {
+        await initiateRawMatch(program, userA, tripA, tripB);
+        throw new Error("Should have failed: one owner blocked the other");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("UserBlocked");
+      }
+    };
+
+    // B blocks A: A can't reach B's trip
+    await setBlocked(userB, userA.publicKey, true);
+    const list = await program.account.blocklist.fetch(blocklistB);
+    expect(list.blocked.map((k) => k.toBase58())).to.deep.equal([userA.publicKey.toBase58()]);
+    await expectBlocked();
+
+    // A's own blocklist applies in the other direction
+    await setBlocked(userB, userA.publicKey, false);
+    await setBlocked(userA, userB.publicKey, true);
+    await expectBlocked();
+
+    // Once both lists are clear the match goes through
+    await setBlocked(userA, userB.publicKey, false);
+    const matchPDA = await initiateRawMatch(program, userA, tripA, tripB);
+    const record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.status).to.have.property("pending");
+
+    try {
+      await setBlocked(userA, userA.publicKey, true);
+      throw new Error("Should have failed: users can't block themselves");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("CannotBlockSelf");
+    }
+    console.log("✅ Blocked pairs can't be matched until unblocked");
+  });
+
+  it("Refuses to initiate a match that leaves out the counterparty's blocklist", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+
+    // B blocks A
+    await program.methods
+      .blockUser(userA.publicKey)
+      .accountsPartial({ blocklist: deriveBlocklistPDA(program.programId, userB.publicKey), user: userB.publicKey })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+
+    // A stands in an account that isn't B's blocklist: an empty address,
+    // then A's own (never created) blocklist
+    const standIns = [Keypair.generate().publicKey, deriveBlocklistPDA(program.programId, userA.publicKey)];
+    for (const blocklistB of standIns) {
+      try {
+        await initiateRawMatch(program, userA, tripA, tripB, { blocklistB });
+        throw new Error("Should have failed: B's blocklist was left out");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("ConstraintSeeds");
+      }
+    }
+    expect(await program.account.matchRecord.fetchNullable(deriveMatchPDA(program.programId, tripA, tripB))).to.be
+      .null;
+    console.log("✅ Counterparty's blocklist can't be skipped");
+  });
+
+  it("Defaults the scoring profile by destination unless the initiator overrides it", async () => {
+    const ROUTE_FOCUSED = 2;
+    const config = await getOrInitProgramConfig(program, provider);
+    const destinationGridHash = randomBytes(32);
+    const setDefault = (profile: number) =>
+      program.methods
+        .setDestinationProfile(Array.from(destinationGridHash), profile)
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+    const setupPair = async () => {
+      const userA = await createFundedUser(provider);
+      const userB = await createFundedUser(provider);
+      const tripA = await createRawTrip(program, userA, { destinationGridHash });
+      const tripB = await createRawTrip(program, userB, { destinationGridHash });
+      return { userA, tripA, tripB };
+    };
+
+    await setDefault(ROUTE_FOCUSED);
+
+    // No profile given: the destination's route-heavy default applies
+    const defaulted = await setupPair();
+    let record = await program.account.matchRecord.fetch(
+      await initiateRawMatch(program, defaulted.userA, defaulted.tripA, defaulted.tripB)
+    );
+    expect(record.scoringProfile).to.have.property("routeFocused");
+    expect(record.weights).to.deep.equal([60, 25, 15]);
+
+    // An explicit profile wins over the destination default
+    const overridden = await setupPair();
+    record = await program.account.matchRecord.fetch(
+      await initiateRawMatch(program, overridden.userA, overridden.tripA, overridden.tripB, {
+        scoringProfile: { interestFocused: {} },
+      })
+    );
+    expect(record.scoringProfile).to.have.property("interestFocused");
+    expect(record.weights).to.deep.equal([25, 25, 50]);
+
+    try {
+      await setDefault(4);
+      expect.fail("an unknown profile index should be rejected");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidScoringProfile");
+    }
+    console.log("✅ Destination default applied, initiator override respected");
+  });
+
+  it("Refunds rent to the funder when the counterparty cancels a pending match", async () => {
+    const { userA, userB, tripA, tripB, matchPDA } = await setupMatch();
+    const rent = await provider.connection.getBalance(matchPDA);
+    const funderBefore = await provider.connection.getBalance(userA.publicKey);
+
+    await program.methods
+      .cancelPendingMatch()
+      .accountsPartial({
+        matchAccount: matchPDA,
+        tripA,
+        tripB,
+        rentPayer: userA.publicKey,
+        user: userB.publicKey,
+        initiatorProfile: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+
+    const funderAfter = await provider.connection.getBalance(userA.publicKey);
+    expect(funderAfter - funderBefore).to.equal(rent);
+    expect(await provider.connection.getAccountInfo(matchPDA)).to.be.null;
+
+    const trip = await program.account.trip.fetch(tripA);
+    expect(trip.matchCount).to.equal(0);
+    console.log("✅ Rent refunded to the original funder");
+  });
+
+  it("Refunds rent to the funder when a rejected match is closed", async () => {
+    const { userA, userB, tripA, tripB, profileA, matchPDA } = await setupMatch();
+
+    await program.methods
+      .rejectMatch()
+      .accountsPartial({
+        matchAccount: matchPDA,
+        trip: tripB,
+        user: userB.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: profileA,
+        ownerProfileB: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+
+    const rent = await provider.connection.getBalance(matchPDA);
+    const funderBefore = await provider.connection.getBalance(userA.publicKey);
+
+    await program.methods
+      .closeMatch()
+      .accountsPartial({
+        matchAccount: matchPDA,
+        trip: tripB,
+        pairHistory: derivePairHistoryPDA(program.programId, tripA, tripB),
+        rentPayer: userA.publicKey,
+        user: userB.publicKey,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+
+    const funderAfter = await provider.connection.getBalance(userA.publicKey);
+    expect(funderAfter - funderBefore).to.equal(rent);
+    console.log("✅ Closed rejected match, rent back to funder");
+  });
+
+  it("Holds a rejected pair in the rematch cooldown, even after closing the record", async () => {
+    const { userA, userB, tripA, tripB, profileA, matchPDA } = await setupMatch();
+    const expectCooldown = async () => {
+      try {
+        await initiateRawMatch(program, userA, tripA, tripB);
+        throw new Error("Should have failed: the pair was just rejected");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("RematchCooldown");
+      }
+    };
+
+    await program.methods
+      .rejectMatch()
+      .accountsPartial({
+        matchAccount: matchPDA,
+        trip: tripB,
+        user: userB.publicKey,
+        initiatorProfile: null,
+        ownerProfileA: profileA,
+        ownerProfileB: null,
+      })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+    const { rejectedAt } = await program.account.matchRecord.fetch(matchPDA);
+
+    // The rejected record itself can't be reused yet
+    await expectCooldown();
+
+    // Closing it hands the rejection to the pair history
+    const pairHistory = derivePairHistoryPDA(program.programId, tripA, tripB);
+    await program.methods
+      .closeMatch()
+      .accountsPartial({ matchAccount: matchPDA, trip: tripB, pairHistory, rentPayer: userA.publicKey, user: userB.publicKey })
+      .signers([userB])
+      .rpc({ commitment: "confirmed" });
+    const history = await program.account.pairHistory.fetch(pairHistory);
+    expect(history.lastRejectedAt.toNumber()).to.equal(rejectedAt.toNumber());
+    await expectCooldown();
+    console.log("✅ Re-initiation refused inside the cooldown");
+  });
+
+  it("Caps re-initiation of the same trip pair", async () => {
+    const MAX_MATCHES_PER_PAIR = 3;
+    const { userA, tripA, tripB, matchPDA } = await setupMatch();
+
+    const cancel = () =>
+      program.methods
+        .cancelPendingMatch()
+        .accountsPartial({
+          matchAccount: matchPDA,
+          tripA,
+          tripB,
+          rentPayer: userA.publicKey,
+          user: userA.publicKey,
+          initiatorProfile: null,
+        })
+        .signers([userA])
+        .rpc({ commitment: "confirmed" });
+
+    // setupMatch used the first slot; use the rest
+    await cancel();
+    for (let i = 1; i < MAX_MATCHES_PER_PAIR; i++) {
+      await initiateRawMatch(program, userA, tripA, tripB);
+      await cancel();
+    }
+
+    try {
+      await initiateRawMatch(program, userA, tripA, tripB);
+      throw new Error("Should have failed: pair rematch limit reached");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("RematchLimitReached");
+    }
+    console.log("✅ Pair rematch limit enforced across closed matches");
+  });
+
+  it("Caps matches between two owners even across recreated trips", async () => {
+    const DAY = 24 * 60 * 60;
+    const DEFAULT_MAX_OWNER_PAIR_MATCHES = 10;
+    const config = await getOrInitProgramConfig(program, provider);
+    const setLimit = (limit: number) =>
+      program.methods
+        .setMaxOwnerPairMatches(limit)
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const ownerPairHistory = deriveOwnerPairHistoryPDA(program.programId, userA.publicKey, userB.publicKey);
+    const start = Math.floor(Date.now() / 1000) + 40 * DAY;
+    // Fresh trips every round, so the per-trip pair history never repeats
+    const freshPair = async (round: number) => ({
+      tripA: await createRawTrip(program, userA, { startDate: start + round * DAY }),
+      tripB: await createRawTrip(program, userB, { startDate: start + round * DAY }),
+    });
+
+    try {
+      await setLimit(2);
+      for (let round = 0; round < 2; round++) {
+        const { tripA, tripB } = await freshPair(round);
+        await initiateRawMatch(program, userA, tripA, tripB);
+        for (const [user, trip] of [[userA, tripA], [userB, tripB]] as const) {
+          await program.methods
+            .deactivateTrip()
+            .accountsPartial({ trip, user: user.publicKey })
+            .signers([user])
+            .rpc({ commitment: "confirmed" });
+        }
+      }
+      expect((await program.account.ownerPairHistory.fetch(ownerPairHistory)).matchCount).to.equal(2);
+
+      // Recreated trips don't reset the owners' counter, in either direction
+      const { tripA, tripB } = await freshPair(2);
+      for (const [payer, mine, theirs] of [[userA, tripA, tripB], [userB, tripB, tripA]] as const) {
+        try {
+          await initiateRawMatch(program, payer, mine, theirs);
+          expect.fail("the owner pair should be at its lifetime limit");
+        } catch (error: any) {
+          expect(error.error?.errorCode?.code).to.equal("OwnerPairLimitReached");
+        }
+      }
+      console.log("✅ Owner pair limit survives trip recreation");
+    } finally {
+      await setLimit(DEFAULT_MAX_OWNER_PAIR_MATCHES);
+    }
+  });
+
+  it("Holds back matching of trips younger than the configured minimum age", async () => {
+    const config = await getOrInitProgramConfig(program, provider);
+    const setMinTripAge = (secs: number) =>
+      program.methods
+        .setMinTripAge(new anchor.BN(secs))
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+    const MIN_AGE_SECS = 5;
+    try {
+      await setMinTripAge(MIN_AGE_SECS);
+      const userA = await createFundedUser(provider);
+      const userB = await createFundedUser(provider);
+      const tripA = await createRawTrip(program, userA);
+      const tripB = await createRawTrip(program, userB);
+
+      try {
+        await initiateRawMatch(program, userA, tripA, tripB);
+        expect.fail("a trip younger than the minimum age should not be matchable");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("TripTooNew");
+      }
+
+      // The localnet clock can't be warped, so wait the window out
+      await new Promise((resolve) => setTimeout(resolve, (MIN_AGE_SECS + 2) * 1000));
+      const matchPDA = await initiateRawMatch(program, userA, tripA, tripB);
+      expect((await program.account.matchRecord.fetch(matchPDA)).status).to.have.property("pending");
+      console.log("✅ Young trip held back, matchable once old enough");
+    } finally {
+      await setMinTripAge(0);
+    }
+  });
+
+  it("Caps a user's unresolved matches and frees a slot when one resolves", async () => {
+    const config = await getOrInitProgramConfig(program, provider);
+    const setCap = (cap: number) =>
+      program.methods
+        .setMaxPendingMatches(cap)
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+
+    const userA = await createFundedUser(provider);
+    const profileA = await createRawUserProfile(program, userA);
+    const tripA = await createRawTrip(program, userA);
+    const counterparties = [];
+    for (let i = 0; i < 3; i++) {
+      const user = await createFundedUser(provider);
+      counterparties.push({ user, trip: await createRawTrip(program, user) });
+    }
+
+    try {
+      await setCap(2);
+
+      // Without the initiator's profile there is nothing to count against
+      try {
+        await initiateRawMatch(program, userA, tripA, counterparties[0].trip);
+        expect.fail("initiating without a profile should fail while a cap is set");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("InitiatorProfileRequired");
+      }
+
+      const first = await initiateRawMatch(program, userA, tripA, counterparties[0].trip, { profileA });
+      await initiateRawMatch(program, userA, tripA, counterparties[1].trip, { profileA });
+      expect((await program.account.userProfile.fetch(profileA)).pendingMatchCount).to.equal(2);
+
+      try {
+        await initiateRawMatch(program, userA, tripA, counterparties[2].trip, { profileA });
+        expect.fail("a third unresolved match should exceed the cap");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("TooManyPendingMatches");
+      }
+      console.log("✅ Cap of 2 blocks a third initiation");
+
+      // Resolving a slot-holding match needs the initiator's profile
+      const { user: userB, trip: tripB } = counterparties[0];
+      try {
+        await program.methods
+          .rejectMatch()
+          .accountsPartial({
+            matchAccount: first,
+            trip: tripB,
+            user: userB.publicKey,
+            initiatorProfile: null,
+            ownerProfileA: null,
+            ownerProfileB: null,
+          })
+          .signers([userB])
+          .rpc();
+        expect.fail("rejecting without the initiator's profile should fail");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("InitiatorProfileRequired");
+      }
+
+      await program.methods
+        .rejectMatch()
+        .accountsPartial({
+          matchAccount: first,
+          trip: tripB,
+          user: userB.publicKey,
+          initiatorProfile: profileA,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([userB])
+        .rpc({ commitment: "confirmed" });
+      expect((await program.account.userProfile.fetch(profileA)).pendingMatchCount).to.equal(1);
+
+      await initiateRawMatch(program, userA, tripA, counterparties[2].trip, { profileA });
+      expect((await program.account.userProfile.fetch(profileA)).pendingMatchCount).to.equal(2);
+      console.log("✅ Rejecting a match frees a slot for a new initiation");
+    } finally {
+      await setCap(0);
+    }
+  });
+
+  it("Recommends compute units that scale with the encrypted payload", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    await createRawUserProfile(program, userA);
+    await createRawUserProfile(program, userB);
+
+    // Route payloads of 2, 12 and 22 encrypted fields on trip A
+    const estimates = [];
+    for (const fields of [2, 12, 22]) {
+      const tripA = await createRawTrip(program, userA, {
+        startDate: Math.floor(Date.now() / 1000) + (fields + 1) * 24 * 60 * 60,
+        encryptedWaypoints: randomBytes(fields * 32),
+      });
+      const tripB = await createRawTrip(program, userB, {
+        startDate: Math.floor(Date.now() / 1000) + (fields + 1) * 24 * 60 * 60,
+      });
+
+      const simulation = await program.methods
+        .estimateMatchArgs()
+        .accountsPartial({ tripA, tripB })
+        .simulate();
+      const event = simulation.events.find((e) => e.name === "matchArgsEstimate");
+      estimates.push(event.data);
+    }
+
+    const [small, medium, large] = estimates;
+    expect(medium.encryptedFields - small.encryptedFields).to.equal(10);
+    expect(large.encryptedFields - medium.encryptedFields).to.equal(10);
+    expect(large.argumentCount - small.argumentCount).to.equal(20);
+
+    // Linear in the field count: equal steps for equal payload growth
+    const step = medium.recommendedComputeUnits - small.recommendedComputeUnits;
+    expect(step).to.be.greaterThan(0);
+    expect(large.recommendedComputeUnits - medium.recommendedComputeUnits).to.equal(step);
+    expect(large.recommendedComputeUnits).to.be.at.most(1_400_000);
+    console.log(
+      `✅ CU estimates: ${small.recommendedComputeUnits} / ${medium.recommendedComputeUnits} / ${large.recommendedComputeUnits}`
+    );
+  });
+
+  it("Rejects initiating a match of a trip with itself", async () => {
+    const user = await createFundedUser(provider);
+    const trip = await createRawTrip(program, user);
+
+    try {
+      await initiateRawMatch(program, user, trip, trip);
+      throw new Error("Should have failed: same trip in both slots");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("SameTripMatch");
+    }
+
+    const account = await program.account.trip.fetch(trip);
+    expect(account.matchCount).to.equal(0);
+    console.log("✅ Identical trip accounts rejected");
+  });
+
+  it("Rejects initiating a match with an inactive or already-ended trip", async () => {
+    const DAY = 24 * 60 * 60;
+    const now = Math.floor(Date.now() / 1000);
+    const userA = await createFundedUser(provider);
+    const tripA = await createRawTrip(program, userA);
+
+    const inactiveUser = await createFundedUser(provider);
+    const inactive = await createRawTrip(program, inactiveUser);
+    await program.methods
+      .deactivateTrip()
+      .accountsPartial({ trip: inactive, user: inactiveUser.publicKey })
+      .signers([inactiveUser])
+      .rpc({ commitment: "confirmed" });
+    try {
+      await initiateRawMatch(program, userA, tripA, inactive);
+      expect.fail("an inactive trip should not be matchable");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("TripNotActive");
+    }
+
+    const pastUser = await createFundedUser(provider);
+    const past = await createRawTrip(program, pastUser, { startDate: now - 10 * DAY, endDate: now - 3 * DAY });
+    try {
+      await initiateRawMatch(program, userA, tripA, past);
+      expect.fail("a trip that already ended should not be matchable");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("TripExpired");
+    }
+    console.log("✅ Inactive and ended trips rejected");
+  });
+
+  it("Exports a user's trips and matches and skips accounts they don't own", async () => {
+    const DAY = 24 * 60 * 60;
+    const now = Math.floor(Date.now() / 1000);
+    const user = await createFundedUser(provider);
+    const other = await createFundedUser(provider);
+    const userProfile = await createRawUserProfile(program, user);
+
+    const tripOne = await createRawTrip(program, user, { startDate: now + 10 * DAY });
+    const tripTwo = await createRawTrip(program, user, { startDate: now + 40 * DAY });
+    const otherTrip = await createRawTrip(program, other, { startDate: now + 10 * DAY });
+    const otherTripTwo = await createRawTrip(program, other, { startDate: now + 40 * DAY });
+    const userMatch = await initiateRawMatch(program, user, tripOne, otherTrip);
+    // Involves only the other user's trips
+    const thirdUser = await createFundedUser(provider);
+    const thirdTrip = await createRawTrip(program, thirdUser, { startDate: now + 40 * DAY });
+    const foreignMatch = await initiateRawMatch(program, other, otherTripTwo, thirdTrip);
+
+    const accounts = [foreignMatch, userMatch, otherTrip, tripTwo, tripOne];
+    const simulation = await program.methods
+      .exportUserData()
+      .accountsPartial({ userProfile })
+      .remainingAccounts(
+        accounts.map((pubkey) => ({ pubkey, isSigner: false, isWritable: false }))
+      )
+      .simulate();
+    const byName = (name: string) =>
+      simulation.events.filter((e) => e.name === name).map((e) => e.data);
+
+    const [profileEvent] = byName("userProfileExported");
+    expect(profileEvent.userProfile.toBase58()).to.equal(userProfile.toBase58());
+    expect(profileEvent.owner.toBase58()).to.equal(user.publicKey.toBase58());
+
+    const exportedTrips = byName("userTripExported").map((e) => e.trip.toBase58());
+    expect(exportedTrips).to.have.members([tripOne.toBase58(), tripTwo.toBase58()]);
+
+    const exportedMatches = byName("userMatchExported");
+    expect(exportedMatches.map((e) => e.matchRecord.toBase58())).to.deep.equal([userMatch.toBase58()]);
+    expect(exportedMatches[0].status).to.have.property("pending");
+
+    const [completed] = byName("userDataExportCompleted");
+    expect(completed.tripsExported).to.equal(2);
+    expect(completed.matchesExported).to.equal(1);
+    expect(completed.skipped).to.equal(2); // otherTrip + foreignMatch
+    console.log("✅ Export covers the user's accounts and skips foreign ones");
+  });
+
+  it("Lets a flexible trip score a date-adjacent candidate a rigid trip can't", async () => {
+    const DAY = 24 * 60 * 60;
+    const now = Math.floor(Date.now() / 1000);
+    const start = now + 90 * DAY;
+    const estimatedDateOverlap = async (tripA: PublicKey, tripB: PublicKey) => {
+      const simulation = await program.methods.estimateCompatibility().accountsPartial({ tripA, tripB }).simulate();
+      return simulation.events.find((e) => e.name === "compatibilityEstimate").data.dateOverlap;
+    };
+
+    // Candidate starts 5 days after the initiators' week-long trips end
+    const candidateUser = await createFundedUser(provider);
+    const candidate = await createRawTrip(program, candidateUser, {
+      startDate: start + 12 * DAY,
+      endDate: start + 19 * DAY,
+    });
+
+    // Dates never gate initiation; a rigid trip just scores the gap as 0
+    const rigidUser = await createFundedUser(provider);
+    const rigid = await createRawTrip(program, rigidUser, { startDate: start, endDate: start + 7 * DAY });
+    expect(await estimatedDateOverlap(rigid, candidate)).to.equal(0);
+    const rigidMatch = await initiateRawMatch(program, rigidUser, rigid, candidate);
+    expect((await program.account.matchRecord.fetch(rigidMatch)).status).to.have.property("pending");
+
+    // Flexibility 50 lets the trip shift by half of 14 days = 7 days, turning
+    // the 5-day gap into a 2-day overlap: 2 * 200 / 14 days = 28
+    const flexibleUser = await createFundedUser(provider);
+    const flexible = await createRawTrip(program, flexibleUser, { startDate: start, endDate: start + 7 * DAY });
+    await program.methods
+      .setTripFlexibility(50)
+      .accountsPartial({ trip: flexible, user: flexibleUser.publicKey })
+      .signers([flexibleUser])
+      .rpc({ commitment: "confirmed" });
+    expect((await program.account.trip.fetch(flexible)).flexibility).to.equal(50);
+    expect(await estimatedDateOverlap(flexible, candidate)).to.equal(28);
+    // The more flexible trip of the pair sets the tolerance, on either side
+    expect(await estimatedDateOverlap(candidate, flexible)).to.equal(28);
+
+    try {
+      await program.methods
+        .setTripFlexibility(101)
+        .accountsPartial({ trip: flexible, user: flexibleUser.publicKey })
+        .signers([flexibleUser])
+        .rpc();
+      expect.fail("flexibility above 100 should be rejected");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidFlexibility");
+    }
+    console.log("✅ Flexibility 50 scores a 5-day gap that flexibility 0 scores as 0");
+  });
+
+  it("Filters match initiation by traveler type and seeking type", async () => {
+    const LOCAL = 0;
+    const TOURIST = 1;
+    const NOMAD = 2;
+    const ANY = 255;
+
+    const typedTrip = async (travelerType: number, seekingType: number) => {
+      const user = await createFundedUser(provider);
+      const trip = await createRawTrip(program, user);
+      await program.methods
+        .setTripType(travelerType, seekingType)
+        .accountsPartial({ trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      return { user, trip };
+    };
+
+    // Local seeking tourists <-> tourist seeking locals
+    const local = await typedTrip(LOCAL, TOURIST);
+    const tourist = await typedTrip(TOURIST, LOCAL);
+    await initiateRawMatch(program, local.user, local.trip, tourist.trip);
+    console.log("✅ Complementary types match");
+
+    // Wildcard tourist <-> local seeking tourists
+    const wildcardTourist = await typedTrip(TOURIST, ANY);
+    await initiateRawMatch(program, wildcardTourist.user, wildcardTourist.trip, local.trip);
+    console.log("✅ Wildcard seeker matches");
+
+    // Local seeking tourists excludes a nomad, even if the nomad accepts anyone
+    const nomad = await typedTrip(NOMAD, ANY);
+    for (const [initiator, counterparty] of [
+      [local, nomad],
+      [nomad, local],
+    ] as const) {
+      try {
+        await initiateRawMatch(program, initiator.user, initiator.trip, counterparty.trip);
+        throw new Error("Should have failed: seeking type excludes counterparty");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("TypeMismatch");
+      }
+    }
+    console.log("✅ Excluded type rejected in both directions");
+
+    // Out-of-range types are refused
+    try {
+      await typedTrip(3, ANY);
+      throw new Error("Should have failed: invalid traveler type");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidTravelerType");
+    }
+  });
+});
@@ -0,0 +1,173 @@
+import { expect } from "chai";
+import {
+  calculateRouteSimilarity,
+  calculateRouteSimilarityMultires,
+  computeRouteDensity,
+  computeRouteDensityInputs,
+  getNeighboringCells,
+  h3ToU64,
+  latLngToH3Cell,
+  validateRouteContinuity,
+  waypointToDestinationCell,
+} from "../../../apps/web/src/lib/geo/h3";
+import { createSampleTripData, denseUrbanRoute, sparseCrossCountryRoute } from "./utils";
+
+describe("Route Continuity Validation", () => {
+  it("Accepts a continuous route", () => {
+    // SF -> LA coastal route, each hop a few hundred km at most
+    const cells = createSampleTripData().waypoints.map((w) => latLngToH3Cell(w.lat, w.lng));
+
+    expect(() => validateRouteContinuity(cells)).to.not.throw();
+    console.log("✅ Continuous route accepted");
+  });
+
+  it("Rejects a route that teleports between continents", () => {
+    const cells = [
+      latLngToH3Cell(37.7749, -122.4194), // San Francisco
+      latLngToH3Cell(37.3382, -121.8863), // San Jose
+      latLngToH3Cell(35.6762, 139.6503),  // Tokyo
+    ];
+
+    expect(() => validateRouteContinuity(cells)).to.throw(/between waypoints 1 and 2/);
+    console.log("✅ Teleporting route rejected");
+  });
+
+  it("Honors a tighter gap limit", () => {
+    const cells = [
+      latLngToH3Cell(37.7749, -122.4194), // San Francisco
+      latLngToH3Cell(37.3382, -121.8863), // San Jose (~70 km)
+    ];
+
+    expect(() => validateRouteContinuity(cells, 5)).to.throw(/max 5/);
+  });
+});
+
+describe("Route Density", () => {
+  it("Separates a dense urban route from a sparse cross-country route", () => {
+    const toCells = (route: { lat: number; lng: number }[]) =>
+      route.map((w) => latLngToH3Cell(w.lat, w.lng));
+    const urban = computeRouteDensityInputs(toCells(denseUrbanRoute()));
+    const sparse = computeRouteDensityInputs(toCells(sparseCrossCountryRoute()));
+
+    expect(urban.waypointCount).to.equal(sparse.waypointCount);
+    expect(urban.areaBucket).to.be.lessThan(sparse.areaBucket);
+
+    const urbanDensity = computeRouteDensity(urban.waypointCount, urban.areaBucket);
+    const sparseDensity = computeRouteDensity(sparse.waypointCount, sparse.areaBucket);
+    expect(urbanDensity).to.be.greaterThan(sparseDensity * 2);
+    console.log(`✅ Urban density ${urbanDensity} vs cross-country ${sparseDensity}`);
+  });
+
+  it("Never reports a box smaller than the route's own cells", () => {
+    // Straight east-west line: zero-height bounding box
+    const cells = [0, 1, 2, 3].map((i) => latLngToH3Cell(0, i * 0.05));
+    const { areaBucket } = computeRouteDensityInputs(cells);
+
+    expect(areaBucket).to.be.at.least(Math.floor(Math.log2(cells.length)));
+  });
+});
+
+// The web client's copy of compute_route_similarity_multires; the circuit's
+// arithmetic is tested through its mirror in programs/triper/src/scoring.rs
+describe("Multi-Resolution Route Similarity", () => {
+  const route = () => createSampleTripData().waypoints.map((w) => latLngToH3Cell(w.lat, w.lng));
+
+  // A different level-7 cell under the same level-6 parent: a near miss
+  const sibling = (cell: string) =>
+    getNeighboringCells(cell, 1).find(
+      (n) => n !== cell && waypointToDestinationCell(n) === waypointToDestinationCell(cell)
+    );
+
+  it("Derives level-6 parents with the circuit's bit shift", () => {
+    const cells = route().flatMap((cell) => getNeighboringCells(cell, 1));
+    for (const a of cells) {
+      for (const b of cells) {
+        const sameParent = waypointToDestinationCell(a) === waypointToDestinationCell(b);
+        expect((h3ToU64(a) >> BigInt(27)) === (h3ToU64(b) >> BigInt(27))).to.equal(sameParent);
+      }
+    }
+  });
+
+  it("Gives partial credit to routes that nearly align", () => {
+    const cellsA = route();
+    const cellsB = cellsA.map(sibling);
+    expect(cellsB.every((cell) => cell !== undefined)).to.be.true;
+
+    const singleRes = calculateRouteSimilarityMultires(cellsA, cellsB, 0);
+    const multiRes = calculateRouteSimilarityMultires(cellsA, cellsB, 30);
+
+    // No shared level-7 cell, every level-6 parent shared
+    expect(singleRes).to.equal(0);
+    expect(multiRes).to.equal(30);
+    console.log(`✅ Near-miss route: single-res ${singleRes}, multi-res ${multiRes}`);
+  });
+
+  it("Matches single-resolution scoring for identical and unrelated routes", () => {
+    const cells = route();
+    expect(calculateRouteSimilarityMultires(cells, cells, 0)).to.equal(100);
+    expect(calculateRouteSimilarityMultires(cells, cells, 30)).to.equal(100);
+
+    const faraway = [latLngToH3Cell(35.6762, 139.6503), latLngToH3Cell(35.0116, 135.7681)];
+    expect(calculateRouteSimilarityMultires(cells, faraway, 0)).to.equal(0);
+    expect(calculateRouteSimilarityMultires(cells, faraway, 30)).to.equal(0);
+  });
+
+  it("Never lets a shared parent count twice", () => {
+    // Two cells of A under one parent vs a single B cell in that parent
+    const [first] = route();
+    const cellsA = [first, sibling(first)];
+    const cellsB = [first];
+
+    // Fine: 1/2 = 50, coarse: 1/2 = 50 (not 2/1)
+    expect(calculateRouteSimilarityMultires(cellsA, cellsB, 100)).to.equal(50);
+    expect(calculateRouteSimilarityMultires(cellsA, cellsB, 30)).to.equal(50);
+  });
+});
+
+// The web client's copy of compute_route_similarity; the circuit's parent
+// credit is tested through its mirror in programs/triper/src/scoring.rs
+describe("Hierarchical Route Similarity", () => {
+  const route = () => createSampleTripData().waypoints.map((w) => latLngToH3Cell(w.lat, w.lng));
+
+  const sibling = (cell: string) =>
+    getNeighboringCells(cell, 1).find(
+      (n) => n !== cell && waypointToDestinationCell(n) === waypointToDestinationCell(cell)
+    )!;
+
+  it("Scores exact matches fully and parent-only matches at half", () => {
+    const cells = route();
+    expect(calculateRouteSimilarity(cells, cells)).to.equal(100);
+
+    // Every waypoint moved to a sibling cell: all parents shared, no exact cell
+    const nearMiss = cells.map(sibling);
+    expect(calculateRouteSimilarity(cells, nearMiss)).to.equal(50);
+
+    // One waypoint nudged across a cell boundary, the rest exact: (4 + 0.5) / 5
+    const nudged = [sibling(cells[0]), ...cells.slice(1)];
+    expect(calculateRouteSimilarity(cells, nudged)).to.equal(90);
+    console.log("✅ Near-miss waypoints earn half credit");
+  });
+
+  it("Keeps unrelated routes at 0 and never exceeds 100", () => {
+    const cells = route();
+    const faraway = [latLngToH3Cell(35.6762, 139.6503), latLngToH3Cell(35.0116, 135.7681)];
+    expect(calculateRouteSimilarity(cells, faraway)).to.equal(0);
+    expect(calculateRouteSimilarity(cells, [])).to.equal(0);
+
+    // Two cells of A under one parent vs that parent's single B cell:
+    // one exact match over a union of 2
+    const [first] = cells;
+    expect(calculateRouteSimilarity([first, sibling(first)], [first])).to.equal(50);
+    expect(calculateRouteSimilarity([first, sibling(first)], [first, sibling(first)])).to.equal(100);
+  });
+
+  it("Matches a repeated exact cell at most once", () => {
+    // A repeats one cell, B holds it once: the circuit pairs cells one-to-one,
+    // so exact (1) never exceeds parent (1) and the score stays 1 / 2
+    const [first] = route();
+    expect(calculateRouteSimilarity([first, first], [first])).to.equal(50);
+    expect(calculateRouteSimilarity([first], [first, first])).to.equal(50);
+    expect(calculateRouteSimilarity([first, first], [first, first])).to.equal(100);
+    console.log("✅ Duplicate cells cannot inflate the exact intersection");
+  });
+});
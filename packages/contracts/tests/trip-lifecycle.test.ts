@@ -0,0 +1,1136 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Triper } from "../target/types/triper";
+import { expect } from "chai";
+import { createHash, randomBytes } from "crypto";
+import {
+  createFundedUser,
+  createRawTrip,
+  createRawUserProfile,
+  denseUrbanRoute,
+  getOrInitProgramConfig,
+  initiateRawMatch,
+  sparseCrossCountryRoute,
+} from "./utils";
+import { computeRouteDensityInputs, latLngToH3Cell } from "../../../apps/web/src/lib/geo/h3";
+import {
+  DESTINATION_SHARD_CAPACITY,
+  deriveDestinationShardPDA,
+  derivePayloadRegistryPDA,
+  deriveTripPDA,
+  findOpenDestinationShard,
+} from "../../../apps/web/src/lib/solana/create-trip";
+
+describe("Trip Lifecycle", () => {
+  // Configure the client to use the local cluster
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.Triper as Program<Triper>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+
+  const DAY = 24 * 60 * 60;
+
+  it("Clones a trip into two date variants sharing the route payload", async () => {
+    const user = await createFundedUser(provider);
+    const sourceTrip = await createRawTrip(program, user);
+    const source = await program.account.trip.fetch(sourceTrip);
+    const userProfile = await createRawUserProfile(program, user);
+
+    console.log("\n🧬 Cloning trip:", sourceTrip.toBase58());
+
+    const starts = [source.startDate.toNumber() + 7 * DAY, source.startDate.toNumber() + 14 * DAY];
+
+    const clones = [];
+    for (const start of starts) {
+      // Clones take the owner's next trip index, like create_trip
+      const { nextTripIndex, tripCount } = await program.account.userProfile.fetch(userProfile);
+      const clonePDA = deriveTripPDA(program.programId, user.publicKey, nextTripIndex);
+      const { shardId, destinationShard, previousShard } = await findOpenDestinationShard(
+        program,
+        Buffer.from(source.destinationGridHash)
+      );
+
+      await program.methods
+        .cloneTrip(new anchor.BN(start), new anchor.BN(start + 5 * DAY), shardId)
+        .accountsPartial({
+          sourceTrip,
+          trip: clonePDA,
+          userProfile,
+          destinationShard,
+          previousShard,
+          user: user.publicKey,
+        })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+
+      const profile = await program.account.userProfile.fetch(userProfile);
+      expect(profile.nextTripIndex).to.equal(nextTripIndex + 1);
+      expect(profile.tripCount).to.equal(tripCount + 1);
+
+      // Clones are indexed under the source's destination
+      const shard = await program.account.destinationIndexShard.fetch(destinationShard);
+      expect(shard.trips.map((t) => t.toBase58())).to.include(clonePDA.toBase58());
+      clones.push(await program.account.trip.fetch(clonePDA));
+    }
+
+    for (const clone of clones) {
+      expect(clone.owner.toBase58()).to.equal(user.publicKey.toBase58());
+      expect(Buffer.from(clone.encryptedWaypoints).equals(Buffer.from(source.encryptedWaypoints))).to.be.true;
+      expect(clone.destinationGridHash).to.deep.equal(source.destinationGridHash);
+      expect(clone.publicKey).to.deep.equal(source.publicKey);
+      expect(clone.isActive).to.be.true;
+    }
+    expect(clones[0].startDate.toNumber()).to.not.equal(clones[1].startDate.toNumber());
+    expect(clones[0].endDate.toNumber()).to.not.equal(clones[1].endDate.toNumber());
+    console.log("✅ Clones share the route but differ in dates");
+  });
+
+  it("Deactivates a trip and tolerates a second idempotent deactivation", async () => {
+    const user = await createFundedUser(provider);
+    const trip = await createRawTrip(program, user);
+
+    await program.methods
+      .deactivateTripIdempotent()
+      .accountsPartial({ trip, user: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    let account = await program.account.trip.fetch(trip);
+    expect(account.isActive).to.be.false;
+
+    // Second call is a no-op instead of TripNotActive
+    await program.methods
+      .deactivateTripIdempotent()
+      .accountsPartial({ trip, user: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    account = await program.account.trip.fetch(trip);
+    expect(account.isActive).to.be.false;
+
+    // Strict version still rejects the double deactivation
+    try {
+      await program.methods
+        .deactivateTrip()
+        .accountsPartial({ trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("strict deactivate_trip should reject an inactive trip");
+    } catch (error: any) {
+      expect(error.toString()).to.include("TripNotActive");
+    }
+    console.log("✅ Idempotent deactivation succeeds twice, strict version still errors");
+  });
+
+  it("Rotates a trip key and marks existing matches stale", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+    const matchPDA = await initiateRawMatch(program, userA, tripA, tripB);
+
+    const newKey = randomBytes(32);
+    const newWaypoints = randomBytes(22 * 32);
+
+    await program.methods
+      .rotateTripKey(newWaypoints, Array.from(newKey))
+      .accountsPartial({ trip: tripA, user: userA.publicKey })
+      .remainingAccounts([{ pubkey: matchPDA, isWritable: true, isSigner: false }])
+      .signers([userA])
+      .rpc({ commitment: "confirmed" });
+
+    const trip = await program.account.trip.fetch(tripA);
+    expect(Buffer.from(trip.publicKey).equals(newKey)).to.be.true;
+    expect(Buffer.from(trip.encryptedWaypoints).equals(newWaypoints)).to.be.true;
+
+    const record = await program.account.matchRecord.fetch(matchPDA);
+    expect(record.isStale).to.be.true;
+
+    // Stale scores can't be accepted until recomputed
+    try {
+      await program.methods
+        .acceptMatch()
+        .accountsPartial({
+          matchAccount: matchPDA,
+          trip: tripB,
+          counterpartyTrip: tripA,
+          user: userB.publicKey,
+          initiatorProfile: null,
+          ownerProfileA: null,
+          ownerProfileB: null,
+        })
+        .signers([userB])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("accepting a stale match should fail");
+    } catch (error: any) {
+      expect(error.toString()).to.include("StaleMatch");
+    }
+    console.log("✅ Key rotated and match marked stale");
+  });
+
+  it("Updates a trip's dates and route in place and resets its quota", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const tripA = await createRawTrip(program, userA);
+    const tripB = await createRawTrip(program, userB);
+    await initiateRawMatch(program, userA, tripA, tripB);
+    expect((await program.account.trip.fetch(tripA)).matchCount).to.equal(1);
+
+    const update = (user: anchor.web3.Keypair, startDate: number, endDate: number) =>
+      program.methods
+        .updateTrip(new anchor.BN(startDate), new anchor.BN(endDate), randomBytes(22 * 32), Array.from(randomBytes(32)))
+        .accountsPartial({ trip: tripA, owner: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+
+    const startDate = Math.floor(Date.now() / 1000) + 60 * DAY;
+    await update(userA, startDate, startDate + 5 * DAY);
+
+    const trip = await program.account.trip.fetch(tripA);
+    expect(trip.startDate.toNumber()).to.equal(startDate);
+    expect(trip.endDate.toNumber()).to.equal(startDate + 5 * DAY);
+    expect(trip.matchCount).to.equal(0);
+    expect(trip.updatedAt.toNumber()).to.be.at.least(trip.createdAt.toNumber());
+
+    const expectError = async (attempt: Promise<string>, code: string) => {
+      try {
+        await attempt;
+        expect.fail(`update should fail with ${code}`);
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal(code);
+      }
+    };
+    await expectError(update(userA, startDate, startDate), "InvalidDateRange");
+    await expectError(update(userB, startDate, startDate + DAY), "Unauthorized");
+
+    await program.methods
+      .deactivateTrip()
+      .accountsPartial({ trip: tripA, user: userA.publicKey })
+      .signers([userA])
+      .rpc({ commitment: "confirmed" });
+    await expectError(update(userA, startDate, startDate + DAY), "TripNotActive");
+    console.log("✅ Trip updated in place; invalid, foreign and inactive updates rejected");
+  });
+
+  it("Boosts a trip for a paid window and stacks repeat boosts", async () => {
+    const user = await createFundedUser(provider);
+    const trip = await createRawTrip(program, user);
+    const [treasury] = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury")],
+      program.programId
+    );
+    const BOOST_LAMPORTS_PER_DAY = 10_000_000;
+
+    let account = await program.account.trip.fetch(trip);
+    expect(account.boostUntil.toNumber()).to.equal(0);
+
+    const treasuryBefore = await provider.connection.getBalance(treasury);
+    await program.methods
+      .boostTrip(1)
+      .accountsPartial({ trip, treasury, user: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    const treasuryAfter = await provider.connection.getBalance(treasury);
+    expect(treasuryAfter - treasuryBefore).to.equal(BOOST_LAMPORTS_PER_DAY);
+
+    account = await program.account.trip.fetch(trip);
+    const now = Math.floor(Date.now() / 1000);
+    const firstExpiry = account.boostUntil.toNumber();
+    expect(firstExpiry).to.be.greaterThan(now);
+    expect(firstExpiry).to.be.at.most(now + DAY + 60);
+
+    // A second boost extends from the current expiry, not from now
+    await program.methods
+      .boostTrip(2)
+      .accountsPartial({ trip, treasury, user: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    account = await program.account.trip.fetch(trip);
+    expect(account.boostUntil.toNumber()).to.equal(firstExpiry + 2 * DAY);
+    console.log("✅ Boost paid and extended");
+  });
+
+  it("Rejects a boost outside the allowed duration", async () => {
+    const user = await createFundedUser(provider);
+    const trip = await createRawTrip(program, user);
+
+    try {
+      await program.methods
+        .boostTrip(0)
+        .accountsPartial({ trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("zero-day boost should fail");
+    } catch (error: any) {
+      expect(error.toString()).to.include("InvalidBoostDuration");
+    }
+  });
+
+  it("Estimates public compatibility for overlapping and disjoint trips", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const userC = await createFundedUser(provider);
+    const start = Math.floor(Date.now() / 1000) + 30 * DAY;
+    const tokyo = Buffer.alloc(32, 7);
+
+    // A: days 0-10, B: days 5-15 (same destination), C: days 20-30 (elsewhere)
+    const tripA = await createRawTrip(program, userA, {
+      startDate: start,
+      endDate: start + 10 * DAY,
+      destinationGridHash: tokyo,
+    });
+    const tripB = await createRawTrip(program, userB, {
+      startDate: start + 5 * DAY,
+      endDate: start + 15 * DAY,
+      destinationGridHash: tokyo,
+    });
+    const tripC = await createRawTrip(program, userC, {
+      startDate: start + 20 * DAY,
+      endDate: start + 30 * DAY,
+      destinationGridHash: Buffer.alloc(32, 9),
+    });
+
+    const overlapping = await program.methods
+      .estimateCompatibility()
+      .accountsPartial({ tripA, tripB })
+      .simulate();
+    const overlapEvent = overlapping.events.find((e) => e.name === "compatibilityEstimate");
+    expect(overlapEvent.data.dateOverlap).to.equal(50);
+    expect(overlapEvent.data.destinationMatch).to.be.true;
+
+    const disjoint = await program.methods
+      .estimateCompatibility()
+      .accountsPartial({ tripA, tripB: tripC })
+      .simulate();
+    const disjointEvent = disjoint.events.find((e) => e.name === "compatibilityEstimate");
+    expect(disjointEvent.data.dateOverlap).to.equal(0);
+    expect(disjointEvent.data.destinationMatch).to.be.false;
+    console.log("✅ Public estimates match expected overlap and destination");
+  });
+
+  it("Previews a partial score from public dates and published interests", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const profileA = await createRawUserProfile(program, userA);
+    const profileB = await createRawUserProfile(program, userB);
+    const start = Math.floor(Date.now() / 1000) + 50 * DAY;
+
+    // A: days 0-10, B: days 5-15 -> 50% date overlap
+    const tripA = await createRawTrip(program, userA, { startDate: start, endDate: start + 10 * DAY });
+    const tripB = await createRawTrip(program, userB, { startDate: start + 5 * DAY, endDate: start + 15 * DAY });
+
+    const preview = async () => {
+      const builder = program.methods
+        .previewMatch()
+        .accountsPartial({ payer: userA.publicKey, tripA, tripB, profileA, profileB })
+        .signers([userA]);
+      const simulation = await builder.simulate();
+      await builder.rpc({ commitment: "confirmed" });
+      return simulation.events.find((e) => e.name === "matchPreviewed").data;
+    };
+    const publish = (user: anchor.web3.Keypair, profile: PublicKey, interests: number | null) =>
+      program.methods
+        .setPublicInterests(interests)
+        .accountsPartial({ userProfile: profile, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+
+    // Only A publishes: dates alone make up the partial score
+    await publish(userA, profileA, 0b0111);
+    const datesOnly = await preview();
+    expect(datesOnly.dateScore).to.equal(50);
+    expect(datesOnly.interestScore).to.be.null;
+    expect(datesOnly.partialScore).to.equal(50);
+
+    // Both publish: {0,1,2} vs {1,2,3} -> 2/4 = 50% interests
+    await publish(userB, profileB, 0b1110);
+    expect((await preview()).interestScore).to.equal(50);
+
+    // Identical sets -> 100% interests, partial = (50*35 + 100*25) / 60 = 70
+    await publish(userB, profileB, 0b0111);
+    const withInterests = await preview();
+    expect(withInterests.interestScore).to.equal(100);
+    expect(withInterests.partialScore).to.equal(70);
+
+    const stored = await program.account.matchPreview.fetch(withInterests.matchPreview);
+    expect(stored.interestScore).to.equal(100);
+    expect(stored.routeScore).to.be.null;
+    console.log("✅ Preview scores dates and public interests, never routes");
+  });
+
+  it("Compares destination hashes only within a salt epoch", async () => {
+    const config = await getOrInitProgramConfig(program, provider);
+    const start = Math.floor(Date.now() / 1000) + 45 * DAY;
+    const destination = randomBytes(32);
+    const estimate = async (tripA: PublicKey, tripB: PublicKey) => {
+      const simulation = await program.methods.estimateCompatibility().accountsPartial({ tripA, tripB }).simulate();
+      return simulation.events.find((e) => e.name === "compatibilityEstimate").data;
+    };
+
+    const oldUser = await createFundedUser(provider);
+    const oldTrip = await createRawTrip(program, oldUser, { startDate: start, destinationGridHash: destination });
+    const oldEpoch = (await program.account.trip.fetch(oldTrip)).saltEpoch;
+
+    try {
+      await program.methods
+        .rotateDestinationSalt(Array.from(Buffer.alloc(32)))
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc();
+      expect.fail("a zero salt commitment should be rejected");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidSaltCommitment");
+    }
+
+    await program.methods
+      .rotateDestinationSalt(Array.from(randomBytes(32)))
+      .accountsPartial({ config, admin: provider.wallet.publicKey })
+      .rpc({ commitment: "confirmed" });
+    expect((await program.account.programConfig.fetch(config)).destinationSaltEpoch).to.equal(oldEpoch + 1);
+
+    // The hashes are byte-equal here, but a real client would have salted
+    // them differently - across epochs they are not compared at all
+    const newUser = await createFundedUser(provider);
+    const newTrip = await createRawTrip(program, newUser, { startDate: start, destinationGridHash: destination });
+    expect((await program.account.trip.fetch(newTrip)).saltEpoch).to.equal(oldEpoch + 1);
+
+    const crossEpoch = await estimate(oldTrip, newTrip);
+    expect(crossEpoch.destinationComparable).to.be.false;
+    expect(crossEpoch.destinationMatch).to.be.false;
+
+    const otherUser = await createFundedUser(provider);
+    const otherTrip = await createRawTrip(program, otherUser, { startDate: start, destinationGridHash: destination });
+    const sameEpoch = await estimate(newTrip, otherTrip);
+    expect(sameEpoch.destinationComparable).to.be.true;
+    expect(sameEpoch.destinationMatch).to.be.true;
+
+    // Migration: the old trip is rehashed under the current salt
+    await program.methods
+      .rehashTripDestination(Array.from(destination))
+      .accountsPartial({ trip: oldTrip, user: oldUser.publicKey })
+      .signers([oldUser])
+      .rpc({ commitment: "confirmed" });
+    const migrated = await estimate(oldTrip, newTrip);
+    expect(migrated.destinationComparable).to.be.true;
+    expect(migrated.destinationMatch).to.be.true;
+    console.log("✅ Destination hashes compared within an epoch, cross-epoch flagged until rehashed");
+  });
+
+  it("Floors and clamps date overlap estimates like the circuit", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const userC = await createFundedUser(provider);
+    const start = Math.floor(Date.now() / 1000) + 60 * DAY;
+
+    // A: days 0-10, B: days 2-4 (inside A), C: same dates as A
+    const tripA = await createRawTrip(program, userA, { startDate: start, endDate: start + 10 * DAY });
+    const tripB = await createRawTrip(program, userB, {
+      startDate: start + 2 * DAY,
+      endDate: start + 4 * DAY,
+    });
+    const tripC = await createRawTrip(program, userC, { startDate: start, endDate: start + 10 * DAY });
+
+    const estimate = async (tripB: PublicKey) => {
+      const simulation = await program.methods
+        .estimateCompatibility()
+        .accountsPartial({ tripA, tripB })
+        .simulate();
+      return simulation.events.find((e) => e.name === "compatibilityEstimate").data.dateOverlap;
+    };
+
+    // 2 days of overlap over a 6-day average duration = 33.3% -> floored
+    expect(await estimate(tripB)).to.equal(33);
+    // Identical ranges hit the 100 ceiling exactly
+    expect(await estimate(tripC)).to.equal(100);
+    console.log("✅ Overlap estimates floor and clamp to 0-100");
+  });
+
+  it("Scores the shortest trips over the exact average duration", async () => {
+    const start = Math.floor(Date.now() / 1000) + 70 * DAY;
+    const trip = async (startOffset: number, endOffset: number) =>
+      createRawTrip(program, await createFundedUser(provider), {
+        startDate: start + startOffset,
+        endDate: start + endOffset,
+      });
+
+    // Shortest trips create_trip allows: 1 second, plus a 2-second trip
+    const oneSecond = await trip(0, 1);
+    const sameSecond = await trip(0, 1);
+    const twoSeconds = await trip(0, 2);
+    const nextSecond = await trip(1, 2);
+
+    const estimate = async (tripA: PublicKey, tripB: PublicKey) => {
+      const simulation = await program.methods
+        .estimateCompatibility()
+        .accountsPartial({ tripA, tripB })
+        .simulate();
+      return simulation.events.find((e) => e.name === "compatibilityEstimate").data.dateOverlap;
+    };
+
+    // 1s overlap over a 1.5s average = 66.7%, not 1s over a floored 1s = 100%
+    expect(await estimate(oneSecond, twoSeconds)).to.equal(66);
+    // Identical 1-second ranges score the full 100
+    expect(await estimate(oneSecond, sameSecond)).to.equal(100);
+    // Touching ranges share only an instant - a zero-length overlap scores 0
+    expect(await estimate(oneSecond, nextSecond)).to.equal(0);
+    console.log("✅ Short-trip overlap uses the exact average and defined zero-length cases");
+  });
+
+  it("Measures start-week distance around the year boundary", async () => {
+    const userA = await createFundedUser(provider);
+    const userB = await createFundedUser(provider);
+    const userC = await createFundedUser(provider);
+    const utc = (year: number, month: number, day: number) => Date.UTC(year, month - 1, day) / 1000;
+
+    // Week 52 of 2030, week 1 of 2031, and week 26 of 2030
+    const lateDecember = utc(2030, 12, 28);
+    const earlyJanuary = utc(2031, 1, 2);
+    const midYear = utc(2030, 6, 25);
+    const tripA = await createRawTrip(program, userA, { startDate: lateDecember, endDate: lateDecember + 7 * DAY });
+    const tripB = await createRawTrip(program, userB, { startDate: earlyJanuary, endDate: earlyJanuary + 7 * DAY });
+    const tripC = await createRawTrip(program, userC, { startDate: midYear, endDate: midYear + 7 * DAY });
+
+    const weekDistance = async (tripB: PublicKey) => {
+      const simulation = await program.methods
+        .estimateCompatibility()
+        .accountsPartial({ tripA, tripB })
+        .simulate();
+      return simulation.events.find((e) => e.name === "compatibilityEstimate").data.weekDistance;
+    };
+
+    // Wrapping comparison: adjacent across New Year, not 51 weeks apart
+    expect(await weekDistance(tripB)).to.equal(1);
+    // Half a year is the farthest two weeks can be
+    expect(await weekDistance(tripC)).to.equal(26);
+    console.log("✅ Week 52 and week 1 are one week apart");
+  });
+
+  it("Publishes distinguishable route densities and rejects implausible inputs", async () => {
+    const user = await createFundedUser(provider);
+    const now = Math.floor(Date.now() / 1000);
+    const urbanTrip = await createRawTrip(program, user, { startDate: now + 5 * DAY });
+    const sparseTrip = await createRawTrip(program, user, { startDate: now + 50 * DAY });
+
+    const setDensity = async (trip: PublicKey, route: { lat: number; lng: number }[]) => {
+      const { waypointCount, areaBucket } = computeRouteDensityInputs(
+        route.map((w) => latLngToH3Cell(w.lat, w.lng))
+      );
+      await program.methods
+        .setRouteDensity(waypointCount, areaBucket)
+        .accountsPartial({ trip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      return (await program.account.trip.fetch(trip)).routeDensity;
+    };
+
+    const urbanDensity = await setDensity(urbanTrip, denseUrbanRoute());
+    const sparseDensity = await setDensity(sparseTrip, sparseCrossCountryRoute());
+    expect(urbanDensity).to.be.greaterThan(sparseDensity);
+    console.log(`✅ On-chain densities: urban ${urbanDensity}, cross-country ${sparseDensity}`);
+
+    // 20 distinct cells can't fit in a single-cell bounding box
+    try {
+      await program.methods
+        .setRouteDensity(20, 0)
+        .accountsPartial({ trip: urbanTrip, user: user.publicKey })
+        .signers([user])
+        .rpc({ commitment: "confirmed" });
+      throw new Error("Should have failed: implausible density inputs");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("InvalidRouteDensity");
+    }
+    console.log("✅ Implausible density inputs rejected");
+  });
+
+  it("Rejects routes whose bounding box exceeds the configured maximum area", async () => {
+    const config = await getOrInitProgramConfig(program, provider);
+    const defaultMax = (await program.account.programConfig.fetch(config)).maxRouteAreaBucket;
+    const setMaxRouteArea = (bucket: number) =>
+      program.methods
+        .setMaxRouteArea(bucket)
+        .accountsPartial({ config, admin: provider.wallet.publicKey })
+        .rpc();
+    const areaBucket = (route: { lat: number; lng: number }[]) =>
+      computeRouteDensityInputs(route.map((w) => latLngToH3Cell(w.lat, w.lng))).areaBucket;
+
+    const user = await createFundedUser(provider);
+    const start = Math.floor(Date.now() / 1000) + 600 * DAY;
+    const cityBucket = areaBucket(denseUrbanRoute());
+    const continentBucket = areaBucket(sparseCrossCountryRoute());
+
+    try {
+      // ~340,000 km²: a region, not a continent
+      await setMaxRouteArea(16);
+      expect(cityBucket).to.be.at.most(16);
+      expect(continentBucket).to.be.greaterThan(16);
+
+      const cityTrip = await createRawTrip(program, user, { startDate: start, routeAreaBucket: cityBucket });
+      expect((await program.account.trip.fetch(cityTrip)).isActive).to.be.true;
+
+      try {
+        await createRawTrip(program, user, { startDate: start + DAY, routeAreaBucket: continentBucket });
+        expect.fail("a San Francisco to New York route should be rejected");
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal("RouteTooLarge");
+      }
+
+      // Lifting the cap admits it
+      await setMaxRouteArea(0);
+      await createRawTrip(program, user, { startDate: start + 2 * DAY, routeAreaBucket: continentBucket });
+      console.log(`✅ City route (bucket ${cityBucket}) accepted, cross-country (bucket ${continentBucket}) rejected`);
+    } finally {
+      await setMaxRouteArea(defaultMax);
+    }
+  });
+
+  it("Sweeps trips of an inactive user but leaves active users alone", async () => {
+    const configPDA = await getOrInitProgramConfig(program, provider);
+    const defaultPeriod = (await program.account.programConfig.fetch(configPDA))
+      .inactivityPeriodSecs;
+
+    const user = await createFundedUser(provider);
+    const userProfile = await createRawUserProfile(program, user);
+    const trip = await createRawTrip(program, user);
+
+    // Freshly created profile is within the default period - sweep is a no-op
+    await program.methods
+      .sweepInactiveUser()
+      .accountsPartial({ config: configPDA, userProfile, cranker: provider.wallet.publicKey })
+      .remainingAccounts([{ pubkey: trip, isSigner: false, isWritable: true }])
+      .rpc({ commitment: "confirmed" });
+
+    expect((await program.account.trip.fetch(trip)).isActive).to.be.true;
+    console.log("✅ Active user is not swept");
+
+    // Shrink the period so the user counts as inactive after a short wait
+    await program.methods
+      .setInactivityPeriod(new anchor.BN(1))
+      .accountsPartial({ config: configPDA, admin: provider.wallet.publicKey })
+      .rpc({ commitment: "confirmed" });
+
+    try {
+      await new Promise((resolve) => setTimeout(resolve, 3000));
+
+      await program.methods
+        .sweepInactiveUser()
+        .accountsPartial({ config: configPDA, userProfile, cranker: provider.wallet.publicKey })
+        .remainingAccounts([{ pubkey: trip, isSigner: false, isWritable: true }])
+        .rpc({ commitment: "confirmed" });
+
+      expect((await program.account.trip.fetch(trip)).isActive).to.be.false;
+      console.log("✅ Inactive user's trip deactivated by sweep");
+    } finally {
+      await program.methods
+        .setInactivityPeriod(defaultPeriod)
+        .accountsPartial({ config: configPDA, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+    }
+  });
+
+  it("Rejects a sweep that passes another user's trip", async () => {
+    const configPDA = await getOrInitProgramConfig(program, provider);
+    const user = await createFundedUser(provider);
+    const other = await createFundedUser(provider);
+    const userProfile = await createRawUserProfile(program, user);
+    const otherTrip = await createRawTrip(program, other);
+
+    await program.methods
+      .setInactivityPeriod(new anchor.BN(0))
+      .accountsPartial({ config: configPDA, admin: provider.wallet.publicKey })
+      .rpc({ commitment: "confirmed" });
+
+    try {
+      await new Promise((resolve) => setTimeout(resolve, 2000));
+
+      await program.methods
+        .sweepInactiveUser()
+        .accountsPartial({ config: configPDA, userProfile, cranker: provider.wallet.publicKey })
+        .remainingAccounts([{ pubkey: otherTrip, isSigner: false, isWritable: true }])
+        .rpc({ commitment: "confirmed" });
+      expect.fail("sweep should reject a trip not owned by the swept user");
+    } catch (error: any) {
+      expect(error.toString()).to.include("Unauthorized");
+    } finally {
+      const period = new anchor.BN(90 * DAY);
+      await program.methods
+        .setInactivityPeriod(period)
+        .accountsPartial({ config: configPDA, admin: provider.wallet.publicKey })
+        .rpc({ commitment: "confirmed" });
+    }
+    console.log("✅ Sweep refuses trips owned by someone else");
+  });
+
+  it("Round-trips waypoints and interests through the combined payload framing", async () => {
+    const split = async (payload: Buffer) => {
+      const simulation = await program.methods
+        .splitEncryptedPayload(payload)
+        .accountsPartial({ payer: provider.wallet.publicKey })
+        .simulate();
+      return simulation.events.find((e) => e.name === "encryptedPayloadSplit").data;
+    };
+
+    // [waypoint_field_count][waypoint fields][interest fields]
+    const waypoints = randomBytes(3 * 32);
+    const interests = randomBytes(2 * 32);
+    const framed = await split(Buffer.concat([Buffer.from([3]), waypoints, interests]));
+    expect(framed.waypointFields).to.equal(3);
+    expect(framed.interestFields).to.equal(2);
+    expect(Buffer.from(framed.waypoints).equals(waypoints)).to.be.true;
+    expect(Buffer.from(framed.interests).equals(interests)).to.be.true;
+
+    // Either half may be empty
+    const routeOnly = await split(Buffer.concat([Buffer.from([3]), waypoints]));
+    expect(Buffer.from(routeOnly.waypoints).equals(waypoints)).to.be.true;
+    expect(routeOnly.interestFields).to.equal(0);
+    const interestsOnly = await split(Buffer.concat([Buffer.from([0]), interests]));
+    expect(interestsOnly.waypointFields).to.equal(0);
+    expect(Buffer.from(interestsOnly.interests).equals(interests)).to.be.true;
+    console.log("✅ Payload framing round-trips");
+  });
+
+  it("Rejects payloads and trip ciphertexts that are not whole encrypted fields", async () => {
+    const malformed = [
+      Buffer.alloc(0),                                        // no header
+      Buffer.concat([Buffer.from([4]), randomBytes(3 * 32)]), // header overruns the body
+      Buffer.concat([Buffer.from([1]), randomBytes(32 + 5)]), // trailing partial field
+    ];
+    for (const payload of malformed) {
+      try {
+        await program.methods
+          .splitEncryptedPayload(payload)
+          .accountsPartial({ payer: provider.wallet.publicKey })
+          .simulate();
+        expect.fail(`payload of ${payload.length} bytes should be rejected`);
+      } catch (error: any) {
+        expect(error.toString()).to.include("MalformedEncryptedPayload");
+      }
+    }
+
+    const user = await createFundedUser(provider);
+    try {
+      await createRawTrip(program, user, { encryptedWaypoints: randomBytes(22 * 32 - 1) });
+      expect.fail("misaligned trip ciphertext should be rejected");
+    } catch (error: any) {
+      expect(error.toString()).to.include("MalformedEncryptedPayload");
+    }
+    console.log("✅ Malformed framing rejected");
+  });
+
+  it("Requires trip ciphertext of at least one whole encrypted field", async () => {
+    const user = await createFundedUser(provider);
+    const start = Math.floor(Date.now() / 1000) + 70 * DAY;
+    const cases: [number, string | null][] = [
+      [0, "InvalidEncryptedData"],
+      [31, "MalformedEncryptedPayload"],
+      [64, null],
+    ];
+    for (const [i, [length, expectedError]] of cases.entries()) {
+      const create = createRawTrip(program, user, {
+        startDate: start + i * DAY,
+        encryptedWaypoints: randomBytes(length),
+      });
+      if (expectedError === null) {
+        const trip = await program.account.trip.fetch(await create);
+        expect(trip.encryptedWaypoints.length).to.equal(length);
+        continue;
+      }
+      try {
+        await create;
+        expect.fail(`${length}-byte ciphertext should be rejected`);
+      } catch (error: any) {
+        expect(error.error?.errorCode?.code).to.equal(expectedError);
+      }
+    }
+    console.log("✅ 0 and 31 bytes rejected, 64 bytes accepted");
+  });
+
+  it("Rejects a waypoint payload reused across an owner's trips once they opt in", async () => {
+    const user = await createFundedUser(provider);
+    const payloadRegistry = derivePayloadRegistryPDA(program.programId, user.publicKey);
+    const start = Math.floor(Date.now() / 1000) + 60 * DAY;
+    const payload = randomBytes(22 * 32);
+
+    // Without a registry the check is skipped
+    await createRawTrip(program, user, { startDate: start, encryptedWaypoints: payload });
+
+    await program.methods
+      .initPayloadRegistry()
+      .accountsPartial({ payloadRegistry, user: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    // A unique payload is accepted and registered
+    await createRawTrip(program, user, { startDate: start + DAY, encryptedWaypoints: payload, payloadRegistry });
+    const registry = await program.account.payloadRegistry.fetch(payloadRegistry);
+    const checksum = createHash("sha256").update(payload).digest();
+    expect(registry.checksums.map((c) => Buffer.from(c).toString("hex"))).to.deep.equal([checksum.toString("hex")]);
+
+    // The same ciphertext on another trip is refused
+    try {
+      await createRawTrip(program, user, { startDate: start + 2 * DAY, encryptedWaypoints: payload, payloadRegistry });
+      expect.fail("a reused payload should be rejected");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("DuplicatePayload");
+    }
+    await createRawTrip(program, user, {
+      startDate: start + 2 * DAY,
+      encryptedWaypoints: randomBytes(22 * 32),
+      payloadRegistry,
+    });
+    console.log("✅ Unique payload accepted, reused payload rejected");
+  });
+
+  it("Accepts trip ciphertexts up to the account's maximum length", async () => {
+    const user = await createFundedUser(provider);
+    const startDate = Math.floor(Date.now() / 1000) + 30 * DAY;
+
+    // 800 bytes = 25 whole fields, exactly the #[max_len] on encrypted_waypoints
+    const tripPDA = await createRawTrip(program, user, {
+      startDate,
+      encryptedWaypoints: randomBytes(800),
+    });
+    const trip = await program.account.trip.fetch(tripPDA);
+    expect(trip.encryptedWaypoints.length).to.equal(800);
+
+    try {
+      await createRawTrip(program, user, {
+        startDate: startDate + DAY,
+        encryptedWaypoints: randomBytes(801),
+      });
+      expect.fail("an 801-byte trip ciphertext should be rejected");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("EncryptedDataTooLarge");
+    }
+    console.log("✅ 800 bytes accepted, 801 rejected");
+  });
+
+  it("Indexes an owner's trips so two can share a start date", async () => {
+    const user = await createFundedUser(provider);
+    const startDate = Math.floor(Date.now() / 1000) + 50 * DAY;
+
+    const first = await createRawTrip(program, user, { startDate });
+    const second = await createRawTrip(program, user, { startDate });
+    expect(first.toBase58()).to.equal(deriveTripPDA(program.programId, user.publicKey, 0).toBase58());
+    expect(second.toBase58()).to.equal(deriveTripPDA(program.programId, user.publicKey, 1).toBase58());
+
+    for (const trip of [first, second]) {
+      expect((await program.account.trip.fetch(trip)).startDate.toNumber()).to.equal(startDate);
+    }
+    const profile = await program.account.userProfile.fetch(await createRawUserProfile(program, user));
+    expect(profile.nextTripIndex).to.equal(2);
+    expect(profile.tripCount).to.equal(2);
+    console.log("✅ Same-date trips coexist at indices 0 and 1");
+  });
+
+  describe("Batch import", () => {
+    // A fresh user's batch takes trip indices 0, 1, ...
+    const tripPda = (owner: PublicKey, index: number) => deriveTripPDA(program.programId, owner, index);
+
+    // Small ciphertexts keep several entries inside one transaction
+    const destinationGridHash = Buffer.alloc(32, 3);
+
+    const entry = (startDate: number, overrides: Partial<{ endDate: number; encryptedWaypoints: Buffer }> = {}) => ({
+      startDate: new anchor.BN(startDate),
+      endDate: new anchor.BN(overrides.endDate ?? startDate + 7 * DAY),
+      encryptedWaypoints: overrides.encryptedWaypoints ?? randomBytes(2 * 32),
+    });
+
+    const importBatch = async (user: anchor.web3.Keypair, entries: ReturnType<typeof entry>[], accounts: PublicKey[]) => {
+      const { shardId, destinationShard, previousShard } = await findOpenDestinationShard(
+        program,
+        destinationGridHash
+      );
+      const builder = program.methods
+        .createTripsBatch(entries, Array.from(randomBytes(32)), Array.from(destinationGridHash), shardId)
+        .accountsPartial({ user: user.publicKey, destinationShard, previousShard })
+        .remainingAccounts(accounts.map((pubkey) => ({ pubkey, isWritable: true, isSigner: false })))
+        .signers([user]);
+      const simulation = await builder.simulate();
+      await builder.rpc({ commitment: "confirmed" });
+      const { trips: indexed } = await program.account.destinationIndexShard.fetch(destinationShard);
+      return {
+        ...simulation.events.find((e) => e.name === "tripsBatchImported").data,
+        indexed: indexed.map((t) => t.toBase58()),
+      };
+    };
+
+    it("Imports a batch of valid trips in one transaction", async () => {
+      const user = await createFundedUser(provider);
+      const start = Math.floor(Date.now() / 1000) + 90 * DAY;
+      const starts = [start, start + 30 * DAY];
+      await createRawUserProfile(program, user);
+      const trips = starts.map((_, i) => tripPda(user.publicKey, i));
+
+      const result = await importBatch(user, starts.map((s) => entry(s)), trips);
+      expect(result.created).to.equal(2);
+      expect(result.skipped).to.equal(0);
+
+      for (const [i, trip] of trips.entries()) {
+        const account = await program.account.trip.fetch(trip);
+        expect(account.owner.toBase58()).to.equal(user.publicKey.toBase58());
+        expect(account.startDate.toNumber()).to.equal(starts[i]);
+        expect(account.isActive).to.be.true;
+        expect(result.indexed).to.include(trip.toBase58());
+      }
+      console.log("✅ Two trips imported in one batch");
+    });
+
+    it("Skips malformed entries and keeps the valid ones", async () => {
+      const user = await createFundedUser(provider);
+      const start = Math.floor(Date.now() / 1000) + 200 * DAY;
+      const starts = [start, start + 30 * DAY, start + 60 * DAY, start + 90 * DAY];
+      const entries = [
+        entry(starts[0]),
+        entry(starts[1], { endDate: starts[1] - DAY }),                // ends before it starts
+        entry(starts[2], { encryptedWaypoints: randomBytes(32 + 5) }), // not whole fields
+        entry(starts[3]),
+      ];
+      await createRawUserProfile(program, user);
+      const accounts = starts.map((_, i) => tripPda(user.publicKey, i));
+      // Entry 3's account doesn't match its PDA
+      accounts[3] = tripPda(user.publicKey, 4);
+
+      const result = await importBatch(user, entries, accounts);
+      expect(result.created).to.equal(1);
+      expect(result.skipped).to.equal(3);
+
+      expect((await program.account.trip.fetch(accounts[0])).isActive).to.be.true;
+      expect(result.indexed).to.include(accounts[0].toBase58());
+      for (const skipped of accounts.slice(1)) {
+        expect(await program.account.trip.fetchNullable(skipped)).to.be.null;
+        expect(result.indexed).to.not.include(skipped.toBase58());
+      }
+
+      // Skipped entries still used up their indices
+      const profile = await program.account.userProfile.fetch(await createRawUserProfile(program, user));
+      expect(profile.nextTripIndex).to.equal(4);
+      expect(profile.tripCount).to.equal(1);
+      console.log("✅ Malformed entries skipped, valid entry imported");
+    });
+  });
+
+  it("Spills a full destination shard into the next and reads it by page", async () => {
+    const user = await createFundedUser(provider);
+    const destinationGridHash = randomBytes(32);
+    const start = Math.floor(Date.now() / 1000) + 400 * DAY;
+    const shardPDA = (shardId: number) =>
+      deriveDestinationShardPDA(program.programId, destinationGridHash, shardId);
+
+    // Fill shard 0, then one more trip opens shard 1
+    const trips: PublicKey[] = [];
+    for (let i = 0; i <= DESTINATION_SHARD_CAPACITY; i++) {
+      trips.push(await createRawTrip(program, user, { startDate: start + i * DAY, destinationGridHash }));
+    }
+    const shard0 = await program.account.destinationIndexShard.fetch(shardPDA(0));
+    const shard1 = await program.account.destinationIndexShard.fetch(shardPDA(1));
+    expect(shard0.trips.length).to.equal(DESTINATION_SHARD_CAPACITY);
+    expect(shard1.shardId).to.equal(1);
+    expect(shard1.trips.map((t) => t.toBase58())).to.deep.equal([trips[DESTINATION_SHARD_CAPACITY].toBase58()]);
+
+    const userProfile = await createRawUserProfile(program, user);
+    const createIn = async (shardId: number, previousShard: PublicKey | null) => {
+      const startDate = start + 100 * DAY + shardId;
+      const { nextTripIndex } = await program.account.userProfile.fetch(userProfile);
+      return program.methods
+        .createTrip(
+          Array.from(destinationGridHash),
+          new anchor.BN(startDate),
+          new anchor.BN(startDate + 7 * DAY),
+          randomBytes(22 * 32),
+          Array.from(randomBytes(32)),
+          shardId,
+          0
+        )
+        .accountsPartial({
+          user: user.publicKey,
+          trip: deriveTripPDA(program.programId, user.publicKey, nextTripIndex),
+          userProfile,
+          destinationShard: shardPDA(shardId),
+          previousShard,
+          payloadRegistry: null,
+        })
+        .signers([user])
+        .rpc();
+    };
+
+    try {
+      await createIn(0, null);
+      expect.fail("a full shard should take no more trips");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("DestinationShardFull");
+    }
+    try {
+      await createIn(2, shardPDA(1));
+      expect.fail("shard 2 should stay closed while shard 1 has room");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("DestinationShardOutOfOrder");
+    }
+
+    const readPage = async (shardId: number, offset: number, limit: number) => {
+      const simulation = await program.methods
+        .readDestinationShard(Array.from(destinationGridHash), shardId, offset, limit)
+        .accountsPartial({ destinationShard: shardPDA(shardId) })
+        .simulate();
+      return simulation.events.find((e) => e.name === "destinationShardPage").data;
+    };
+
+    const page = await readPage(0, 16, 16);
+    expect(page.trips.map((t: PublicKey) => t.toBase58())).to.deep.equal(
+      trips.slice(16, 32).map((t) => t.toBase58())
+    );
+    expect(page.shardLen).to.equal(DESTINATION_SHARD_CAPACITY);
+    expect(page.shardFull).to.be.true;
+
+    const last = await readPage(1, 0, 16);
+    expect(last.trips.length).to.equal(1);
+    expect(last.shardFull).to.be.false;
+    console.log(`✅ ${trips.length} trips indexed across 2 shards, page of ${page.trips.length} read`);
+  });
+
+  it("Describes a trip's public fields in one event", async () => {
+    const user = await createFundedUser(provider);
+    const trip = await createRawTrip(program, user, { destinationGridHash: randomBytes(32) });
+    await program.methods
+      .setTripFlexibility(40)
+      .accountsPartial({ trip, user: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+    await program.methods
+      .setTripNotifications(0b010)
+      .accountsPartial({ trip, user: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    const simulation = await program.methods.describeTrip().accountsPartial({ trip }).simulate();
+    const described = simulation.events.find((e) => e.name === "tripDescribed").data;
+    const account = await program.account.trip.fetch(trip);
+
+    expect(described.trip.toBase58()).to.equal(trip.toBase58());
+    expect(described.owner.toBase58()).to.equal(account.owner.toBase58());
+    expect(described.destinationGridHash).to.deep.equal(account.destinationGridHash);
+    for (const field of ["startDate", "endDate", "boostUntil", "createdAt", "updatedAt"]) {
+      expect(described[field].toNumber(), field).to.equal(account[field].toNumber());
+    }
+    for (const field of [
+      "saltEpoch",
+      "isActive",
+      "matchCount",
+      "minCounterpartyTier",
+      "travelerType",
+      "seekingType",
+      "routeDensity",
+      "flexibility",
+      "notifyOn",
+    ]) {
+      expect(described[field], field).to.equal(account[field]);
+    }
+    expect(described.flexibility).to.equal(40);
+    expect(described.notifyOn).to.equal(0b010);
+    expect(described.boosted).to.be.false;
+    // Ciphertext stays out of the event
+    expect(described).to.not.have.property("encryptedWaypoints");
+    console.log("✅ TripDescribed mirrors the account's public fields");
+  });
+
+  it("Draws a match suggestion only from the committed seed", async () => {
+    const user = await createFundedUser(provider);
+    const destinationGridHash = randomBytes(32);
+    const start = Math.floor(Date.now() / 1000) + 500 * DAY;
+    const trip = await createRawTrip(program, user, { startDate: start, destinationGridHash });
+    const others: PublicKey[] = [];
+    for (let i = 0; i < 3; i++) {
+      const other = await createFundedUser(provider);
+      others.push(await createRawTrip(program, other, { startDate: start, destinationGridHash }));
+    }
+
+    const seed = randomBytes(32);
+    const commitment = createHash("sha256").update(seed).digest();
+    await program.methods
+      .commitSuggestionSeed(Array.from(commitment), 0)
+      .accountsPartial({ trip, owner: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    // Every trip in the shard is passed, in shard order, so boosts can weigh in
+    const destinationShard = deriveDestinationShardPDA(program.programId, destinationGridHash, 0);
+    const { trips: shardTrips } = await program.account.destinationIndexShard.fetch(destinationShard);
+    const reveal = (revealed: Buffer, candidates: PublicKey[] = shardTrips) =>
+      program.methods
+        .revealAndSuggest(Array.from(revealed))
+        .accountsPartial({ trip, owner: user.publicKey, destinationShard })
+        .remainingAccounts(candidates.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })))
+        .signers([user]);
+
+    try {
+      await reveal(randomBytes(32)).rpc();
+      expect.fail("a different seed should not match the commitment");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("SeedCommitmentMismatch");
+    }
+    try {
+      await reveal(seed, [...shardTrips].reverse()).rpc();
+      expect.fail("candidates out of shard order should be refused");
+    } catch (error: any) {
+      expect(error.error?.errorCode?.code).to.equal("SuggestionCandidatesMismatch");
+    }
+
+    // The committed seed draws the same trip every time, never the requester's own
+    const suggestion = async () =>
+      (await reveal(seed).simulate()).events.find((e) => e.name === "matchSuggested").data;
+    const first = await suggestion();
+    const second = await suggestion();
+    expect(first.suggestedTrip.toBase58()).to.equal(second.suggestedTrip.toBase58());
+    expect(Buffer.from(first.randomness).equals(Buffer.from(second.randomness))).to.be.true;
+    expect(others.map((t) => t.toBase58())).to.include(first.suggestedTrip.toBase58());
+    expect(first.boosted).to.be.false;
+
+    // Revealing spends the commitment
+    await reveal(seed).rpc({ commitment: "confirmed" });
+    const [suggestionSeed] = PublicKey.findProgramAddressSync(
+      [Buffer.from("suggestion_seed"), trip.toBuffer()],
+      program.programId
+    );
+    expect(await program.account.suggestionSeed.fetchNullable(suggestionSeed)).to.be.null;
+    console.log(`✅ Seed reveal suggested ${first.suggestedTrip.toBase58()}`);
+  });
+
+  it("Flags a suggestion drawn from a boosted trip", async () => {
+    const user = await createFundedUser(provider);
+    const other = await createFundedUser(provider);
+    const destinationGridHash = randomBytes(32);
+    const start = Math.floor(Date.now() / 1000) + 510 * DAY;
+    const trip = await createRawTrip(program, user, { startDate: start, destinationGridHash });
+    const boostedTrip = await createRawTrip(program, other, { startDate: start, destinationGridHash });
+    const [treasury] = PublicKey.findProgramAddressSync([Buffer.from("treasury")], program.programId);
+    await program.methods
+      .boostTrip(1)
+      .accountsPartial({ trip: boostedTrip, treasury, user: other.publicKey })
+      .signers([other])
+      .rpc({ commitment: "confirmed" });
+
+    const seed = randomBytes(32);
+    await program.methods
+      .commitSuggestionSeed(Array.from(createHash("sha256").update(seed).digest()), 0)
+      .accountsPartial({ trip, owner: user.publicKey })
+      .signers([user])
+      .rpc({ commitment: "confirmed" });
+
+    // Reveal only lands after the commit slot
+    await new Promise((resolve) => setTimeout(resolve, 1000));
+
+    // The boosted trip is the only other candidate, so it must be drawn
+    const destinationShard = deriveDestinationShardPDA(program.programId, destinationGridHash, 0);
+    const { trips } = await program.account.destinationIndexShard.fetch(destinationShard);
+    const { events } = await program.methods
+      .revealAndSuggest(Array.from(seed))
+      .accountsPartial({ trip, owner: user.publicKey, destinationShard })
+      .remainingAccounts(trips.map((pubkey) => ({ pubkey, isWritable: false, isSigner: false })))
+      .signers([user])
+      .simulate();
+    const suggested = events.find((e) => e.name === "matchSuggested").data;
+    expect(suggested.suggestedTrip.toBase58()).to.equal(boostedTrip.toBase58());
+    expect(suggested.boosted).to.be.true;
+    console.log("✅ Boosted trip drawn and flagged");
+  });
+});
@@ -1,8 +1,19 @@
-import { Program, AnchorProvider } from "@coral-xyz/anchor";
-import { PublicKey, Keypair } from "@solana/web3.js";
+import { Program, AnchorProvider, BN } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { randomBytes } from "crypto";
 import { Triper } from "../target/types/triper";
-import { getMXEPublicKey } from "@arcium-hq/client";
+import {
+  getMXEPublicKey,
+  getArciumAccountBaseSeed,
+  getArciumProgAddress,
+  getCompDefAccOffset,
+  getMXEAccAddress,
+  uploadCircuit,
+  buildFinalizeCompDefTx,
+} from "@arcium-hq/client";
+import * as fs from "fs";
 import type { Waypoint, InterestTag } from "../../../apps/web/src/types";
+import { deriveTripPDA, findOpenDestinationShard } from "../../../apps/web/src/lib/solana/create-trip";
 
 /**
  * Test helper to get MXE public key with retry logic
@@ -26,6 +37,113 @@ export async function getMXEPublicKeyWithRetry(
   throw new Error("Failed to get MXE public key after retries");
 }
 
+/**
+ * Read a keypair from a Solana CLI JSON file
+ */
+export function readKpJson(path: string): Keypair {
+  const file = fs.readFileSync(path);
+  return Keypair.fromSecretKey(
+    new Uint8Array(JSON.parse(file.toString()))
+  );
+}
+
+/**
+ * Test helper to initialize, upload and finalize a computation definition
+ * Safe to call repeatedly - an existing comp def is only re-uploaded and finalized
+ *
+ * @param circuitName - Encrypted instruction name (e.g. "compute_trip_match")
+ * @param initMethod - Builder for the program's init_<circuit>_comp_def instruction
+ */
+export async function initCompDef(
+  program: Program<Triper>,
+  provider: AnchorProvider,
+  owner: Keypair,
+  circuitName: string,
+  initMethod: () => any
+): Promise<void> {
+  const baseSeedCompDefAcc = getArciumAccountBaseSeed(
+    "ComputationDefinitionAccount"
+  );
+  const offset = getCompDefAccOffset(circuitName);
+  const compDefPDA = PublicKey.findProgramAddressSync(
+    [baseSeedCompDefAcc, program.programId.toBuffer(), offset],
+    getArciumProgAddress()
+  )[0];
+
+  console.log("Comp def PDA:", compDefPDA.toBase58());
+
+  const compDefAccount = await provider.connection.getAccountInfo(compDefPDA);
+
+  if (compDefAccount) {
+    console.log("⚠️  Computation definition already exists");
+  } else {
+    const sig = await initMethod()
+      .accounts({
+        compDefAccount: compDefPDA,
+        payer: owner.publicKey,
+        mxeAccount: getMXEAccAddress(program.programId),
+      })
+      .signers([owner])
+      .rpc({
+        commitment: "confirmed",
+      });
+    console.log("Init computation definition transaction:", sig);
+  }
+
+  // Try different possible circuit file names
+  let rawCircuit: Buffer | undefined;
+  const possiblePaths = [
+    `build/${circuitName}_localnet.arcis`,
+    `build/${circuitName}_testnet.arcis`,
+    `build/${circuitName}.arcis`,
+  ];
+
+  for (const path of possiblePaths) {
+    if (fs.existsSync(path)) {
+      console.log(`Found circuit at ${path}`);
+      rawCircuit = fs.readFileSync(path);
+      break;
+    }
+  }
+
+  if (!rawCircuit) {
+    throw new Error("Circuit file not found. Please build with 'arcium build' first.");
+  }
+
+  try {
+    await uploadCircuit(
+      provider,
+      circuitName,
+      program.programId,
+      rawCircuit,
+      true
+    );
+    console.log("✅ Circuit uploaded successfully!");
+  } catch (e: any) {
+    console.log("⚠️  Circuit already uploaded (this is okay):", e.message?.split('\n')[0]);
+  }
+
+  console.log("Finalizing computation definition...");
+  try {
+    const finalizeTx = await buildFinalizeCompDefTx(
+      provider,
+      Buffer.from(offset).readUInt32LE(),
+      program.programId
+    );
+
+    const latestBlockhash = await provider.connection.getLatestBlockhash();
+    finalizeTx.recentBlockhash = latestBlockhash.blockhash;
+    finalizeTx.lastValidBlockHeight = latestBlockhash.lastValidBlockHeight;
+
+    finalizeTx.sign(owner);
+
+    await provider.sendAndConfirm!(finalizeTx);
+    console.log("✅ Computation definition finalized!");
+  } catch (finalizeError: any) {
+    console.log("⚠️  Finalization info:", finalizeError.message?.split('\n')[0]);
+  }
+}
+
 /**
  * Helper to create sample trip data for SF to LA route
  * Returns Waypoints and InterestTags
@@ -124,3 +242,277 @@ export function createSampleUserData(variant: 'userA' | 'userB' = 'userA'): {
     };
   }
 }
+
+/**
+ * Test helper to create and fund a fresh wallet
+ */
+export async function createFundedUser(
+  provider: AnchorProvider,
+  sol: number = 2
+): Promise<Keypair> {
+  const user = Keypair.generate();
+  const sig = await provider.connection.requestAirdrop(
+    user.publicKey,
+    sol * LAMPORTS_PER_SOL
+  );
+  await provider.connection.confirmTransaction(sig);
+  return user;
+}
+
+/**
+ * Helper to create a trip with placeholder ciphertext
+ * Exercises account logic only - no MPC computation can run on it
+ */
+export async function createRawTrip(
+  program: Program<Triper>,
+  owner: Keypair,
+  options: {
+    startDate?: number;
+    endDate?: number;
+    destinationGridHash?: Buffer;
+    encryptedWaypoints?: Buffer;
+    publicKey?: Buffer;
+    routeAreaBucket?: number;
+    payloadRegistry?: PublicKey;
+  } = {}
+): Promise<PublicKey> {
+  const startDate = options.startDate ?? Math.floor(Date.now() / 1000) + 24 * 60 * 60;
+  const endDate = options.endDate ?? startDate + 7 * 24 * 60 * 60;
+  const destinationGridHash = options.destinationGridHash ?? Buffer.alloc(32, 1);
+  const encryptedWaypoints = options.encryptedWaypoints ?? randomBytes(22 * 32);
+  const publicKey = options.publicKey ?? randomBytes(32);
+
+  // Trips are indexed off the owner's profile
+  const userProfile = await createRawUserProfile(program, owner);
+  const { nextTripIndex } = await program.account.userProfile.fetch(userProfile);
+  const tripPDA = deriveTripPDA(program.programId, owner.publicKey, nextTripIndex);
+
+  const { shardId, destinationShard, previousShard } = await findOpenDestinationShard(
+    program,
+    destinationGridHash
+  );
+  await program.methods
+    .createTrip(
+      Array.from(destinationGridHash),
+      new BN(startDate),
+      new BN(endDate),
+      encryptedWaypoints,
+      Array.from(publicKey),
+      shardId,
+      options.routeAreaBucket ?? 0
+    )
+    .accountsPartial({
+      user: owner.publicKey,
+      trip: tripPDA,
+      userProfile,
+      destinationShard,
+      previousShard,
+      payloadRegistry: options.payloadRegistry ?? null,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([owner])
+    .rpc({ commitment: "confirmed" });
+
+  return tripPDA;
+}
+
+/**
+ * Trip pair in byte order, mirroring the program's canonical_pair
+ */
+export function canonicalPair(tripA: PublicKey, tripB: PublicKey): [PublicKey, PublicKey] {
+  return Buffer.compare(tripA.toBuffer(), tripB.toBuffer()) <= 0 ? [tripA, tripB] : [tripB, tripA];
+}
+
+/**
+ * Helper to derive the match record PDA for a trip pair (either order)
+ */
+export function deriveMatchPDA(
+  programId: PublicKey,
+  tripA: PublicKey,
+  tripB: PublicKey
+): PublicKey {
+  const [first, second] = canonicalPair(tripA, tripB);
+  const [matchPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("match"), first.toBuffer(), second.toBuffer()],
+    programId
+  );
+  return matchPDA;
+}
+
+/**
+ * Helper to derive the pair history PDA for a trip pair (either order)
+ */
+export function derivePairHistoryPDA(
+  programId: PublicKey,
+  tripA: PublicKey,
+  tripB: PublicKey
+): PublicKey {
+  const [first, second] = canonicalPair(tripA, tripB);
+  const [pairHistoryPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("pair_history"), first.toBuffer(), second.toBuffer()],
+    programId
+  );
+  return pairHistoryPDA;
+}
+
+/**
+ * Helper to derive the owner pair history PDA for two wallets (either order)
+ */
+export function deriveOwnerPairHistoryPDA(
+  programId: PublicKey,
+  ownerA: PublicKey,
+  ownerB: PublicKey
+): PublicKey {
+  const [first, second] = canonicalPair(ownerA, ownerB);
+  const [ownerPairHistoryPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("owner_pair_history"), first.toBuffer(), second.toBuffer()],
+    programId
+  );
+  return ownerPairHistoryPDA;
+}
+
+/**
+ * Scoring profile argument as Anchor encodes enum variants
+ */
+export type ScoringProfile =
+  | { balanced: {} }
+  | { dateFocused: {} }
+  | { routeFocused: {} }
+  | { interestFocused: {} };
+
+/**
+ * Helper to initiate a match between two trips (no MPC queued)
+ */
+export async function initiateRawMatch(
+  program: Program<Triper>,
+  payer: Keypair,
+  tripA: PublicKey,
+  tripB: PublicKey,
+  options: {
+    profileA?: PublicKey;
+    profileB?: PublicKey;
+    blocklistA?: PublicKey;
+    blocklistB?: PublicKey;
+    scoringProfile?: ScoringProfile;
+    encryptScores?: boolean;
+    budgetWeight?: number;
+    minScore?: number;
+  } = {}
+): Promise<PublicKey> {
+  const matchPDA = deriveMatchPDA(program.programId, tripA, tripB);
+  const { owner: ownerB } = await program.account.trip.fetch(tripB);
+
+  await program.methods
+    .initiateMatch(
+      options.scoringProfile ?? null,
+      options.encryptScores ?? false,
+      options.budgetWeight ?? 0,
+      options.minScore ?? 0
+    )
+    .accountsPartial({
+      payer: payer.publicKey,
+      tripA,
+      tripB,
+      matchRecord: matchPDA,
+      pairHistory: derivePairHistoryPDA(program.programId, tripA, tripB),
+      ownerPairHistory: deriveOwnerPairHistoryPDA(program.programId, payer.publicKey, ownerB),
+      profileA: options.profileA ?? null,
+      profileB: options.profileB ?? null,
+      blocklistA: options.blocklistA ?? deriveBlocklistPDA(program.programId, payer.publicKey),
+      blocklistB: options.blocklistB ?? deriveBlocklistPDA(program.programId, ownerB),
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([payer])
+    .rpc({ commitment: "confirmed" });
+
+  return matchPDA;
+}
+
+/**
+ * Derive a user's blocklist PDA
+ */
+export function deriveBlocklistPDA(programId: PublicKey, owner: PublicKey): PublicKey {
+  const [blocklistPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("blocklist"), owner.toBuffer()],
+    programId
+  );
+  return blocklistPDA;
+}
+
+/**
+ * Helper to create a user profile with placeholder ciphertext
+ * Returns the existing profile if the user already has one (createRawTrip
+ * creates it on demand)
+ */
+export async function createRawUserProfile(
+  program: Program<Triper>,
+  user: Keypair
+): Promise<PublicKey> {
+  const [userProfilePDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("user_profile"), user.publicKey.toBuffer()],
+    program.programId
+  );
+  if (await program.account.userProfile.fetchNullable(userProfilePDA)) {
+    return userProfilePDA;
+  }
+
+  await program.methods
+    .createUserProfile(randomBytes(2 * 32), Array.from(randomBytes(32)))
+    .accountsPartial({
+      userProfile: userProfilePDA,
+      user: user.publicKey,
+      systemProgram: SystemProgram.programId,
+    })
+    .signers([user])
+    .rpc({ commitment: "confirmed" });
+
+  return userProfilePDA;
+}
+
+/**
+ * Helper to get the ProgramConfig PDA, initializing it if needed
+ * The provider wallet is used as both admin and verifier
+ */
+export async function getOrInitProgramConfig(
+  program: Program<Triper>,
+  provider: AnchorProvider
+): Promise<PublicKey> {
+  const [configPDA] = PublicKey.findProgramAddressSync(
+    [Buffer.from("program_config")],
+    program.programId
+  );
+
+  const existing = await provider.connection.getAccountInfo(configPDA);
+  if (!existing) {
+    await program.methods
+      .initializeConfig(provider.wallet.publicKey)
+      .accountsPartial({
+        config: configPDA,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+  }
+
+  return configPDA;
+}
+
+/**
+ * Ten waypoints within a few kilometres of downtown San Francisco
+ */
+export function denseUrbanRoute(): { lat: number; lng: number }[] {
+  return Array.from({ length: 10 }, (_, i) => ({
+    lat: 37.7749 + (i % 5) * 0.006,
+    lng: -122.4194 + Math.floor(i / 5) * 0.008,
+  }));
+}
+
+/**
+ * Ten waypoints spread from San Francisco to New York
+ */
+export function sparseCrossCountryRoute(): { lat: number; lng: number }[] {
+  return Array.from({ length: 10 }, (_, i) => ({
+    lat: 37.7749 + (40.7128 - 37.7749) * (i / 9) + (i % 2 === 0 ? 2 : -2),
+    lng: -122.4194 + (-74.006 + 122.4194) * (i / 9),
+  }));
+}
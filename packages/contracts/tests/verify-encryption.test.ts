@@ -0,0 +1,121 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Triper } from "../target/types/triper";
+import { randomBytes } from "crypto";
+import {
+  awaitComputationFinalization,
+  getArciumEnv,
+  getCompDefAccOffset,
+  deserializeLE,
+  getMXEAccAddress,
+  getMempoolAccAddress,
+  getCompDefAccAddress,
+  getExecutingPoolAccAddress,
+  getComputationAccAddress,
+} from "@arcium-hq/client";
+import * as os from "os";
+import { expect } from "chai";
+import {
+  initCompDef,
+  readKpJson,
+  createRawTrip,
+  createRawUserProfile,
+  createSampleTripData,
+} from "./utils";
+import { createTrip } from "../../../apps/web/src/lib/solana/create-trip";
+
+describe("Encryption Self-Verification", () => {
+  // Configure the client to use the local cluster
+  anchor.setProvider(anchor.AnchorProvider.env());
+  const program = anchor.workspace.Triper as Program<Triper>;
+  const provider = anchor.getProvider() as anchor.AnchorProvider;
+  const arciumEnv = getArciumEnv();
+
+  type Event = anchor.IdlEvents<(typeof program)["idl"]>;
+  const awaitEvent = async <E extends keyof Event>(
+    eventName: E
+  ): Promise<Event[E]> => {
+    let listenerId: number;
+    const event = await new Promise<Event[E]>((res) => {
+      listenerId = program.addEventListener(eventName, (event) => {
+        res(event);
+      });
+    });
+    await program.removeEventListener(listenerId);
+
+    return event;
+  };
+
+  const verifyTrip = async (trip: PublicKey, nonce: Uint8Array) => {
+    const owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
+    const resultPromise = awaitEvent("selfVerificationResult");
+    const computationOffset = new anchor.BN(randomBytes(8), "hex");
+
+    await program.methods
+      .verifyOwnEncryption(
+        computationOffset,
+        new anchor.BN(deserializeLE(nonce).toString())
+      )
+      .accountsPartial({
+        payer: owner.publicKey,
+        computationAccount: getComputationAccAddress(program.programId, computationOffset),
+        clusterAccount: arciumEnv.arciumClusterPubkey,
+        mxeAccount: getMXEAccAddress(program.programId),
+        mempoolAccount: getMempoolAccAddress(program.programId),
+        executingPool: getExecutingPoolAccAddress(program.programId),
+        compDefAccount: getCompDefAccAddress(
+          program.programId,
+          Buffer.from(getCompDefAccOffset("verify_trip_encryption")).readUInt32LE()
+        ),
+        trip,
+      })
+      .signers([owner])
+      .rpc({ commitment: "confirmed" });
+
+    await awaitComputationFinalization(provider, computationOffset, program.programId, "confirmed");
+    return resultPromise;
+  };
+
+  it("Initializes computation definition for verify_trip_encryption", async () => {
+    const owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
+    await initCompDef(
+      program,
+      provider,
+      owner,
+      "verify_trip_encryption",
+      () => program.methods.initVerifyTripEncryptionCompDef()
+    );
+  });
+
+  it("Reports a correctly encrypted trip as valid", async () => {
+    // Trips are indexed off the owner's profile
+    await createRawUserProfile(program, readKpJson(`${os.homedir()}/.config/solana/id.json`));
+    const tripData = createSampleTripData(30 * 24 * 60 * 60);
+    const trip = await createTrip(
+      program,
+      provider,
+      tripData.waypoints,
+      tripData.destination,
+      tripData.startDate,
+      tripData.endDate
+    );
+
+    const result = await verifyTrip(trip.tripPDA, trip.nonce);
+    expect(result.trip.toBase58()).to.equal(trip.tripPDA.toBase58());
+    expect(result.isValid).to.be.true;
+    console.log("✅ Well-formed ciphertext verified");
+  });
+
+  it("Reports a corrupted ciphertext as invalid", async () => {
+    const owner = readKpJson(`${os.homedir()}/.config/solana/id.json`);
+    const trip = await createRawTrip(program, owner, {
+      startDate: Math.floor(Date.now() / 1000) + 60 * 24 * 60 * 60,
+    });
+
+    const result = await verifyTrip(trip, randomBytes(16));
+    expect(result.trip.toBase58()).to.equal(trip.toBase58());
+    expect(result.isValid).to.be.false;
+    console.log("✅ Corrupted ciphertext rejected");
+  });
+});